@@ -0,0 +1,158 @@
+use rock_paper_scissors::{Client, Error, Message, MoveParser, Reveal, RuleSet, Winner};
+use std::{env, io};
+
+// Usage: cargo run --example client [address] [name] [queue <queue> | create <queue> | join <code> | list | watch <match>]
+//        cargo run --example client [address] [name] resume <token> [<move> <nonce>]
+fn main() {
+    let mut args = env::args().skip(1);
+    let address = args.next().unwrap_or_else(|| String::from("127.0.0.1:7878"));
+    let name = args.next().unwrap_or_else(|| String::from("Player"));
+    let command = args.next().unwrap_or_else(|| String::from("queue"));
+    let argument = args.next().unwrap_or_else(|| String::from("default"));
+    // The reveal of a move committed to before the connection dropped.
+    let reveal = args.collect::<Vec<_>>().join(" ");
+
+    let connected = match (command.as_str(), reveal.as_str()) {
+        ("resume", "") => Client::resume(&address, &argument),
+        ("resume", reveal) => reveal.parse().and_then(|reveal| Client::resume_with(&address, &argument, Some(reveal))),
+        _ => Client::connect(&address, &name),
+    };
+
+    let mut client = match connected {
+        Ok(client) => client,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    if command == "list" {
+        match client.list_matches() {
+            Ok(ids) => println!("Live matches: {:?}", ids),
+            Err(err) => println!("{}", err),
+        }
+
+        return;
+    }
+
+    let watching = command == "watch";
+    let joined = match command.as_str() {
+        "create" => client.create_room(&argument).map(|code| println!("Your room code is {}.", code)),
+        "join" => client.join_room(&argument),
+        "resume" => {
+            println!("Back in the match.");
+            Ok(())
+        }
+        "watch" => match argument.parse() {
+            Ok(id) => client.watch(id).map(|_| println!("Watching match {}.", id)),
+            Err(_) => Err(Error::Protocol(format!("'{}' is not a match id", argument))),
+        },
+        _ => client.join_queue(&argument),
+    };
+
+    if let Err(err) = joined {
+        println!("{}", err);
+        return;
+    }
+
+    let mut parser = MoveParser::new();
+
+    loop {
+        let message = match client.receive() {
+            Ok(message) => message,
+            Err(err) => {
+                println!("{}", err);
+                return;
+            }
+        };
+
+        match message {
+            Message::Waiting => println!("Waiting for an opponent..."),
+            Message::Session(token) => println!("If you lose your connection, rejoin with: resume {}", token),
+            Message::Match { name, .. } => println!("You are playing against {}.", name),
+            Message::Settings(settings) => println!("The match is {}.", settings.format),
+            Message::Rules(gestures) => parser = MoveParser::with_rules(RuleSet::new(gestures, Vec::new())),
+            Message::Round(round) => {
+                println!("Round {}", round);
+
+                let user_move = loop {
+                    match parser.read_from(&mut io::stdin().lock(), &mut io::stdout()) {
+                        Ok(user_move) => break user_move,
+                        Err(err) if err.is_invalid() => println!("{}", err),
+                        Err(err) => {
+                            println!("{}", err);
+                            let _ = client.send(&Message::Quit);
+                            return;
+                        }
+                    }
+                };
+
+                // Commit to the move first, so the opponent cannot learn it before they are bound to theirs.
+                let reveal = Reveal::random(user_move);
+                client.pending = Some(reveal);
+
+                if let Err(err) = client.send(&Message::Commit(reveal.commitment())) {
+                    println!("{}", err);
+                    return;
+                }
+
+                if let Some(token) = &client.session {
+                    println!("If you lose your connection during this round, rejoin with: resume {} {}", token, reveal);
+                }
+
+                println!("Waiting for your opponent's move...");
+            }
+            Message::Commit(_) if !watching => match client.pending {
+                Some(reveal) => {
+                    if let Err(err) = client.send(&Message::Reveal(reveal)) {
+                        println!("{}", err);
+                        return;
+                    }
+                }
+                None => {
+                    println!("The move you committed to was lost with your connection.");
+                    return;
+                }
+            },
+            Message::Result { round, moves, scores, .. } if watching => println!(
+                "Round {}: {} against {}, {} - {}",
+                round,
+                moves.user_move.convert_to_string(),
+                moves.enemy_move.convert_to_string(),
+                scores.user_wins,
+                scores.enemy_wins,
+            ),
+            Message::Over { winner, .. } if watching => {
+                println!("The match is over: {}", winner.convert_to_string());
+                return;
+            }
+            Message::Result { moves, winner, scores, .. } => {
+                println!(
+                    "You chose {}. Your opponent chose {}.",
+                    moves.user_move.convert_to_string(),
+                    moves.enemy_move.convert_to_string(),
+                );
+
+                match winner {
+                    Winner::User => println!("You win the round!"),
+                    Winner::Enemy => println!("Your opponent wins the round."),
+                    Winner::Tie => println!("It's a tie!"),
+                }
+
+                println!("Scores -> You: {}, Opponent: {}", scores.user_wins, scores.enemy_wins);
+            }
+            Message::Over { winner, forfeit } => {
+                match (winner, forfeit) {
+                    (Winner::User, true) => println!("Your opponent left. You win!"),
+                    (Winner::User, false) => println!("Game over! You win!"),
+                    (Winner::Enemy, _) => println!("Game over! Your opponent wins."),
+                    (Winner::Tie, _) => println!("Game over! It's a draw."),
+                }
+
+                return;
+            }
+            Message::Error(reason) => println!("{}", reason),
+            _ => (),
+        }
+    }
+}
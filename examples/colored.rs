@@ -1,5 +1,6 @@
 use colored::Colorize;
 use rock_paper_scissors::*;
+use std::io;
 
 fn main() {
     println!("{}", "Welcome to rock paper scissors!".blue().bold());
@@ -9,55 +10,52 @@ fn main() {
 
     println!("{}", "Game will be first to 3 rounds.".blue().bold());
 
-    let mut scores = Scores::new(); // instantiate Scores struct
+    let mut game = Game::new(game_settings); // instantiate a Game session
 
-    let mut round_counter = 0;
-
-    while scores.check_for_winner(&game_settings).is_err() { // checking to see if there has been a winner yet.
-        round_counter += 1;
-        let player_moves = PlayerMoves::build_from_input(); // build a PlayerMoves struct from input
-        let round_winner = player_moves.check_who_wins_round(); // check who wins round based on moves.
+    while !game.is_over() { // checking to see if there has been a winner yet.
+        let user_move = loop { // get the user's move from input
+            match MoveType::read_from(&mut io::stdin().lock(), &mut io::stdout()) {
+                Ok(user_move) => break user_move,
+                Err(err) if err.is_invalid() => println!("{}", err),
+                Err(err) => {
+                    println!("{}", err);
+                    return;
+                }
+            }
+        };
+        let outcome = game.play_round(user_move).unwrap(); // play the round; the game picks the enemy move and updates scores.
 
         println!(
             "{}{}{}",
             "-----Round ".magenta(),
-            round_counter.to_string().magenta(),
+            outcome.round.to_string().magenta(),
             "-----".magenta()
         );
 
         println!(
             "{}{}{}{}{}",
             "User: ".italic(),
-            player_moves.user_move.convert_to_string().italic(),
+            outcome.moves.user_move.convert_to_string().italic(),
             " :: ".italic(),
             "Enemy: ".italic(),
-            player_moves.enemy_move.convert_to_string().italic()
+            outcome.moves.enemy_move.convert_to_string().italic()
         );
 
 
-        match round_winner {
-            Winner::Tie => {
-                println!("{}", "It's a tie!".blue());
-                round_counter -= 1;
-            },
-            Winner::User => {
-                println!("{}", "You win!".green());
-                scores.user_wins += 1;
-            },
-            Winner::Enemy => {
-                println!("{}", "Enemy wins!".red());
-                scores.enemy_wins += 1;
-            }
+        match outcome.winner {
+            Winner::Tie => println!("{}", "It's a tie!".blue()),
+            Winner::User => println!("{}", "You win!".green()),
+            Winner::Enemy => println!("{}", "Enemy wins!".red()),
         }
 
         // print scores
         println!("{}", "[Scores]".magenta().bold());
-        println!("{}{}", "User: ".magenta(), scores.user_wins.to_string().magenta());
-        println!("{}{}", "Enemy: ".magenta(), scores.enemy_wins.to_string().magenta());
+        println!("{}{}", "User: ".magenta(), outcome.scores.user_wins.to_string().magenta());
+        println!("{}{}", "Enemy: ".magenta(), outcome.scores.enemy_wins.to_string().magenta());
     }
 
 
-    if scores.check_for_winner(&game_settings) == Ok(Winner::User) {
+    if game.winner() == Some(Winner::User) {
         println!("{}", "You won the game!".green());
     } else {
         println!("{}", "You lost the game!".red());
@@ -1,51 +1,54 @@
 use rock_paper_scissors::*;
+use std::io;
 
 fn main() {
-    let mut scores = Scores::new();
-
     println!("Welcome to the Rock-Paper-Scissors game!");
     println!("Please enter what you want to play to: ");
 
     let game_settings = loop {
-        match GameSettings::from_user_input() {
+        match GameSettings::read_from(&mut io::stdin().lock()) {
             Ok(game_settings) => break game_settings,
-            Err(err) => println!("Error: {}", err),
+            Err(err) if err.is_invalid() => println!("Error: {}", err),
+            Err(err) => {
+                println!("Error: {}", err);
+                return;
+            }
         }
     };
 
+    let mut game = Game::new(game_settings);
+
     println!();
-    let mut round_counter = 0;
-    while scores.check_for_winner(&game_settings).is_err() {
-        round_counter += 1;
-        println!("Round {}:", round_counter);
-        let player_moves = PlayerMoves::build_from_input();
+    while !game.is_over() {
+        println!("Round {}:", game.history().len() + 1);
+        let user_move = loop {
+            match MoveType::read_from(&mut io::stdin().lock(), &mut io::stdout()) {
+                Ok(user_move) => break user_move,
+                Err(err) if err.is_invalid() => println!("{}", err),
+                Err(err) => {
+                    println!("{}", err);
+                    return;
+                }
+            }
+        };
+        let outcome = game.play_round(user_move).unwrap();
         println!();
-        println!("User: {}", player_moves.user_move.convert_to_string());
-        println!("Enemy: {}", player_moves.enemy_move.convert_to_string());
+        println!("User: {}", outcome.moves.user_move.convert_to_string());
+        println!("Enemy: {}", outcome.moves.enemy_move.convert_to_string());
 
-        let round_winner = player_moves.check_who_wins_round();
         println!();
-        match round_winner {
-            Winner::Tie => {
-                println!("Tie!");
-                round_counter -= 1
-            },
-            Winner::User => {
-                println!("User win");
-                scores.user_wins += 1;
-            },
-            Winner::Enemy => {
-                println!("Enemy win");
-                scores.enemy_wins += 1;
-            }
+        match outcome.winner {
+            Winner::Tie => println!("Tie!"),
+            Winner::User => println!("User win"),
+            Winner::Enemy => println!("Enemy win"),
         }
 
-        println!("Current Scores -> User: {} :: Enemy: {}", scores.user_wins, scores.enemy_wins);
+        println!("Current Scores -> User: {} :: Enemy: {}", outcome.scores.user_wins, outcome.scores.enemy_wins);
     }
 
-    let game_winner = scores.check_for_winner(&game_settings).unwrap(); // can use unwrap here because the while loop
-    // will only break if the return is Ok()
+    let game_winner = game.winner().unwrap(); // can use unwrap here because the while loop
+    // will only break once the game is over
 
 
     println!("Game Winner: {}", game_winner.convert_to_string());
-}
\ No newline at end of file
+}
@@ -1,38 +1,43 @@
-use rock_paper_scissors::{PlayerMoves, Scores, Winner, GameSettings};
+use rock_paper_scissors::{Game, GameSettings, MoveType, Winner};
+use std::io;
 
 fn main() {
-    let mut scores = Scores::new();
-
-    let game_settings = GameSettings::from_first_to(3);
+    let mut game = Game::new(GameSettings::from_first_to(3));
 
     println!("Welcome to Rock-Paper-Scissors!");
 
     // Game loop
-    while scores.check_for_winner(&game_settings).is_err() {
-        let player_moves = PlayerMoves::build_from_input();
-
-        let round_winner = player_moves.check_who_wins_round();
+    while !game.is_over() {
+        let user_move = loop {
+            match MoveType::read_from(&mut io::stdin().lock(), &mut io::stdout()) {
+                Ok(user_move) => break user_move,
+                Err(err) if err.is_invalid() => println!("{}", err),
+                Err(err) => {
+                    println!("{}", err);
+                    return;
+                }
+            }
+        };
+
+        let outcome = game.play_round(user_move).unwrap();
         println!(
             "You chose {}. Enemy chose {}.",
-            player_moves.user_move.convert_to_string(),
-            player_moves.enemy_move.convert_to_string(),
+            outcome.moves.user_move.convert_to_string(),
+            outcome.moves.enemy_move.convert_to_string(),
         );
-        println!("Result: {}", round_winner.convert_to_string());
+        println!("Result: {}", outcome.winner.convert_to_string());
 
-        // Update scores
-        match round_winner {
-            Winner::User => scores.user_wins += 1,
-            Winner::Enemy => scores.enemy_wins += 1,
-            Winner::Tie => (),
+        if outcome.winner == Winner::Tie {
+            println!("It's a tie!");
         }
 
         println!(
             "Current Scores -> You: {}, Enemy: {}",
-            scores.user_wins, scores.enemy_wins
+            outcome.scores.user_wins, outcome.scores.enemy_wins
         );
     }
 
     // Display final results
-    let game_winner = scores.check_for_winner(&game_settings).unwrap();
+    let game_winner = game.winner().unwrap();
     println!("Game over! {}", game_winner.convert_to_string());
-}
\ No newline at end of file
+}
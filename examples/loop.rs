@@ -1,42 +1,39 @@
 use rock_paper_scissors::*;
+use std::io;
 
 
 fn main() {
     let game_settings = GameSettings::from_first_to(3);
 
-    let mut scores = Scores::new();
-
-    let mut round_counter = 0;
+    let mut game = Game::new(game_settings);
 
     loop {
-        round_counter += 1;
-        let player_moves = PlayerMoves::build_from_input();
-
-        let winner = player_moves.check_who_wins_round();
-
-        match winner {
-            Winner::User => {
-                scores.user_wins += 1;
-                println!("User wins round {}", round_counter)
-
-            },
-            Winner::Enemy => {
-                scores.enemy_wins += 1;
-                println!("Enemy wins round {}", round_counter)
-            },
-            Winner::Tie => {
-                println!("It's a tie! Replaying round {}...", round_counter);
-                round_counter -= 1;
-            },
+        let user_move = loop {
+            match MoveType::read_from(&mut io::stdin().lock(), &mut io::stdout()) {
+                Ok(user_move) => break user_move,
+                Err(err) if err.is_invalid() => println!("{}", err),
+                Err(err) => {
+                    println!("{}", err);
+                    return;
+                }
+            }
+        };
+
+        let outcome = game.play_round(user_move).unwrap(); // can use unwrap here because the loop breaks once the game is over
+
+        match outcome.winner {
+            Winner::User => println!("User wins round {}", outcome.round),
+            Winner::Enemy => println!("Enemy wins round {}", outcome.round),
+            Winner::Tie => println!("It's a tie in round {}!", outcome.round),
         }
 
-        if let Ok(winner) = scores.check_for_winner(&game_settings) {
+        if let Some(winner) = outcome.match_winner {
             println!("Game Over! Winner: {}", winner.convert_to_string());
             println!();
             println!("Final Scores:");
-            println!("    User: {}", scores.user_wins);
-            println!("    Enemy: {}", scores.enemy_wins);
+            println!("    User: {}", outcome.scores.user_wins);
+            println!("    Enemy: {}", outcome.scores.enemy_wins);
             break;
         }
     }
-}
\ No newline at end of file
+}
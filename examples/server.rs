@@ -0,0 +1,42 @@
+use rock_paper_scissors::{GameSettings, MatchFormat, RuleSet, Server};
+use std::io::BufRead;
+use std::{env, io, thread};
+
+// Usage: cargo run --example server [address] [first to]
+//
+// Type an admin command (status, queues, rooms or matches) to inspect the lobby while the server runs.
+fn main() {
+    let mut args = env::args().skip(1);
+    let address = args.next().unwrap_or_else(|| String::from("0.0.0.0:7878"));
+    let first_to = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(3);
+
+    let server = match Server::bind(&address, GameSettings::from_first_to(first_to)) {
+        Ok(server) => server,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let lobby = server
+        .lobby
+        .clone()
+        .with_queue("best-of-5", GameSettings::from_format(MatchFormat::BestOf(5)), RuleSet::classic())
+        .with_queue("rpsls", GameSettings::from_first_to(first_to), RuleSet::lizard_spock());
+    let mut server = server.with_lobby(lobby.clone());
+
+    println!("Listening on {} with the queues default (first to {}), best-of-5 and rpsls.", address, first_to);
+
+    thread::spawn(move || {
+        for command in io::stdin().lock().lines().map_while(Result::ok) {
+            match lobby.admin(&command) {
+                Ok(output) => println!("{}", output),
+                Err(err) => println!("{}", err),
+            }
+        }
+    });
+
+    if let Err(err) = server.run() {
+        println!("{}", err);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{Error, Message, PlayerId, PlayerMoves, Reveal, Strategy, Winner, PROTOCOL_VERSIONS};
+use rand::RngCore;
+use std::io::BufReader;
+use std::net::{TcpStream, ToSocketAddrs};
+
+/// # Client Struct
+///
+/// A connection to a `Server`. `Client::connect()` performs the handshake described under `Message`. The client then
+/// tells the server's `Lobby` what it wants with `join_queue()`, `create_room()`, `join_room()` or `watch()`. After
+/// that, `send()` and `receive()` exchange messages one at a time, e.g. to let a person pick every move, and `play()`
+/// plays a whole match with a `Strategy`.
+///
+/// The lobby needs protocol version 2. A server that only speaks version 1 puts the client in its queue right away.
+///
+/// `play()` commits to every move before revealing it, and checks the opponent's reveal against their commitment.
+///
+/// From version 3 on, the server gives each player a session token when their match starts. A player whose
+/// connection drops can take their seat back with `Client::resume()` within the server's grace period.
+///
+/// ## Fields
+///
+/// - `version`: The protocol version agreed on with the server.
+/// - `player`: The id the server gave this player.
+/// - `session`: The token of the player's session, once a match has started.
+/// - `pending`: The reveal of the move `play()` has committed to in the open round, if any. A player who resumes the
+///   match hands it to `Client::resume_with()`, as only it can open their commitment.
+pub struct Client {
+    pub version: u32,
+    pub player: PlayerId,
+    pub session: Option<String>,
+    pub pending: Option<Reveal>,
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    /// Connects to a server and introduces the player as `name`.
+    ///
+    /// Returns `Error::Protocol` if the server turns the client down, e.g. because they share no protocol version.
+    pub fn connect<A: ToSocketAddrs>(address: A, name: &str) -> Result<Client, Error> {
+        Client::connect_with_versions(address, name, PROTOCOL_VERSIONS)
+    }
+
+    /// Connects to a server offering only the given protocol versions.
+    pub fn connect_with_versions<A: ToSocketAddrs>(address: A, name: &str, versions: &[u32]) -> Result<Client, Error> {
+        let stream = TcpStream::connect(address)?;
+        stream.set_nodelay(true)?;
+        let mut client = Client {
+            version: 0,
+            player: PlayerId(0),
+            session: None,
+            pending: None,
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        };
+
+        client.send(&Message::Hello {
+            versions: versions.to_vec(),
+            name: name.to_string(),
+        })?;
+
+        match client.receive()? {
+            Message::Welcome { version, player } => {
+                client.version = version;
+                client.player = player;
+                Ok(client)
+            }
+            Message::Error(reason) => Err(Error::Protocol(reason)),
+            other => Err(Error::Protocol(format!("Expected WELCOME, got '{other}'"))),
+        }
+    }
+
+    /// Reconnects to a match the connection to which was lost, using the token from the player's `session`. The
+    /// server then sends the match, settings and rules again, and the next round unless the player's move for it
+    /// was already in; `play()` carries on from there. A player who had committed to a move in the open round resumes
+    /// with `resume_with()` instead.
+    ///
+    /// Returns `Error::Protocol` if the server speaks no version with sessions, or the match is over or no longer
+    /// waits for the player.
+    pub fn resume<A: ToSocketAddrs>(address: A, token: &str) -> Result<Client, Error> {
+        Client::resume_with(address, token, None)
+    }
+
+    /// Like `resume()`, but `pending` is the reveal of the move the player had committed to, as kept in the `pending`
+    /// of the client that lost its connection. The token and the reveal are all a brand-new client needs, e.g. after
+    /// the program was restarted, to open the player's commitment when the server asks for it.
+    ///
+    /// Returns `Error::Protocol` if the server holds a commitment of the player that `pending` does not open, as the
+    /// round cannot go on without it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rock_paper_scissors::{Client, ConstantStrategy, MoveType, Reveal};
+    ///
+    /// // Written down by the previous client: its session token, and its `pending` reveal as text.
+    /// let token = "9f86d081884c7d659a2feaa0c55ad015";
+    /// let reveal: Reveal = "0 7c211433f02071597741e6ff5a8ea34789abbf43c5e7e7ce0f2b4b79b4fd7a3e".parse().unwrap();
+    ///
+    /// let mut client = Client::resume_with("127.0.0.1:7878", token, Some(reveal)).unwrap();
+    /// client.play(&mut ConstantStrategy::new(MoveType::Rock)).unwrap();
+    /// ```
+    pub fn resume_with<A: ToSocketAddrs>(address: A, token: &str, pending: Option<Reveal>) -> Result<Client, Error> {
+        let mut client = Client::connect(address, "")?;
+
+        if client.version < 3 {
+            return Err(Error::Protocol(String::from("Resuming a match needs protocol version 3")));
+        }
+
+        client.send(&Message::Resume(token.to_string()))?;
+
+        match client.receive()? {
+            Message::Resumed { player, commitment, .. } => {
+                client.pending = match (commitment, pending) {
+                    (None, _) => None,
+                    (Some(commitment), Some(reveal)) if reveal.matches(&commitment) => Some(reveal),
+                    (Some(_), _) => {
+                        return Err(Error::Protocol(String::from(
+                            "The match waits for the reveal of a move this client was not given",
+                        )))
+                    }
+                };
+                client.player = player;
+                client.session = Some(token.to_string());
+                Ok(client)
+            }
+            Message::Error(reason) => Err(Error::Protocol(reason)),
+            other => Err(Error::Protocol(format!("Expected RESUMED, got '{other}'"))),
+        }
+    }
+
+    /// Joins a queue of the server's lobby. The match is played with `play()`, which fails with `Error::Protocol` if
+    /// there is no such queue.
+    pub fn join_queue(&mut self, queue: &str) -> Result<(), Error> {
+        self.lobby()?;
+        self.send(&Message::Queue(queue.to_string()))
+    }
+
+    /// Opens a private room with the settings of `queue` and returns its join code. The match starts once another
+    /// client joins the room and is played with `play()`.
+    pub fn create_room(&mut self, queue: &str) -> Result<String, Error> {
+        self.lobby()?;
+        self.send(&Message::Create(queue.to_string()))?;
+
+        match self.receive()? {
+            Message::Room(code) => Ok(code),
+            Message::Error(reason) => Err(Error::Protocol(reason)),
+            other => Err(Error::Protocol(format!("Expected ROOM, got '{other}'"))),
+        }
+    }
+
+    /// Joins the private room with the join code `code`. The match is played with `play()`, which fails with
+    /// `Error::Protocol` if there is no such room.
+    pub fn join_room(&mut self, code: &str) -> Result<(), Error> {
+        self.lobby()?;
+        self.send(&Message::Join(code.to_string()))
+    }
+
+    /// Returns the ids of the matches being played on the server.
+    pub fn list_matches(&mut self) -> Result<Vec<usize>, Error> {
+        self.lobby()?;
+        self.send(&Message::List)?;
+
+        match self.receive()? {
+            Message::Matches(ids) => Ok(ids),
+            Message::Error(reason) => Err(Error::Protocol(reason)),
+            other => Err(Error::Protocol(format!("Expected MATCHES, got '{other}'"))),
+        }
+    }
+
+    /// Watches the live match `id` and returns the ids of its user and enemy. The server then sends the match's
+    /// settings, rules and results, from the user's point of view, to be read with `receive()` until `Message::Over`.
+    pub fn watch(&mut self, id: usize) -> Result<[PlayerId; 2], Error> {
+        self.lobby()?;
+        self.send(&Message::Watch(id))?;
+
+        match self.receive()? {
+            Message::Watching { user, enemy, .. } => Ok([user, enemy]),
+            Message::Error(reason) => Err(Error::Protocol(reason)),
+            other => Err(Error::Protocol(format!("Expected WATCHING, got '{other}'"))),
+        }
+    }
+
+    fn lobby(&self) -> Result<(), Error> {
+        match self.version {
+            1 => Err(Error::Protocol(String::from("The lobby needs protocol version 2"))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Sends a message to the server.
+    pub fn send(&mut self, message: &Message) -> Result<(), Error> {
+        message.write_to(&mut self.writer)
+    }
+
+    /// Waits for the next message from the server. A session token is also kept in `session`.
+    pub fn receive(&mut self) -> Result<Message, Error> {
+        let message = Message::read_from(&mut self.reader)?;
+
+        if let Message::Session(token) = &message {
+            self.session = Some(token.clone());
+        }
+
+        Ok(message)
+    }
+
+    /// Plays the rest of a match with `strategy` and returns how it ended, from this player's point of view.
+    ///
+    /// The strategy sees the history as an opponent does in a `Game`: `user_move` is the other player's move and
+    /// `enemy_move` its own.
+    ///
+    /// Returns `Error::Protocol` if the server rejects a move, and `Error::CommitmentMismatch` if the opponent's
+    /// reveal does not match their commitment.
+    pub fn play<S: Strategy + ?Sized>(&mut self, strategy: &mut S) -> Result<Winner, Error> {
+        self.play_with(strategy, &mut rand::rng())
+    }
+
+    /// Like `play()`, but the strategy draws from the given random number generator instead of the thread-local one,
+    /// so a seeded bot plays the same moves against the same opponent. The nonces of its commitments still come from
+    /// the thread's cryptographically secure generator.
+    pub fn play_with<S: Strategy + ?Sized>(
+        &mut self,
+        strategy: &mut S,
+        rng: &mut dyn RngCore,
+    ) -> Result<Winner, Error> {
+        let mut history: Vec<PlayerMoves> = Vec::new();
+        let mut opponent = None;
+
+        loop {
+            match self.receive()? {
+                Message::Round(_) => {
+                    let reveal = Reveal::random(strategy.next_move_with(&history, rng));
+                    self.pending = Some(reveal);
+                    self.send(&Message::Commit(reveal.commitment()))?;
+                }
+                // The opponent's commitment arrives once both sides are bound to their moves.
+                Message::Commit(commitment) => {
+                    let reveal = self.pending.ok_or_else(|| Error::Protocol(String::from("There is no move to reveal")))?;
+                    opponent = Some(commitment);
+                    self.send(&Message::Reveal(reveal))?;
+                }
+                // A client that resumed after revealing has no commitment to check the reveal against.
+                Message::Reveal(reveal) if opponent.is_some_and(|commitment| !reveal.matches(&commitment)) => {
+                    return Err(Error::CommitmentMismatch(Winner::Enemy))
+                }
+                Message::Result { moves, .. } => {
+                    self.pending = None;
+                    history.push(moves.swapped());
+                }
+                Message::Over { winner, .. } => return Ok(winner),
+                Message::Error(reason) => return Err(Error::Protocol(reason)),
+                _ => (),
+            }
+        }
+    }
+}
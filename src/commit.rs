@@ -0,0 +1,324 @@
+use crate::{Error, Game, MoveType, PlayerMoves, RoundOutcome, Winner};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Prefixed to every hashed move, so a commitment cannot be mistaken for a hash made for anything else.
+const DOMAIN: &[u8] = b"rock-paper-scissors commit 1";
+
+/// # Commitment Struct
+///
+/// The SHA-256 hash of a move and a secret nonce. Publishing it binds a player to their move without giving the move
+/// away, as the nonce keeps anyone from trying every gesture against the hash.
+///
+/// A commitment is sent as 64 hexadecimal digits; `Display` and `FromStr` convert to and from that form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Commitment(pub [u8; 32]);
+
+impl fmt::Display for Commitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl FromStr for Commitment {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_hex(text.trim())
+            .map(Commitment)
+            .ok_or_else(|| Error::Protocol(format!("'{}' is not a commitment", text.trim())))
+    }
+}
+
+/// # Reveal Struct
+///
+/// A move together with the nonce it was committed with. Sending the reveal after both sides have committed lets the
+/// other side check it against the commitment.
+///
+/// A reveal is sent as the move's `MoveType::index()` and the nonce in hexadecimal, separated by a space; `Display`
+/// and `FromStr` convert to and from that form.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{MoveType, Reveal};
+///
+/// let reveal = Reveal::random(MoveType::Paper);
+/// let commitment = reveal.commitment();
+///
+/// // The commitment travels first, the reveal later.
+/// let received: Reveal = reveal.to_string().parse().unwrap();
+/// assert!(received.matches(&commitment));
+/// assert!(!Reveal::random(MoveType::Paper).matches(&commitment));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reveal {
+    pub move_type: MoveType,
+    pub nonce: [u8; 32],
+}
+
+impl Reveal {
+    /// Creates a reveal for `move_type` with a nonce drawn from `rng`. Use a cryptographically secure generator
+    /// (such as `rand::rng()`) whenever the other side must not be able to guess the nonce.
+    pub fn new(move_type: MoveType, rng: &mut dyn RngCore) -> Reveal {
+        let mut nonce = [0; 32];
+        rng.fill_bytes(&mut nonce);
+
+        Reveal { move_type, nonce }
+    }
+
+    /// Creates a reveal for `move_type` with a nonce from the thread's cryptographically secure generator.
+    pub fn random(move_type: MoveType) -> Reveal {
+        Reveal::new(move_type, &mut rand::rng())
+    }
+
+    /// Returns the commitment to publish before revealing.
+    pub fn commitment(&self) -> Commitment {
+        let index = self.move_type.index().map_or([0, 0], |index| [1, index]);
+
+        Commitment(Sha256::new().chain_update(DOMAIN).chain_update(index).chain_update(self.nonce).finalize().into())
+    }
+
+    /// Returns `true` if this reveal is the one `commitment` was made for.
+    pub fn matches(&self, commitment: &Commitment) -> bool {
+        self.commitment() == *commitment
+    }
+}
+
+impl fmt::Display for Reveal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.move_type.index() {
+            Some(index) => write!(f, "{index} ")?,
+            None => write!(f, "none ")?,
+        }
+
+        write!(f, "{}", Commitment(self.nonce))
+    }
+}
+
+impl FromStr for Reveal {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::Protocol(format!("'{}' is not a reveal", text.trim()));
+        let (move_type, nonce) = text.trim().split_once(' ').ok_or_else(invalid)?;
+
+        let move_type = match move_type {
+            "none" => MoveType::None,
+            index => MoveType::from_index(index.parse().map_err(|_| invalid())?),
+        };
+
+        Ok(Reveal {
+            move_type,
+            nonce: parse_hex(nonce).ok_or_else(invalid)?,
+        })
+    }
+}
+
+fn parse_hex(text: &str) -> Option<[u8; 32]> {
+    if text.len() != 64 || !text.is_ascii() {
+        return None;
+    }
+
+    let mut bytes = [0; 32];
+
+    for (byte, digits) in bytes.iter_mut().zip(text.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(digits).ok()?, 16).ok()?;
+    }
+
+    Some(bytes)
+}
+
+/// # CommitPhase Enum
+///
+/// Where a `CommitRound` stands.
+///
+/// - `CommitPhase::Committing`: Waiting for both commitments. Reveals are refused, as a reveal sent now could still
+///   be answered.
+/// - `CommitPhase::Revealing`: Both sides have committed; waiting for both reveals.
+/// - `CommitPhase::Revealed`: Both moves are known and checked, and the round can be played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CommitPhase {
+    Committing,
+    Revealing,
+    Revealed,
+}
+
+/// # CommitRound Struct
+///
+/// One round of the commit–reveal protocol between the user and the enemy, for two remote players who do not trust
+/// each other. Each side first commits to its move, and only once both commitments are in do they reveal. A reveal
+/// that does not match its commitment is refused, so neither side can change its move after seeing the other's.
+///
+/// The round only checks messages; carrying them between the players is up to the caller, over any transport.
+///
+/// Sides are named by `Winner::User` and `Winner::Enemy`, as in `Game`.
+///
+/// ## Errors
+///
+/// - `Error::CommitmentMismatch`: A reveal does not match the side's commitment.
+/// - `Error::Timeout`: A side did not commit or reveal within the timeout of the phase.
+/// - `Error::Protocol`: A message arrived out of turn, e.g. a second commitment or a reveal before both commitments.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{CommitRound, Game, GameSettings, MoveType, Reveal, Winner};
+///
+/// let mut game = Game::new(GameSettings::from_first_to(1));
+/// let mut round = CommitRound::new();
+///
+/// let user = Reveal::random(MoveType::Rock);
+/// let enemy = Reveal::random(MoveType::Scissors);
+///
+/// round.commit(Winner::User, user.commitment()).unwrap();
+/// round.commit(Winner::Enemy, enemy.commitment()).unwrap();
+///
+/// round.reveal(Winner::User, &user).unwrap();
+/// round.reveal(Winner::Enemy, &enemy).unwrap();
+///
+/// assert_eq!(round.play(&mut game).unwrap().winner, Winner::User);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitRound {
+    pub timeout: Option<Duration>,
+    commitments: [Option<Commitment>; 2],
+    moves: [Option<MoveType>; 2],
+    phase_started: Instant,
+}
+
+impl CommitRound {
+    /// Starts a round without a timeout.
+    pub fn new() -> CommitRound {
+        CommitRound {
+            timeout: None,
+            commitments: [None; 2],
+            moves: [None; 2],
+            phase_started: Instant::now(),
+        }
+    }
+
+    /// Starts a round in which each phase has to be completed within `timeout`.
+    pub fn with_timeout(timeout: Duration) -> CommitRound {
+        CommitRound {
+            timeout: Some(timeout),
+            ..CommitRound::new()
+        }
+    }
+
+    /// Returns the phase the round is in.
+    pub fn phase(&self) -> CommitPhase {
+        match (self.commitments, self.moves) {
+            (_, [Some(_), Some(_)]) => CommitPhase::Revealed,
+            ([Some(_), Some(_)], _) => CommitPhase::Revealing,
+            _ => CommitPhase::Committing,
+        }
+    }
+
+    /// Returns the commitment a side has made, if any.
+    pub fn commitment(&self, side: Winner) -> Option<Commitment> {
+        self.commitments[index(side)?]
+    }
+
+    /// Records a side's commitment.
+    ///
+    /// Returns an error if the phase has timed out, if the side has already committed or if `side` is `Winner::Tie`.
+    pub fn commit(&mut self, side: Winner, commitment: Commitment) -> Result<(), Error> {
+        self.check_timeout(Instant::now())?;
+        let slot = index(side).ok_or_else(|| Error::Protocol(String::from("Only the user and the enemy can commit")))?;
+
+        if self.commitments[slot].is_some() {
+            return Err(Error::Protocol(format!("{} has already committed", side.convert_to_string())));
+        }
+
+        self.commitments[slot] = Some(commitment);
+
+        if self.phase() == CommitPhase::Revealing {
+            self.phase_started = Instant::now();
+        }
+
+        Ok(())
+    }
+
+    /// Records a side's reveal after checking it against the side's commitment.
+    ///
+    /// Returns an error if the phase has timed out, if not both sides have committed yet, if the side has already
+    /// revealed, or if the reveal does not match the commitment.
+    pub fn reveal(&mut self, side: Winner, reveal: &Reveal) -> Result<(), Error> {
+        self.check_timeout(Instant::now())?;
+        let slot = index(side).ok_or_else(|| Error::Protocol(String::from("Only the user and the enemy can reveal")))?;
+
+        match self.phase() {
+            CommitPhase::Committing => {
+                return Err(Error::Protocol(String::from("Moves are revealed once both sides have committed")))
+            }
+            _ if self.moves[slot].is_some() => {
+                return Err(Error::Protocol(format!("{} has already revealed", side.convert_to_string())))
+            }
+            _ => (),
+        }
+
+        if !self.commitments[slot].is_some_and(|commitment| reveal.matches(&commitment)) {
+            return Err(Error::CommitmentMismatch(side));
+        }
+
+        self.moves[slot] = Some(reveal.move_type);
+        Ok(())
+    }
+
+    /// Returns an error naming the side that has not acted if the current phase has lasted longer than the timeout
+    /// at `now`. When both sides are late, the user is named.
+    pub fn check_timeout(&self, now: Instant) -> Result<(), Error> {
+        let late = match self.phase() {
+            CommitPhase::Committing => self.commitments.iter().position(Option::is_none),
+            CommitPhase::Revealing => self.moves.iter().position(Option::is_none),
+            CommitPhase::Revealed => None,
+        };
+
+        match (late, self.timeout) {
+            (Some(slot), Some(timeout)) if now.duration_since(self.phase_started) > timeout => {
+                Err(Error::Timeout([Winner::User, Winner::Enemy][slot]))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns both checked moves once both sides have revealed.
+    pub fn moves(&self) -> Option<PlayerMoves> {
+        match self.moves {
+            [Some(user_move), Some(enemy_move)] => Some(PlayerMoves { user_move, enemy_move }),
+            _ => None,
+        }
+    }
+
+    /// Plays the revealed moves as the next round of `game`.
+    ///
+    /// Returns an error if not both moves have been revealed, or if `game` refuses them.
+    pub fn play(&self, game: &mut Game) -> Result<RoundOutcome, Error> {
+        let moves = self
+            .moves()
+            .ok_or_else(|| Error::Protocol(String::from("Both moves have to be revealed before the round is played")))?;
+
+        game.play_moves(moves)
+    }
+}
+
+impl Default for CommitRound {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn index(side: Winner) -> Option<usize> {
+    match side {
+        Winner::User => Some(0),
+        Winner::Enemy => Some(1),
+        Winner::Tie => None,
+    }
+}
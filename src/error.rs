@@ -0,0 +1,122 @@
+use crate::Winner;
+use std::fmt;
+use std::io;
+
+/// # Error Enum
+///
+/// Everything that can go wrong in this crate.
+///
+/// - `Error::InvalidMove`: A move could not be parsed, or is not part of the rules being played.
+/// - `Error::InvalidSetting`: A game setting was rejected, e.g. a non-numeric "first to" value.
+/// - `Error::InvalidRules`: Rules or payoffs that cannot describe a game.
+/// - `Error::InvalidScores`: Scores that the match format can never produce.
+/// - `Error::InvalidStrategy`: A stored strategy could not be loaded.
+/// - `Error::InvalidSave`: A saved game could not be written, read, or does not describe a game that can be resumed.
+/// - `Error::InvalidReplay`: A replay is malformed, fails its hash check, or does not play out as recorded.
+/// - `Error::CommitmentMismatch`: A side revealed a move that does not match its commitment.
+/// - `Error::Timeout`: A side did not answer in time.
+/// - `Error::Protocol`: A message arrived that is malformed or not allowed at this point of the protocol.
+/// - `Error::MatchAlreadyOver`: A round was played after the match was decided.
+/// - `Error::Eof`: The input ended before a line could be read.
+/// - `Error::Io`: Reading input or writing output failed.
+///
+/// Only `Error::InvalidMove` and `Error::InvalidSetting` are worth asking the user again for; `is_invalid()` tells
+/// them apart from the rest.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{Error, MoveType};
+///
+/// let mut output = Vec::new();
+///
+/// let error = MoveType::read_from(&mut "7\n".as_bytes(), &mut output).unwrap_err();
+/// assert!(matches!(error, Error::InvalidMove(_)));
+/// assert!(error.is_invalid());
+///
+/// let error = MoveType::read_from(&mut "".as_bytes(), &mut output).unwrap_err();
+/// assert!(matches!(error, Error::Eof));
+/// assert_eq!(error.to_string(), "rock-paper-scissors: err: Reached the end of the input");
+/// ```
+#[derive(Debug)]
+pub enum Error {
+    InvalidMove(String),
+    InvalidSetting(String),
+    InvalidRules(String),
+    InvalidScores(String),
+    InvalidStrategy(String),
+    InvalidSave(String),
+    InvalidReplay(String),
+    CommitmentMismatch(Winner),
+    Timeout(Winner),
+    Protocol(String),
+    MatchAlreadyOver,
+    Eof,
+    Io(io::Error),
+}
+
+impl Error {
+    /// Returns `true` if the input was read but rejected, so asking again can succeed.
+    pub fn is_invalid(&self) -> bool {
+        matches!(self, Error::InvalidMove(_) | Error::InvalidSetting(_))
+    }
+}
+
+/// Two `Error::Io` errors are equal when their `io::ErrorKind` is; every other variant compares by value.
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Error::InvalidMove(a), Error::InvalidMove(b))
+            | (Error::InvalidSetting(a), Error::InvalidSetting(b))
+            | (Error::InvalidRules(a), Error::InvalidRules(b))
+            | (Error::InvalidScores(a), Error::InvalidScores(b))
+            | (Error::InvalidStrategy(a), Error::InvalidStrategy(b))
+            | (Error::InvalidSave(a), Error::InvalidSave(b))
+            | (Error::InvalidReplay(a), Error::InvalidReplay(b))
+            | (Error::Protocol(a), Error::Protocol(b)) => a == b,
+            (Error::CommitmentMismatch(a), Error::CommitmentMismatch(b)) | (Error::Timeout(a), Error::Timeout(b)) => a == b,
+            (Error::MatchAlreadyOver, Error::MatchAlreadyOver) | (Error::Eof, Error::Eof) => true,
+            (Error::Io(a), Error::Io(b)) => a.kind() == b.kind(),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rock-paper-scissors: err: ")?;
+
+        match self {
+            Error::InvalidMove(message)
+            | Error::InvalidSetting(message)
+            | Error::InvalidRules(message)
+            | Error::InvalidScores(message)
+            | Error::InvalidStrategy(message)
+            | Error::InvalidSave(message)
+            | Error::InvalidReplay(message)
+            | Error::Protocol(message) => write!(f, "{}", message),
+            Error::CommitmentMismatch(side) => {
+                write!(f, "{}'s reveal does not match their commitment", side.convert_to_string())
+            }
+            Error::Timeout(side) => write!(f, "{} did not answer in time", side.convert_to_string()),
+            Error::MatchAlreadyOver => write!(f, "The match is already over"),
+            Error::Eof => write!(f, "Reached the end of the input"),
+            Error::Io(err) => write!(f, "Failed to read input: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{PlayerId, PlayerMoves, Scores, Winner};
+
+/// # GameEvent Enum
+///
+/// Something that happened during a `Game`. Events are emitted in the order they are listed here, so a round that
+/// decides the match produces `RoundStarted`, `MovesRevealed`, `RoundWon`, `ScoreChanged` and finally `MatchWon`.
+///
+/// - `GameEvent::RoundStarted`: A round is about to be played, with the scores going into it.
+/// - `GameEvent::MovesRevealed`: Both players' moves are known.
+/// - `GameEvent::RoundWon`: The round was won by `winner`, which is never `Winner::Tie`. `winner_id` is the id of the
+///   `Player` who won it.
+/// - `GameEvent::RoundTied`: The round was a tie.
+/// - `GameEvent::TiebreakDecided`: The tie was one too many in a row under `TiePolicy::CapThenTiebreak`, and the
+///   tiebreaker awarded the round to `winner`. It follows the `RoundTied` event of the same round.
+/// - `GameEvent::ScoreChanged`: The scores changed from `previous` to `scores`. Ties are counted, so every round changes them.
+/// - `GameEvent::MatchWon`: The round decided the match. `winner` is `Winner::Tie` for a drawn match, in which case
+///   `winner_id` is `None`.
+///
+/// Rounds are numbered from `1`, like `RoundOutcome::round`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{ConstantStrategy, Game, GameEvent, GameSettings, MoveType, Winner};
+///
+/// let mut game = Game::new(GameSettings::from_first_to(1)).with_opponent(ConstantStrategy::new(MoveType::Scissors));
+/// let events = game.subscribe();
+///
+/// game.play_round(MoveType::Rock).unwrap();
+///
+/// let events: Vec<GameEvent> = events.try_iter().collect();
+/// assert_eq!(events.len(), 5);
+/// assert!(matches!(events[0], GameEvent::RoundStarted { round: 1, .. }));
+/// assert!(matches!(events[4], GameEvent::MatchWon { winner: Winner::User, .. }));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameEvent {
+    RoundStarted { round: usize, scores: Scores },
+    MovesRevealed { round: usize, moves: PlayerMoves },
+    RoundWon { round: usize, moves: PlayerMoves, winner: Winner, winner_id: Option<PlayerId> },
+    RoundTied { round: usize, moves: PlayerMoves },
+    TiebreakDecided { round: usize, winner: Winner },
+    ScoreChanged { round: usize, previous: Scores, scores: Scores },
+    MatchWon { round: usize, winner: Winner, winner_id: Option<PlayerId>, scores: Scores },
+}
+
+/// A listener registered with `Game::add_listener()`.
+pub type GameListener = Box<dyn FnMut(&GameEvent)>;
@@ -0,0 +1,228 @@
+use crate::{Error, Scores, TiePolicy, Winner};
+use std::collections::HashMap;
+use std::fmt;
+
+/// # MatchFormat Enum
+///
+/// Decides when a match is over and who won it.
+///
+/// - `MatchFormat::FirstTo(n)`: The first player to win `n` rounds wins the match.
+/// - `MatchFormat::BestOf(n)`: Best of `n` decisive rounds, so the first to win a majority of them. `n` must be odd.
+/// - `MatchFormat::WinBy { target, margin }`: A player needs at least `target` wins and a lead of at least `margin`,
+///   e.g. first to 11, win by 2.
+/// - `MatchFormat::FixedRounds(n)`: Exactly `n` rounds are played, ties included. The player with more wins takes the
+///   match, and equal wins make it a draw (`Winner::Tie`).
+/// - `MatchFormat::SuddenDeath { first_to, max_rounds }`: Like `FirstTo(first_to)`, but once `max_rounds` rounds have
+///   been played the leader wins. If the scores are level at that point, the next round that is not a tie decides.
+///
+/// How ties count is up to a `TiePolicy`. Without one, as in `winner()` and `check()`, ties are played rounds worth
+/// nothing (`TiePolicy::AwardNobody`). Only `FixedRounds` and `SuddenDeath` look at how many rounds were played.
+///
+/// No count can go past 255, so a match that has not ended by the time either player has won 255 rounds, or 255
+/// rounds were tied, is settled there: the leader wins, and level scores make it a draw. Only matches that might
+/// otherwise never end get that far, such as a `WinBy` in which the players keep trading wins.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{MatchFormat, Scores, Winner};
+///
+/// let format = MatchFormat::WinBy { target: 3, margin: 2 };
+///
+/// assert_eq!(format.winner(&Scores { user_wins: 3, enemy_wins: 2, ties: 0 }), None);
+/// assert_eq!(format.winner(&Scores { user_wins: 5, enemy_wins: 3, ties: 0 }), Some(Winner::User));
+///
+/// let format = MatchFormat::FixedRounds(4);
+/// assert_eq!(format.winner(&Scores { user_wins: 1, enemy_wins: 1, ties: 2 }), Some(Winner::Tie));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MatchFormat {
+    FirstTo(u8),
+    BestOf(u8),
+    WinBy { target: u8, margin: u8 },
+    FixedRounds(u8),
+    SuddenDeath { first_to: u8, max_rounds: u8 },
+}
+
+impl MatchFormat {
+    /// Returns the winner of the match with the given scores, `Some(Winner::Tie)` for a drawn match, or `None` while
+    /// the match is still being played.
+    ///
+    /// This does not check whether the scores could actually occur; scores past the end of the match still report the
+    /// player who is ahead. Use `check()` to reject them. A match still being played when a count reaches 255 is
+    /// settled in favour of the leader, or drawn if the scores are level.
+    pub fn winner(&self, scores: &Scores) -> Option<Winner> {
+        self.winner_with(scores, &TiePolicy::AwardNobody)
+    }
+
+    /// Returns the winner of the match like `winner()`, counting ties as the given `TiePolicy` says.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{MatchFormat, Scores, TiePolicy, Winner};
+    ///
+    /// let format = MatchFormat::FixedRounds(3);
+    /// let scores = Scores { user_wins: 2, enemy_wins: 0, ties: 1 };
+    ///
+    /// assert_eq!(format.winner_with(&scores, &TiePolicy::AwardNobody), Some(Winner::User));
+    /// assert_eq!(format.winner_with(&scores, &TiePolicy::Replay), None);
+    /// ```
+    pub fn winner_with(&self, scores: &Scores, tie_policy: &TiePolicy) -> Option<Winner> {
+        let ties = f64::from(scores.ties) * tie_policy.tie_value();
+        let user = f64::from(scores.user_wins) + ties;
+        let enemy = f64::from(scores.enemy_wins) + ties;
+        let rounds = match tie_policy.counts_ties() {
+            true => scores.rounds(),
+            false => u32::from(scores.user_wins) + u32::from(scores.enemy_wins),
+        };
+
+        let leader = if user > enemy {
+            Winner::User
+        } else if enemy > user {
+            Winner::Enemy
+        } else {
+            Winner::Tie
+        };
+
+        let winner = match *self {
+            MatchFormat::FirstTo(n) => MatchFormat::first_to(user, enemy, leader, n),
+            MatchFormat::BestOf(n) => MatchFormat::first_to(user, enemy, leader, n / 2 + 1),
+            MatchFormat::WinBy { target, margin } => {
+                let (target, margin) = (f64::from(target), f64::from(margin));
+
+                if user >= target && user - enemy >= margin {
+                    Some(Winner::User)
+                } else if enemy >= target && enemy - user >= margin {
+                    Some(Winner::Enemy)
+                } else {
+                    None
+                }
+            }
+            MatchFormat::FixedRounds(n) => match rounds >= u32::from(n) {
+                true => Some(leader),
+                false => None,
+            },
+            MatchFormat::SuddenDeath { first_to, max_rounds } => match MatchFormat::first_to(user, enemy, leader, first_to) {
+                Some(winner) => Some(winner),
+                None if rounds >= u32::from(max_rounds) && leader != Winner::Tie => Some(leader),
+                None => None,
+            },
+        };
+
+        let capped = scores.user_wins == u8::MAX || scores.enemy_wins == u8::MAX || scores.ties == u8::MAX;
+        winner.or(capped.then_some(leader))
+    }
+
+    /// Checks that the format describes a playable match.
+    ///
+    /// Every count must be at least `1`, and `BestOf` needs an odd number of rounds so it cannot end level.
+    pub fn validate(&self) -> Result<(), Error> {
+        let valid = match *self {
+            MatchFormat::FirstTo(n) | MatchFormat::FixedRounds(n) => n > 0,
+            MatchFormat::BestOf(n) => n % 2 == 1,
+            MatchFormat::WinBy { target, margin } => target > 0 && margin > 0,
+            MatchFormat::SuddenDeath { first_to, max_rounds } => first_to > 0 && max_rounds > 0,
+        };
+
+        match valid {
+            true => Ok(()),
+            false => Err(Error::InvalidSetting(format!("{} is not a playable match format", self))),
+        }
+    }
+
+    /// Returns the result of the match like `winner()`, but fails with `Error::InvalidSetting` for an unplayable format
+    /// and with `Error::InvalidScores` for scores the format can never produce: scores that overshoot the end of the
+    /// match (4-1 in a first to 3), or that have both players winning (3-3 in a first to 3).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{Error, MatchFormat, Scores, Winner};
+    ///
+    /// let format = MatchFormat::FirstTo(3);
+    ///
+    /// assert_eq!(format.check(&Scores { user_wins: 3, enemy_wins: 2, ties: 4 }), Ok(Some(Winner::User)));
+    /// assert!(matches!(format.check(&Scores { user_wins: 4, enemy_wins: 1, ties: 0 }), Err(Error::InvalidScores(_))));
+    /// ```
+    pub fn check(&self, scores: &Scores) -> Result<Option<Winner>, Error> {
+        self.check_with(scores, &TiePolicy::AwardNobody)
+    }
+
+    /// Returns the result of the match like `check()`, counting ties as the given `TiePolicy` says.
+    pub fn check_with(&self, scores: &Scores, tie_policy: &TiePolicy) -> Result<Option<Winner>, Error> {
+        self.validate()?;
+
+        let state = (scores.user_wins, scores.enemy_wins, scores.ties);
+
+        match self.reachable(state, tie_policy, &mut HashMap::new()) {
+            true => Ok(self.winner_with(scores, tie_policy)),
+            false => Err(Error::InvalidScores(format!(
+                "{}-{} with {} ties cannot happen in a match of {}",
+                scores.user_wins, scores.enemy_wins, scores.ties, self
+            ))),
+        }
+    }
+
+    /// A state can be reached if it is the start of the match, or if one more round (a win for either player or a tie)
+    /// leads to it from a reachable state in which the match was still being played.
+    fn reachable(&self, state: (u8, u8, u8), tie_policy: &TiePolicy, seen: &mut HashMap<(u8, u8, u8), bool>) -> bool {
+        if state == (0, 0, 0) {
+            return true;
+        }
+
+        if let Some(reachable) = seen.get(&state) {
+            return *reachable;
+        }
+
+        let (user, enemy, ties) = state;
+        let previous = [
+            user.checked_sub(1).map(|user| (user, enemy, ties)),
+            enemy.checked_sub(1).map(|enemy| (user, enemy, ties)),
+            ties.checked_sub(1).map(|ties| (user, enemy, ties)),
+        ];
+
+        let reachable = previous.into_iter().flatten().any(|(user_wins, enemy_wins, ties)| {
+            let scores = Scores {
+                user_wins,
+                enemy_wins,
+                ties,
+            };
+
+            self.winner_with(&scores, tie_policy).is_none() && self.reachable((user_wins, enemy_wins, ties), tie_policy, seen)
+        });
+
+        seen.insert(state, reachable);
+        reachable
+    }
+
+    /// The match is over once a player reaches `n` points. Under `TiePolicy::HalfPoint` a tie can bring both players
+    /// there at once, which is a draw.
+    fn first_to(user: f64, enemy: f64, leader: Winner, n: u8) -> Option<Winner> {
+        match user >= f64::from(n) || enemy >= f64::from(n) {
+            true => Some(leader),
+            false => None,
+        }
+    }
+}
+
+impl Default for MatchFormat {
+    fn default() -> Self {
+        MatchFormat::FirstTo(1)
+    }
+}
+
+impl fmt::Display for MatchFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchFormat::FirstTo(n) => write!(f, "first to {}", n),
+            MatchFormat::BestOf(n) => write!(f, "best of {}", n),
+            MatchFormat::WinBy { target, margin } => write!(f, "first to {}, win by {}", target, margin),
+            MatchFormat::FixedRounds(n) => write!(f, "{} rounds", n),
+            MatchFormat::SuddenDeath { first_to, max_rounds } => {
+                write!(f, "first to {}, sudden death after {} rounds", first_to, max_rounds)
+            }
+        }
+    }
+}
@@ -0,0 +1,530 @@
+use crate::{
+    seeded_rng, Error, GameEvent, GameListener, GameRng, GameSettings, MoveType, Player, PlayerId, PlayerMoves,
+    RandomStrategy, RuleSet, Scores, Strategy, TiePolicy, Winner,
+};
+use std::sync::mpsc;
+
+/// # Round Struct
+///
+/// A finished round of a `Game`: the moves both players made and who won. A tie decided by a tiebreaker records the
+/// tiebreak winner. `winner_id` is the id of the `Player` who won, or `None` for a tie.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Round {
+    pub moves: PlayerMoves,
+    pub winner: Winner,
+    pub winner_id: Option<PlayerId>,
+}
+
+/// # RoundOutcome Struct
+///
+/// What `Game::play_round()` reports back after a round.
+///
+/// - `round`: The 1-based number of the round that was just played. Ties count as rounds.
+/// - `moves`: The moves of both players.
+/// - `winner`: Who won the round, after any tiebreaker.
+/// - `winner_id`: The id of the `Player` who won the round, or `None` for a tie.
+/// - `scores`: The scores after the round.
+/// - `match_winner`: `Some` once the round has decided the match.
+/// - `match_winner_id`: The id of the `Player` who won the match, or `None` while it is being played or if it was drawn.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundOutcome {
+    pub round: usize,
+    pub moves: PlayerMoves,
+    pub winner: Winner,
+    pub winner_id: Option<PlayerId>,
+    pub scores: Scores,
+    pub match_winner: Option<Winner>,
+    pub match_winner_id: Option<PlayerId>,
+}
+
+/// # GameState Struct
+///
+/// Everything needed to resume a `Game` where it left off: its settings, rules, players, scores, history and the
+/// positions of its random number generators. `Game::state()` takes a snapshot and `Game::from_state()` resumes it.
+///
+/// The opponent's `Strategy` and the listeners are not part of the state. A resumed game plays against a random
+/// opponent until `Game::with_opponent()` sets another one; the strategies of this crate relearn what they knew from
+/// the history on their next move.
+///
+/// With the `serde` feature, a `GameState` can be serialized, and `Game::to_json()`, `Game::to_toml()` and
+/// `Game::save()` write it out. `seed` and the positions are written as strings, as TOML and many JSON readers cannot
+/// hold integers that large.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{Game, GameSettings, MoveType};
+///
+/// let mut game = Game::with_seed(GameSettings::from_first_to(3), 9);
+/// game.play_round(MoveType::Rock).unwrap();
+///
+/// let mut resumed = Game::from_state(game.state()).unwrap();
+///
+/// assert_eq!(resumed.history(), game.history());
+/// assert_eq!(resumed.play_round(MoveType::Paper), game.play_round(MoveType::Paper));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameState {
+    pub settings: GameSettings,
+    pub rules: RuleSet,
+    pub players: [Player; 2],
+    pub scores: Scores,
+    pub history: Vec<Round>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::save::text"))]
+    pub seed: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::save::text"))]
+    pub rng_position: u128,
+    #[cfg_attr(feature = "serde", serde(with = "crate::save::text"))]
+    pub tiebreak_position: u128,
+}
+
+/// # Game Struct
+///
+/// A complete match session. A `Game` owns its `GameSettings`, `RuleSet`, `Scores`, the history of every round and the
+/// opponent's `Strategy`, so a front-end only has to pass in the user's moves.
+///
+/// The two sides are `Player`s. The user is `PlayerId(0)`, called "User", and the enemy is `PlayerId(1)`, called
+/// "Enemy", unless `with_players()` names them. `Winner::User` and `Winner::Enemy` stand for these two players, and
+/// `player()` / `score()` translate between the two views.
+///
+/// Every random decision of the session is drawn from a `GameRng` seeded with `seed()`. Two games created with the same
+/// seed, rules and opponent produce the same match when the user plays the same moves.
+///
+/// ## Methods
+///
+/// - **`Game::new()`**: Starts a game against a random opponent with a random seed.
+/// - **`Game::with_seed()`**: Starts a reproducible game from a given seed.
+/// - **`Game::with_rules()`** / **`Game::with_opponent()`**: Swap in other rules or another opponent before playing.
+/// - **`Game::with_players()`**: Names the two sides, e.g. for a human-vs-human or bot-vs-bot game.
+/// - **`Game::play_round()`**: Plays a round with the user's move and updates scores and history.
+/// - **`Game::play_moves()`**: Plays a round with both moves given, e.g. for bot-vs-bot or human-vs-human games.
+/// - **`Game::add_listener()`** / **`Game::subscribe()`**: Receive a `GameEvent` for every step of every round.
+/// - **`Game::state()`** / **`Game::from_state()`**: Take a `GameState` snapshot and resume a game from it.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{ConstantStrategy, Game, GameSettings, MoveType, Winner};
+///
+/// let mut game = Game::new(GameSettings::from_first_to(2))
+///     .with_opponent(ConstantStrategy::new(MoveType::Scissors));
+///
+/// let outcome = game.play_round(MoveType::Rock).unwrap();
+/// assert_eq!(outcome.winner, Winner::User);
+/// assert_eq!(outcome.match_winner, None);
+///
+/// let outcome = game.play_round(MoveType::Rock).unwrap();
+/// assert_eq!(outcome.match_winner, Some(Winner::User));
+/// assert!(game.is_over());
+/// assert!(game.play_round(MoveType::Rock).is_err());
+/// ```
+pub struct Game {
+    settings: GameSettings,
+    rules: RuleSet,
+    scores: Scores,
+    history: Vec<Round>,
+    players: [Player; 2],
+    opponent: Box<dyn Strategy>,
+    default_opponent: bool,
+    rng: GameRng,
+    tiebreak_rng: GameRng,
+    seed: u64,
+    listeners: Vec<GameListener>,
+}
+
+impl Game {
+    /// Creates a game with the classic rules against a uniformly random opponent, using a random seed.
+    pub fn new(settings: GameSettings) -> Game {
+        Game::with_seed(settings, rand::random())
+    }
+
+    /// Creates a game with the classic rules against a uniformly random opponent, drawing every random decision from
+    /// a `GameRng` seeded with `seed`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{Game, GameSettings, MoveType};
+    ///
+    /// let mut first = Game::with_seed(GameSettings::from_first_to(3), 7);
+    /// let mut second = Game::with_seed(GameSettings::from_first_to(3), 7);
+    ///
+    /// while !first.is_over() {
+    ///     assert_eq!(first.play_round(MoveType::Paper), second.play_round(MoveType::Paper));
+    /// }
+    /// ```
+    pub fn with_seed(settings: GameSettings, seed: u64) -> Game {
+        Game {
+            settings,
+            rules: RuleSet::classic(),
+            scores: Scores::new(),
+            history: Vec::new(),
+            players: [Player::human(PlayerId(0), "User"), Player::bot(PlayerId(1), "Enemy")],
+            opponent: Box::new(RandomStrategy::new()),
+            default_opponent: true,
+            rng: seeded_rng(seed),
+            tiebreak_rng: tiebreak_rng(seed),
+            seed,
+            listeners: Vec::new(),
+        }
+    }
+
+    /// Replaces the rules of the game. While the opponent is still the default random one, it is switched to pick
+    /// randomly from the new rules; an opponent set with `with_opponent()` is kept, whichever order they are called in.
+    pub fn with_rules(mut self, rules: RuleSet) -> Game {
+        if self.default_opponent {
+            self.opponent = Box::new(RandomStrategy::with_rules(rules.clone()));
+        }
+        self.rules = rules;
+        self
+    }
+
+    /// Replaces the two players. `user` plays the moves passed to `play_round()` and `enemy` the opponent's moves.
+    ///
+    /// Returns an error if both players have the same id, as results could not tell them apart.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{ConstantStrategy, Game, GameSettings, MoveType, Player, PlayerId};
+    ///
+    /// let mut game = Game::new(GameSettings::from_first_to(1))
+    ///     .with_players(Player::human(PlayerId(7), "Ada"), Player::human(PlayerId(9), "Grace"))
+    ///     .unwrap()
+    ///     .with_opponent(ConstantStrategy::new(MoveType::Rock));
+    ///
+    /// game.play_round(MoveType::Scissors).unwrap();
+    ///
+    /// assert_eq!(game.winning_player().unwrap().name, "Grace");
+    /// assert_eq!(game.score(PlayerId(9)), Some(1));
+    /// assert_eq!(game.score(PlayerId(7)), Some(0));
+    ///
+    /// let twins = Game::new(GameSettings::from_first_to(1))
+    ///     .with_players(Player::human(PlayerId(3), "Ada"), Player::human(PlayerId(3), "Grace"));
+    /// assert!(twins.is_err());
+    /// ```
+    pub fn with_players(mut self, user: Player, enemy: Player) -> Result<Game, Error> {
+        check_players(&user, &enemy)?;
+
+        self.players = [user, enemy];
+        Ok(self)
+    }
+
+    /// Replaces the opponent's strategy.
+    pub fn with_opponent<S: Strategy + 'static>(mut self, opponent: S) -> Game {
+        self.opponent = Box::new(opponent);
+        self.default_opponent = false;
+        self
+    }
+
+    /// Registers a listener that is called with every `GameEvent` the game emits, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{Game, GameEvent, GameSettings, MoveType};
+    ///
+    /// let mut game = Game::new(GameSettings::from_first_to(3));
+    /// game.add_listener(|event| {
+    ///     if let GameEvent::MovesRevealed { moves, .. } = event {
+    ///         println!("Enemy played {}", moves.enemy_move.convert_to_string());
+    ///     }
+    /// });
+    ///
+    /// game.play_round(MoveType::Rock).unwrap();
+    /// ```
+    pub fn add_listener<F: FnMut(&GameEvent) + 'static>(&mut self, listener: F) {
+        self.listeners.push(Box::new(listener));
+    }
+
+    /// Returns a channel that receives a copy of every `GameEvent` the game emits from now on.
+    ///
+    /// Dropping the receiver is fine; the game keeps playing and simply stops sending.
+    pub fn subscribe(&mut self) -> mpsc::Receiver<GameEvent> {
+        let (sender, receiver) = mpsc::channel();
+
+        self.add_listener(move |event| {
+            let _ = sender.send(event.clone());
+        });
+
+        receiver
+    }
+
+    /// Plays a round with the user's move: asks the opponent for its move, decides the winner, updates the scores
+    /// and records the round.
+    ///
+    /// Returns an error if the match is already over or if `user_move` is not part of the rules.
+    pub fn play_round(&mut self, user_move: MoveType) -> Result<RoundOutcome, Error> {
+        self.start_round(&[user_move])?;
+
+        let history = self.moves();
+        let moves = PlayerMoves {
+            user_move,
+            enemy_move: self.opponent.next_move_with(&history, &mut self.rng),
+        };
+
+        Ok(self.finish_round(moves))
+    }
+
+    /// Plays a round in which both moves are given, bypassing the opponent's strategy. This lets two bots, or two
+    /// humans, play each other through the same session.
+    ///
+    /// Returns an error if the match is already over or if either move is not part of the rules.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{Game, GameSettings, MoveType, PlayerMoves, Winner};
+    ///
+    /// let mut game = Game::new(GameSettings::from_first_to(1));
+    /// let moves = PlayerMoves { user_move: MoveType::Rock, enemy_move: MoveType::Paper };
+    ///
+    /// assert_eq!(game.play_moves(moves).unwrap().match_winner, Some(Winner::Enemy));
+    /// ```
+    pub fn play_moves(&mut self, moves: PlayerMoves) -> Result<RoundOutcome, Error> {
+        self.start_round(&[moves.user_move, moves.enemy_move])?;
+
+        Ok(self.finish_round(moves))
+    }
+
+    fn start_round(&mut self, moves: &[MoveType]) -> Result<(), Error> {
+        if self.is_over() {
+            return Err(Error::MatchAlreadyOver);
+        }
+
+        if let Some(invalid) = moves.iter().find(|m| !self.rules.contains(m)) {
+            return Err(Error::InvalidMove(format!("{} is not part of the rules", self.rules.name(invalid))));
+        }
+
+        self.emit(GameEvent::RoundStarted {
+            round: self.history.len() + 1,
+            scores: self.scores.clone(),
+        });
+
+        Ok(())
+    }
+
+    fn finish_round(&mut self, moves: PlayerMoves) -> RoundOutcome {
+        let round = self.history.len() + 1;
+        let previous = self.scores.clone();
+        self.emit(GameEvent::MovesRevealed { round, moves });
+
+        let mut winner = moves.check_who_wins_round_with(&self.rules);
+
+        match winner {
+            Winner::Tie => self.emit(GameEvent::RoundTied { round, moves }),
+            _ => self.emit(GameEvent::RoundWon {
+                round,
+                moves,
+                winner,
+                winner_id: self.player_id(winner),
+            }),
+        }
+
+        if let TiePolicy::CapThenTiebreak { max_ties, tiebreaker } = self.settings.tie_policy {
+            let ties_in_a_row = self.history.iter().rev().take_while(|round| round.winner == Winner::Tie).count();
+
+            if winner == Winner::Tie && ties_in_a_row >= usize::from(max_ties) {
+                winner = tiebreaker.decide(&mut self.tiebreak_rng);
+                self.emit(GameEvent::TiebreakDecided { round, winner });
+            }
+        }
+
+        match winner {
+            Winner::User => self.scores.user_wins += 1,
+            Winner::Enemy => self.scores.enemy_wins += 1,
+            Winner::Tie => self.scores.ties += 1,
+        }
+
+        let winner_id = self.player_id(winner);
+        self.history.push(Round { moves, winner, winner_id });
+
+        if self.scores != previous {
+            self.emit(GameEvent::ScoreChanged {
+                round,
+                previous,
+                scores: self.scores.clone(),
+            });
+        }
+
+        let match_winner = self.winner();
+        let match_winner_id = match_winner.and_then(|winner| self.player_id(winner));
+
+        if let Some(winner) = match_winner {
+            self.emit(GameEvent::MatchWon {
+                round,
+                winner,
+                winner_id: match_winner_id,
+                scores: self.scores.clone(),
+            });
+        }
+
+        RoundOutcome {
+            round,
+            moves,
+            winner,
+            winner_id,
+            scores: self.scores.clone(),
+            match_winner,
+            match_winner_id,
+        }
+    }
+
+    /// Returns the winner of the match, or `None` while it is still being played.
+    pub fn winner(&self) -> Option<Winner> {
+        self.scores.winner(&self.settings)
+    }
+
+    /// Returns the player who won the match, or `None` while it is still being played or if it ended in a draw.
+    pub fn winning_player(&self) -> Option<&Player> {
+        self.winner().and_then(|winner| self.player(winner))
+    }
+
+    /// Returns the player a `Winner` stands for, or `None` for `Winner::Tie`.
+    pub fn player(&self, winner: Winner) -> Option<&Player> {
+        match winner {
+            Winner::User => Some(&self.players[0]),
+            Winner::Enemy => Some(&self.players[1]),
+            Winner::Tie => None,
+        }
+    }
+
+    /// Returns the `Winner` that stands for the player with the given id, or `None` if the player is not in the game.
+    pub fn side(&self, id: PlayerId) -> Option<Winner> {
+        if self.players[0].id == id {
+            Some(Winner::User)
+        } else if self.players[1].id == id {
+            Some(Winner::Enemy)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of rounds won by the player with the given id, or `None` if the player is not in the game.
+    pub fn score(&self, id: PlayerId) -> Option<u8> {
+        match self.side(id)? {
+            Winner::User => Some(self.scores.user_wins),
+            Winner::Enemy => Some(self.scores.enemy_wins),
+            Winner::Tie => None,
+        }
+    }
+
+    /// Returns both players, the user first.
+    pub fn players(&self) -> &[Player; 2] {
+        &self.players
+    }
+
+    /// Returns `true` once the match has a winner.
+    pub fn is_over(&self) -> bool {
+        self.winner().is_some()
+    }
+
+    /// Returns every round played so far, in order.
+    pub fn history(&self) -> &[Round] {
+        &self.history
+    }
+
+    /// Returns the moves of every round played so far, in order.
+    pub fn moves(&self) -> Vec<PlayerMoves> {
+        self.history.iter().map(|round| round.moves).collect()
+    }
+
+    /// Returns the current scores.
+    pub fn scores(&self) -> &Scores {
+        &self.scores
+    }
+
+    /// Returns the settings the match is played with.
+    pub fn settings(&self) -> &GameSettings {
+        &self.settings
+    }
+
+    /// Returns the rules the match is played with.
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// Returns the seed of the game's random number generator.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns a snapshot of the game that `Game::from_state()` can resume.
+    pub fn state(&self) -> GameState {
+        GameState {
+            settings: self.settings.clone(),
+            rules: self.rules.clone(),
+            players: self.players.clone(),
+            scores: self.scores.clone(),
+            history: self.history.clone(),
+            seed: self.seed,
+            rng_position: self.rng.get_word_pos(),
+            tiebreak_position: self.tiebreak_rng.get_word_pos(),
+        }
+    }
+
+    /// Resumes a game from a snapshot taken by `Game::state()`, against a random opponent.
+    ///
+    /// Returns an error if the match format could never produce the scores, or if the history holds a move that is not
+    /// part of the rules.
+    pub fn from_state(state: GameState) -> Result<Game, Error> {
+        let mut moves = state.history.iter().flat_map(|round| [round.moves.user_move, round.moves.enemy_move]);
+
+        if let Some(invalid) = moves.find(|gesture| !state.rules.contains(gesture)) {
+            return Err(Error::InvalidSave(format!("{} is not part of the rules", state.rules.name(&invalid))));
+        }
+
+        state.settings.format.check_with(&state.scores, &state.settings.tie_policy)?;
+
+        let [user, enemy] = &state.players;
+        check_players(user, enemy).map_err(|err| Error::InvalidSave(err.to_string()))?;
+
+        let mut rng = seeded_rng(state.seed);
+        rng.set_word_pos(state.rng_position);
+
+        let mut tiebreak_rng = tiebreak_rng(state.seed);
+        tiebreak_rng.set_word_pos(state.tiebreak_position);
+
+        Ok(Game {
+            opponent: Box::new(RandomStrategy::with_rules(state.rules.clone())),
+            default_opponent: true,
+            settings: state.settings,
+            rules: state.rules,
+            scores: state.scores,
+            history: state.history,
+            players: state.players,
+            rng,
+            tiebreak_rng,
+            seed: state.seed,
+            listeners: Vec::new(),
+        })
+    }
+
+    fn player_id(&self, winner: Winner) -> Option<PlayerId> {
+        self.player(winner).map(|player| player.id)
+    }
+
+    fn emit(&mut self, event: GameEvent) {
+        for listener in &mut self.listeners {
+            listener(&event);
+        }
+    }
+}
+
+fn check_players(user: &Player, enemy: &Player) -> Result<(), Error> {
+    match user.id == enemy.id {
+        true => Err(Error::InvalidSetting(format!("Player id {} is used twice", user.id))),
+        false => Ok(()),
+    }
+}
+
+/// Tiebreakers draw from their own stream of the seed, so they come out the same whatever the opponent drew. This is
+/// what lets a `Replay` re-simulate a match from its moves alone.
+fn tiebreak_rng(seed: u64) -> GameRng {
+    let mut rng = seeded_rng(seed);
+    rng.set_stream(1);
+    rng
+}
@@ -0,0 +1,207 @@
+use crate::{Error, MoveType, Player, PlayerId, RuleSet};
+
+/// # GroupRound Struct
+///
+/// A finished round of a `GroupGame`.
+///
+/// - `round`: The 1-based number of the round. Draws count as rounds.
+/// - `moves`: Every player still in the match with the gesture they threw, as `(player, move)` pairs.
+/// - `eliminated`: The players knocked out by this round. Empty for a draw.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupRound {
+    pub round: usize,
+    pub moves: Vec<(PlayerId, MoveType)>,
+    pub eliminated: Vec<PlayerId>,
+}
+
+/// # GroupRoundOutcome Struct
+///
+/// What `GroupGame::play_round()` reports back after a round.
+///
+/// - `round`: The round that was just played.
+/// - `remaining`: The players still in the match after the round.
+/// - `match_winner`: `Some(player)` once only one player remains.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupRoundOutcome {
+    pub round: GroupRound,
+    pub remaining: Vec<PlayerId>,
+    pub match_winner: Option<PlayerId>,
+}
+
+/// # GroupGame Struct
+///
+/// An elimination match for any number of players who throw at the same time, like an office game of 3 to 10 people.
+///
+/// Every round, each remaining player throws a gesture and the holders of the gestures
+/// returned by `RuleSet::losing_gestures()` are eliminated. With the classic rules, a round in which exactly two
+/// gestures appear knocks out everyone holding the losing one, and a round with one or all three gestures is a draw.
+/// The match runs until a single player remains.
+///
+/// ## Methods
+///
+/// - **`GroupGame::new()`**: Starts a match for the given number of players with the classic rules.
+/// - **`GroupGame::with_players()`**: Starts a match for named players.
+/// - **`GroupGame::with_rules()`**: Swaps in other rules before playing.
+/// - **`GroupGame::play_round()`**: Plays a round with one move per remaining player.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{GroupGame, MoveType, PlayerId};
+///
+/// let mut game = GroupGame::new(4).unwrap();
+///
+/// // Three gestures appear: a draw.
+/// let outcome = game.play_round(&[MoveType::Rock, MoveType::Paper, MoveType::Scissors, MoveType::Rock]).unwrap();
+/// assert!(outcome.round.eliminated.is_empty());
+///
+/// // Paper covers Rock: players 0 and 3 are out.
+/// let outcome = game.play_round(&[MoveType::Rock, MoveType::Paper, MoveType::Paper, MoveType::Rock]).unwrap();
+/// assert_eq!(outcome.round.eliminated, vec![PlayerId(0), PlayerId(3)]);
+/// assert_eq!(outcome.remaining, vec![PlayerId(1), PlayerId(2)]);
+///
+/// // The remaining players throw in order of `remaining()`.
+/// let outcome = game.play_round(&[MoveType::Scissors, MoveType::Paper]).unwrap();
+/// assert_eq!(outcome.match_winner, Some(PlayerId(1)));
+/// assert_eq!(game.winning_player().unwrap().name, "Player 2");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupGame {
+    rules: RuleSet,
+    players: Vec<Player>,
+    remaining: Vec<PlayerId>,
+    history: Vec<GroupRound>,
+}
+
+impl GroupGame {
+    /// Creates a match for `players` human players with the classic rules. They get the ids `PlayerId(0)` up to
+    /// `PlayerId(players - 1)` and are named "Player 1", "Player 2" and so on.
+    ///
+    /// Returns an error for fewer than two players.
+    pub fn new(players: usize) -> Result<GroupGame, Error> {
+        GroupGame::with_players(
+            (0..players)
+                .map(|index| Player::human(PlayerId(index), &format!("Player {}", index + 1)))
+                .collect(),
+        )
+    }
+
+    /// Creates a match for the given players with the classic rules. The players throw in the order given.
+    ///
+    /// Returns an error for fewer than two players or if two players share an id.
+    pub fn with_players(players: Vec<Player>) -> Result<GroupGame, Error> {
+        if players.len() < 2 {
+            return Err(Error::InvalidSetting(String::from("A group game needs at least 2 players")));
+        }
+
+        for (index, player) in players.iter().enumerate() {
+            if players[..index].iter().any(|other| other.id == player.id) {
+                return Err(Error::InvalidSetting(format!("Player id {} is used twice", player.id)));
+            }
+        }
+
+        Ok(GroupGame {
+            rules: RuleSet::classic(),
+            remaining: players.iter().map(|player| player.id).collect(),
+            players,
+            history: Vec::new(),
+        })
+    }
+
+    /// Replaces the rules of the match.
+    pub fn with_rules(mut self, rules: RuleSet) -> GroupGame {
+        self.rules = rules;
+        self
+    }
+
+    /// Plays a round. `moves` holds one move for every remaining player, in the order of `remaining()`.
+    ///
+    /// Returns an error if the match is already over, if the number of moves does not match the number of remaining
+    /// players, or if a move is not part of the rules.
+    pub fn play_round(&mut self, moves: &[MoveType]) -> Result<GroupRoundOutcome, Error> {
+        if self.is_over() {
+            return Err(Error::MatchAlreadyOver);
+        }
+
+        if moves.len() != self.remaining.len() {
+            return Err(Error::InvalidMove(format!(
+                "Expected {} moves, one for every remaining player, but got {}",
+                self.remaining.len(),
+                moves.len()
+            )));
+        }
+
+        if let Some(invalid) = moves.iter().find(|m| !self.rules.contains(m)) {
+            return Err(Error::InvalidMove(format!("{} is not part of the rules", self.rules.name(invalid))));
+        }
+
+        let losing = self.rules.losing_gestures(moves);
+        let moves: Vec<(PlayerId, MoveType)> = self.remaining.iter().copied().zip(moves.iter().copied()).collect();
+        let eliminated: Vec<PlayerId> = moves
+            .iter()
+            .filter(|(_, move_type)| losing.contains(move_type))
+            .map(|(player, _)| *player)
+            .collect();
+
+        self.remaining.retain(|player| !eliminated.contains(player));
+
+        let round = GroupRound {
+            round: self.history.len() + 1,
+            moves,
+            eliminated,
+        };
+        self.history.push(round.clone());
+
+        Ok(GroupRoundOutcome {
+            round,
+            remaining: self.remaining.clone(),
+            match_winner: self.winner(),
+        })
+    }
+
+    /// Returns the last player standing, or `None` while the match is still being played.
+    pub fn winner(&self) -> Option<PlayerId> {
+        match self.remaining[..] {
+            [winner] => Some(winner),
+            _ => None,
+        }
+    }
+
+    /// Returns the last player standing, or `None` while the match is still being played.
+    pub fn winning_player(&self) -> Option<&Player> {
+        self.winner().and_then(|id| self.player(id))
+    }
+
+    /// Returns the player with the given id.
+    pub fn player(&self, id: PlayerId) -> Option<&Player> {
+        self.players.iter().find(|player| player.id == id)
+    }
+
+    /// Returns `true` once a single player remains.
+    pub fn is_over(&self) -> bool {
+        self.winner().is_some()
+    }
+
+    /// Returns every player the match started with.
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// Returns the players still in the match, in the order they throw.
+    pub fn remaining(&self) -> &[PlayerId] {
+        &self.remaining
+    }
+
+    /// Returns every round played so far, in order.
+    pub fn history(&self) -> &[GroupRound] {
+        &self.history
+    }
+
+    /// Returns the rules the match is played with.
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+}
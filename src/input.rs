@@ -0,0 +1,13 @@
+use crate::Error;
+use std::io::BufRead;
+
+/// Reads one line from `input` without its line ending, failing with `Error::Eof` at the end of the input.
+pub(crate) fn read_line<R: BufRead + ?Sized>(input: &mut R) -> Result<String, Error> {
+    let mut line = String::new();
+
+    if input.read_line(&mut line)? == 0 {
+        return Err(Error::Eof);
+    }
+
+    Ok(line.trim().to_string())
+}
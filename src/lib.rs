@@ -27,32 +27,98 @@
 //!    Tracks the cumulative scores of a session and provides methods to check if there's an overall winner based on predefined game settings.
 //!
 //! 5. **GameSettings Struct**
-//!    Offers customizable configurations for game-winning conditions, such as "first to 3 wins" or other scenarios.
+//!    Offers customizable configurations for game-winning conditions through a `MatchFormat`, such as "first to 3 wins",
+//!    "best of 5", "first to 11, win by 2", a fixed number of rounds with draws allowed, or sudden death after a cap, and a
+//!    `TiePolicy` deciding whether ties are replayed, worth nothing, worth half a point, or broken after too many in a row.
+//!
+//! 6. **RuleSet Struct**
+//!    Defines which gestures are in play and which gesture beats which. Ships with the classic rules, Rock-Paper-Scissors-Lizard-Spock
+//!    and generated balanced RPS-N variants (RPS-7, RPS-15, RPS-25, RPS-101, ...).
+//!
+//! 7. **Strategy Trait**
+//!    Chooses the enemy's next move from the match history. Ships with random, constant, cycling, copying and countering opponents,
+//!    plus an adaptive Markov-chain opponent that learns the user's patterns and an ensemble "hard mode" opponent.
+//!
+//! 8. **PayoffMatrix Struct**
+//!    States arbitrary zero-sum payoffs and solves them for the mixed-strategy Nash equilibrium, the game value and the
+//!    exploitability of a given strategy.
+//!
+//! 9. **RegretTrainer Struct**
+//!    Trains opponent strategies offline through regret-matching self-play, converging toward the equilibrium of any rule set.
+//!
+//! 10. **Game Struct**
+//!     A complete match session that owns the settings, rules, scores, round history and opponent. Feed it the user's moves
+//!     with `play_round()` and it reports each round's outcome and when the match is over.
+//!
+//! 11. **GameEvent Enum**
+//!     Typed events a `Game` emits to registered listeners or a channel as rounds start, moves are revealed, rounds are won
+//!     or tied, scores change and the match is won.
+//!
+//! 12. **GroupGame Struct**
+//!     An elimination match for three or more players throwing at once. Each round knocks out the holders of the losing
+//!     gesture, or is a draw, until one player remains.
+//!
+//! 13. **Player Struct**
+//!     Gives the sides of a match an id, a display name and a kind (human, bot or remote), so results can be reported per
+//!     player instead of as `User` and `Enemy`.
+//!
+//! 14. **Tournament Struct**
+//!     Runs round-robin, single or double elimination and Swiss tournaments between bot strategies, with byes for odd
+//!     fields and standings broken by head-to-head results and Buchholz scores.
+//!
+//! 15. **RatingSystem Trait**
+//!     Keeps `Elo` or `Glicko2` ratings for players across finished games and tournament matches, and ranks them on a
+//!     leaderboard.
+//!
+//! 16. **Replay Struct**
+//!     Records a match to a compact, versioned and hashed text file, and re-simulates it to check that every round and
+//!     the final scores come out as recorded.
+//!
+//! 17. **CommitRound Struct**
+//!     A commit–reveal round for remote players: both sides publish a hash of their move and a secret nonce before
+//!     revealing, so neither can change their move after seeing the other's.
+//!
+//! 18. **Server and Client Structs**
+//!     Play matches across machines over TCP, with a documented line-based protocol (see `Message`) that negotiates
+//!     its version when a client connects. Every round is played as a `CommitRound`. Players who lose their
+//!     connection resume their match, pending commitment included, with a session token within a grace period, or
+//!     forfeit it under a configurable `ForfeitRule`.
+//!
+//! 19. **Lobby Struct**
+//!     The server's matchmaking: queues with their own settings and rules, private rooms joined by code, spectators
+//!     following live matches, and a status that admin commands print.
+//!
+//! 20. **Error Enum**
+//!     The single error type of the crate. It separates rejected input, which is worth asking for again, from a closed or
+//!     failing input and from misuse such as playing on after the match is over.
+//!
+//! ## Cargo Features
+//!
+//! - **`serde`**: Derives `Serialize` and `Deserialize` for the public data types and adds `Game::to_json()`,
+//!   `Game::to_toml()`, `Game::save()` and their loading counterparts, so a match can be stored and resumed after a
+//!   restart.
 //!
 //! ## Example Usage
 //! Create and run a short game loop of "Rock, Paper, Scissors":
 //!
 //! ```no_run
-//! use rock_paper_scissors::{PlayerMoves, Scores, Winner, GameSettings};
+//! use rock_paper_scissors::{Game, GameSettings, PlayerMoves, Winner};
 //!
 //! fn main() {
-//!     let mut scores = Scores::new();
-//!     let game_settings = GameSettings::from_first_to(3);
+//!     let mut game = Game::new(GameSettings::from_first_to(3));
 //!
-//!     while scores.check_for_winner(&game_settings).is_err() {
-//!         let player_moves = PlayerMoves::build_from_input();
-//!         let round_winner = player_moves.check_who_wins_round();
+//!     while !game.is_over() {
+//!         let user_move = PlayerMoves::build_from_input().user_move;
+//!         let outcome = game.play_round(user_move).unwrap();
 //!
-//!         match round_winner {
-//!             Winner::User => scores.user_wins += 1,
-//!             Winner::Enemy => scores.enemy_wins += 1,
-//!             Winner::Tie => println!("It's a tie!"),
+//!         if outcome.winner == Winner::Tie {
+//!             println!("It's a tie!");
 //!         }
 //!
-//!         println!("Scores -> User: {}, Enemy: {}", scores.user_wins, scores.enemy_wins);
+//!         println!("Scores -> User: {}, Enemy: {}", outcome.scores.user_wins, outcome.scores.enemy_wins);
 //!     }
 //!
-//!     let game_winner = scores.check_for_winner(&game_settings).unwrap();
+//!     let game_winner = game.winner().unwrap();
 //!     println!("Game Winner: {}", game_winner.convert_to_string());
 //! }
 //! ```
@@ -64,8 +130,83 @@
 //! ## Contributing
 //! Contributions such as bug fixing, feature additions, and code improvements are welcome! Please read the [contribution guidelines](#) for more details.
 
-use rand::Rng;
-use std::io;
+use rand::{Rng, RngCore, SeedableRng};
+use std::io::{self, BufRead, Write};
+
+mod client;
+mod commit;
+mod error;
+mod event;
+mod format;
+mod game;
+mod group;
+mod input;
+mod lobby;
+mod parse;
+mod player;
+mod protocol;
+mod rating;
+mod replay;
+mod rules;
+#[cfg(feature = "serde")]
+mod save;
+mod server;
+mod solver;
+mod strategy;
+mod tie;
+mod tournament;
+mod trainer;
+
+pub use client::Client;
+pub use commit::{CommitPhase, CommitRound, Commitment, Reveal};
+pub use error::Error;
+pub use event::{GameEvent, GameListener};
+pub use format::MatchFormat;
+pub use game::{Game, GameState, Round, RoundOutcome};
+pub use group::{GroupGame, GroupRound, GroupRoundOutcome};
+pub use lobby::{Lobby, LobbyStatus, MatchStatus, QueueStatus, RoomStatus};
+pub use parse::{MoveParser, ParseMoveError};
+pub use player::{Player, PlayerId, PlayerKind};
+pub use protocol::{Message, PROTOCOL_VERSIONS};
+pub use rating::{Elo, Glicko2, Glicko2Rating, LeaderboardEntry, RatingSystem};
+pub use replay::Replay;
+pub use rules::RuleSet;
+pub use server::{ForfeitRule, MatchRecord, Server};
+pub use solver::{Equilibrium, PayoffMatrix};
+pub use strategy::{
+    BeatLastStrategy, ConstantStrategy, CopyLastStrategy, CycleStrategy, EnsembleStrategy, MarkovContext,
+    MarkovStrategy, MixedStrategy, Predictor, RandomStrategy, Strategy,
+};
+pub use tie::{TiePolicy, Tiebreaker};
+pub use tournament::{MatchResult, Standing, Tournament, TournamentFormat, TournamentReport};
+pub use trainer::{RegretTrainer, TrainingReport};
+
+/// The random number generator used for reproducible games.
+///
+/// Every random decision in the crate has a variant that draws from a caller-provided generator (such as
+/// `MoveType::random_move_with()` or `Strategy::next_move_with()`). Seeding a `GameRng` with `seeded_rng()` makes
+/// those decisions, and therefore a whole match, repeatable. Unlike `rand::rngs::StdRng`, its output for a given seed
+/// is guaranteed not to change between releases.
+pub type GameRng = rand_chacha::ChaCha8Rng;
+
+/// Creates a `GameRng` from a seed. The same seed always produces the same sequence of random decisions.
+///
+/// # Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{seeded_rng, RuleSet};
+///
+/// let rules = RuleSet::lizard_spock();
+/// let mut first = seeded_rng(42);
+/// let mut second = seeded_rng(42);
+///
+/// for _ in 0..10 {
+///     assert_eq!(rules.random_move_with(&mut first), rules.random_move_with(&mut second));
+/// }
+/// ```
+pub fn seeded_rng(seed: u64) -> GameRng {
+    GameRng::seed_from_u64(seed)
+}
 
 /// # Winner enum
 ///
@@ -83,7 +224,8 @@ use std::io;
 /// let winner = Winner::User;
 /// assert_eq!(winner.convert_to_string(), "User");
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Winner {
     Tie,
     User,
@@ -121,6 +263,10 @@ impl Winner {
 /// - `MoveType::Rock`: The "Rock" move.
 /// - `MoveType::Paper`: The "Paper" move.
 /// - `MoveType::Scissors`: The "Scissors" move.
+/// - `MoveType::Lizard`: The "Lizard" move, only used by rule sets such as `RuleSet::lizard_spock()`.
+/// - `MoveType::Spock`: The "Spock" move, only used by rule sets such as `RuleSet::lizard_spock()`.
+/// - `MoveType::Gesture(index)`: Any further gesture of a generalized RPS-N rule set (see `RuleSet::balanced()`),
+///   identified by its zero-based index. Indices `0` to `4` are always represented by the named variants.
 /// - `MoveType::None`: A default state to handle uninitialized or invalid moves.
 ///
 /// ## Key Features
@@ -173,11 +319,15 @@ impl Winner {
 ///     Err(err) => println!("Error: {}", err),
 /// }
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MoveType {
     Rock,
     Paper,
     Scissors,
+    Lizard,
+    Spock,
+    Gesture(u8),
     None,
 }
 
@@ -193,7 +343,23 @@ impl MoveType {
     /// assert!(matches!(random_move, MoveType::Rock | MoveType::Paper | MoveType::Scissors));
     /// ```
     pub fn random_move() -> MoveType {
-        let rand_num = rand::rng().random_range(1..=3);
+        MoveType::random_move_with(&mut rand::rng())
+    }
+
+    /// Generates a random `MoveType` (one of `Rock`, `Paper`, or `Scissors`), drawing from the given random number
+    /// generator instead of the thread-local one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{seeded_rng, MoveType};
+    ///
+    /// let first = MoveType::random_move_with(&mut seeded_rng(7));
+    /// let second = MoveType::random_move_with(&mut seeded_rng(7));
+    /// assert_eq!(first, second);
+    /// ```
+    pub fn random_move_with<R: Rng + ?Sized>(rng: &mut R) -> MoveType {
+        let rand_num = rng.random_range(1..=3);
 
         match rand_num {
             1 => MoveType::Rock,
@@ -217,10 +383,63 @@ impl MoveType {
             Self::Rock => "Rock".to_string(),
             Self::Paper => "Paper".to_string(),
             Self::Scissors => "Scissors".to_string(),
+            Self::Lizard => "Lizard".to_string(),
+            Self::Spock => "Spock".to_string(),
+            Self::Gesture(index) => format!("Gesture {}", *index as u16 + 1),
             Self::None => "None".to_string(),
         }
     }
 
+    /// Returns the zero-based position of the move in a generalized RPS-N rule set.
+    ///
+    /// The order is Rock, Paper, Scissors, Spock, Lizard, followed by the `Gesture` variants, so that every odd
+    /// prefix of the sequence forms a balanced game. `MoveType::None` has no index.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::MoveType;
+    ///
+    /// assert_eq!(MoveType::Spock.index(), Some(3));
+    /// assert_eq!(MoveType::Gesture(6).index(), Some(6));
+    /// assert_eq!(MoveType::None.index(), None);
+    /// ```
+    pub fn index(&self) -> Option<u8> {
+        match self {
+            Self::Rock => Some(0),
+            Self::Paper => Some(1),
+            Self::Scissors => Some(2),
+            Self::Spock => Some(3),
+            Self::Lizard => Some(4),
+            Self::Gesture(index) => Some(*index),
+            Self::None => None,
+        }
+    }
+
+    /// Creates the move at the given zero-based position of a generalized RPS-N rule set.
+    ///
+    /// This is the inverse of `index()`: indices `0` to `4` map to the named variants, everything else to
+    /// `MoveType::Gesture`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::MoveType;
+    ///
+    /// assert_eq!(MoveType::from_index(4), MoveType::Lizard);
+    /// assert_eq!(MoveType::from_index(9), MoveType::Gesture(9));
+    /// ```
+    pub fn from_index(index: u8) -> MoveType {
+        match index {
+            0 => Self::Rock,
+            1 => Self::Paper,
+            2 => Self::Scissors,
+            3 => Self::Spock,
+            4 => Self::Lizard,
+            _ => Self::Gesture(index),
+        }
+    }
+
     /// # Gets the User's Move
     ///
     /// Handles user input from the console, validates it, and converts it into a `MoveType`.
@@ -230,7 +449,7 @@ impl MoveType {
     /// - `2` for `Paper`
     /// - `3` for `Scissors`
     ///
-    /// The function will ensure valid input by repeatedly asking for input until a valid value is provided.
+    /// This is a thin wrapper around `MoveType::read_from()` for standard input and output.
     ///
     /// # Examples
     ///
@@ -245,22 +464,33 @@ impl MoveType {
     ///
     /// # Behavior
     ///
-    /// When the user provides invalid input (e.g., letters or numbers outside the valid range),
-    /// the function will re-prompt for valid input until it is received
-    pub fn from_user_input() -> Result<MoveType, String> {
-        println!("Enter your move: (1 = Rock, 2 = Paper, 3 = Scissors)");
-        let mut user_input = String::new();
-
-        io::stdin()
-            .read_line(&mut user_input)
-            .expect("Failed to read line");
-
-        match user_input.trim().parse::<u8>() {
-            Ok(1) => Ok(MoveType::Rock),
-            Ok(2) => Ok(MoveType::Paper),
-            Ok(3) => Ok(MoveType::Scissors),
-            _ => Err("Invalid input. Please enter 1, 2, or 3.".to_string()),
-        }
+    /// Names, abbreviations and emoji are accepted as well, see `MoveParser`. When the user provides invalid input
+    /// (e.g., unknown words or numbers outside the valid range), an error message, with a suggestion for likely typos,
+    /// is returned so the caller can ask again. Failing to read standard input is reported as an error as well.
+    pub fn from_user_input() -> Result<MoveType, Error> {
+        MoveType::read_from(&mut io::stdin().lock(), &mut io::stdout())
+    }
+
+    /// Prompts for a move on `output` and reads it as a line from `input`.
+    ///
+    /// The line is parsed by `MoveParser::new()`, so besides `1`, `2` and `3` it accepts names, abbreviations and
+    /// emoji of the classic gestures. Returns `Error::InvalidMove` for anything else, and `Error::Eof` or `Error::Io`
+    /// when no line can be read, so it never blocks on a closed input.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::MoveType;
+    ///
+    /// let mut input = "2\nscissors\n".as_bytes();
+    /// let mut output = Vec::new();
+    ///
+    /// assert_eq!(MoveType::read_from(&mut input, &mut output).unwrap(), MoveType::Paper);
+    /// assert_eq!(MoveType::read_from(&mut input, &mut output).unwrap(), MoveType::Scissors);
+    /// assert!(String::from_utf8(output).unwrap().starts_with("Enter your move: (1 = Rock, 2 = Paper, 3 = Scissors)\n"));
+    /// ```
+    pub fn read_from<R: BufRead + ?Sized, W: Write + ?Sized>(input: &mut R, output: &mut W) -> Result<MoveType, Error> {
+        MoveParser::new().read_from(input, output)
     }
 }
 
@@ -282,7 +512,10 @@ impl MoveType {
 ///
 /// - **`PlayerMoves::new()`**: Safely initializes a `PlayerMoves` instance with both moves set to `MoveType::None`.
 /// - **`PlayerMoves::build_from_input()`**: Builds a new `PlayerMoves` instance by getting the user's input and randomly generating the enemy's move.
+/// - **`PlayerMoves::build_from_input_against()`**: Like `build_from_input()`, but the enemy's move comes from any `Strategy`.
 /// - **`PlayerMoves::check_who_wins_round()`**: Determines the winner of the round based on the moves.
+/// - **`PlayerMoves::swapped()`**: Returns the round seen from the other side.
+/// - **`PlayerMoves::check_who_wins_round_with()`**: Determines the winner of the round using a custom `RuleSet`.
 ///
 /// ## Examples
 ///
@@ -306,7 +539,8 @@ impl MoveType {
 ///
 /// assert_eq!(moves.check_who_wins_round(), Winner::User);
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PlayerMoves {
     pub user_move: MoveType,
     pub enemy_move: MoveType,
@@ -360,16 +594,135 @@ impl PlayerMoves {
     /// assert_eq!(player_moves.enemy_move, MoveType::Scissors);
     /// ```
     pub fn build_from_input() -> PlayerMoves {
+        PlayerMoves::build_from_input_against(&mut RandomStrategy::new(), &[])
+    }
+
+    /// Builds a new `PlayerMoves` instance with the user's move provided via input and the enemy's move chosen by
+    /// the given `Strategy`.
+    ///
+    /// `history` holds the previous rounds of the match, which the strategy may use to pick its move.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rock_paper_scissors::{BeatLastStrategy, PlayerMoves};
+    ///
+    /// let mut strategy = BeatLastStrategy::new();
+    /// let mut history = Vec::new();
+    ///
+    /// for _ in 0..3 {
+    ///     let player_moves = PlayerMoves::build_from_input_against(&mut strategy, &history);
+    ///     history.push(player_moves);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if standard input is closed or cannot be read. Use `PlayerMoves::read_from_against()` to handle that.
+    pub fn build_from_input_against<S: Strategy + ?Sized>(strategy: &mut S, history: &[PlayerMoves]) -> PlayerMoves {
+        PlayerMoves::read_from_against(&mut io::stdin().lock(), &mut io::stdout(), strategy, history)
+            .expect("Failed to read line")
+    }
+
+    /// Builds a new `PlayerMoves` instance with the user's move read from `input` and the enemy's move chosen by the
+    /// given `Strategy`.
+    ///
+    /// Invalid lines are reported on `output` and the user is asked again. Returns an error once no more lines can be
+    /// read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{ConstantStrategy, Error, MoveType, PlayerMoves};
+    ///
+    /// let mut strategy = ConstantStrategy::new(MoveType::Rock);
+    /// let mut input = "spock\n3\n".as_bytes();
+    /// let mut output = Vec::new();
+    ///
+    /// let player_moves = PlayerMoves::read_from_against(&mut input, &mut output, &mut strategy, &[]).unwrap();
+    /// assert_eq!(player_moves.user_move, MoveType::Scissors);
+    /// assert_eq!(player_moves.enemy_move, MoveType::Rock);
+    ///
+    /// let error = PlayerMoves::read_from_against(&mut input, &mut output, &mut strategy, &[]).unwrap_err();
+    /// assert!(matches!(error, Error::Eof));
+    /// ```
+    pub fn read_from_against<R, W, S>(
+        input: &mut R,
+        output: &mut W,
+        strategy: &mut S,
+        history: &[PlayerMoves],
+    ) -> Result<PlayerMoves, Error>
+    where
+        R: BufRead + ?Sized,
+        W: Write + ?Sized,
+        S: Strategy + ?Sized,
+    {
+        PlayerMoves::read_from_against_with(input, output, strategy, history, &mut rand::rng())
+    }
+
+    /// Like `PlayerMoves::read_from_against()`, but the strategy draws from the given random number generator instead
+    /// of the thread-local one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{seeded_rng, MoveType, PlayerMoves, RandomStrategy};
+    ///
+    /// let mut strategy = RandomStrategy::new();
+    /// let mut input = "rock\nrock\n".as_bytes();
+    /// let mut output = Vec::new();
+    ///
+    /// let mut rng = seeded_rng(3);
+    /// let first = PlayerMoves::read_from_against_with(&mut input, &mut output, &mut strategy, &[], &mut rng);
+    /// let mut rng = seeded_rng(3);
+    /// let second = PlayerMoves::read_from_against_with(&mut input, &mut output, &mut strategy, &[], &mut rng);
+    ///
+    /// assert_eq!(first.unwrap(), second.unwrap());
+    /// ```
+    pub fn read_from_against_with<R, W, S>(
+        input: &mut R,
+        output: &mut W,
+        strategy: &mut S,
+        history: &[PlayerMoves],
+        rng: &mut dyn RngCore,
+    ) -> Result<PlayerMoves, Error>
+    where
+        R: BufRead + ?Sized,
+        W: Write + ?Sized,
+        S: Strategy + ?Sized,
+    {
         let user_move = loop {
-            match MoveType::from_user_input() {
+            match MoveType::read_from(input, output) {
                 Ok(m) => break m,
-                Err(e) => println!("{}", e),
+                Err(e) if e.is_invalid() => writeln!(output, "{}", e)?,
+                Err(e) => return Err(e),
             }
         };
 
-        PlayerMoves {
+        Ok(PlayerMoves {
             user_move,
-            enemy_move: MoveType::random_move(),
+            enemy_move: strategy.next_move_with(history, rng),
+        })
+    }
+
+    /// Returns the same round seen from the other side, with the user's and enemy's moves exchanged.
+    ///
+    /// A `Strategy` always plays the enemy's side. Swapping the history lets it play the user's side instead, e.g.
+    /// when two bots play each other.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{PlayerMoves, MoveType};
+    ///
+    /// let player_moves = PlayerMoves { user_move: MoveType::Rock, enemy_move: MoveType::Paper };
+    ///
+    /// assert_eq!(player_moves.swapped(), PlayerMoves { user_move: MoveType::Paper, enemy_move: MoveType::Rock });
+    /// ```
+    pub fn swapped(&self) -> PlayerMoves {
+        PlayerMoves {
+            user_move: self.enemy_move,
+            enemy_move: self.user_move,
         }
     }
 
@@ -388,11 +741,31 @@ impl PlayerMoves {
     /// assert_eq!(player_moves.check_who_wins_round(), Winner::User);
     /// ```
     pub fn check_who_wins_round(&self) -> Winner {
-        match (&self.user_move, &self.enemy_move) {
-            (MoveType::Rock, MoveType::Rock) | (MoveType::Paper, MoveType::Paper) | (MoveType::Scissors, MoveType::Scissors) => Winner::Tie,
-            (MoveType::Rock, MoveType::Scissors) | (MoveType::Paper, MoveType::Rock) | (MoveType::Scissors, MoveType::Paper) => Winner::User,
-            _ => Winner::Enemy,
-        }
+        self.check_who_wins_round_with(&RuleSet::classic())
+    }
+
+    /// Determines the winner of the round using the given `RuleSet` instead of the classic rules.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{PlayerMoves, MoveType, RuleSet, Winner};
+    ///
+    /// let player_moves = PlayerMoves {
+    ///     user_move: MoveType::Spock,
+    ///     enemy_move: MoveType::Rock,
+    /// };
+    ///
+    /// assert_eq!(player_moves.check_who_wins_round_with(&RuleSet::lizard_spock()), Winner::User);
+    /// ```
+    pub fn check_who_wins_round_with(&self, rules: &RuleSet) -> Winner {
+        rules.check_who_wins(&self.user_move, &self.enemy_move)
+    }
+}
+
+impl Default for PlayerMoves {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -402,6 +775,7 @@ impl PlayerMoves {
 ///
 /// - `user_wins`: Number of rounds won by the user.
 /// - `enemy_wins`: Number of rounds won by the enemy.
+/// - `ties`: Number of rounds that ended in a tie.
 ///
 /// # Examples
 ///
@@ -412,11 +786,14 @@ impl PlayerMoves {
 /// scores.user_wins += 1;
 /// assert_eq!(scores.user_wins, 1);
 /// assert_eq!(scores.enemy_wins, 0);
+/// assert_eq!(scores.rounds(), 1);
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Scores {
     pub user_wins: u8,
     pub enemy_wins: u8,
+    pub ties: u8,
 }
 
 impl Scores {
@@ -435,12 +812,18 @@ impl Scores {
         Scores {
             user_wins: 0,
             enemy_wins: 0,
+            ties: 0,
         }
     }
 
-    /// Checks if the game has a winner (first to however many wins).
+    /// Returns the number of rounds played, ties included.
+    pub fn rounds(&self) -> u32 {
+        u32::from(self.user_wins) + u32::from(self.enemy_wins) + u32::from(self.ties)
+    }
+
+    /// Checks if the game has a winner according to the match format of the settings.
     ///
-    /// If either the user or the enemy has a certain number of specified wins, returns the winner as `Ok(Winner)`. Otherwise, returns an `Err` type.a
+    /// If either the user or the enemy has a certain number of specified wins, returns the winner as `Ok(Winner)`. Otherwise, returns an `Err` type.
     ///
     /// # Examples
     ///
@@ -452,18 +835,59 @@ impl Scores {
     /// let scores = Scores {
     ///     user_wins: 3,
     ///     enemy_wins: 2,
+    ///     ties: 0,
     /// };
     ///
+    /// # #[allow(deprecated)]
     /// assert_eq!(scores.check_for_winner(&game_settings), Ok(Winner::User));
     /// ```
+    ///
+    /// Having no winner yet is the normal state of a running match, so this is deprecated in favour of `winner()`,
+    /// which reports it as `None`.
+    #[deprecated(since = "0.5.0", note = "use `Scores::winner()`, which returns `None` while nobody has won yet")]
     pub fn check_for_winner(&self, game_settings: &GameSettings) -> Result<Winner, &str> {
-        if self.user_wins == game_settings.first_to {
-            Ok(Winner::User)
-        } else if self.enemy_wins == game_settings.first_to {
-            Ok(Winner::Enemy)
-        } else {
-            Err("rock-paper-scissors: err: No winner yet")
-        }
+        self.winner(game_settings).ok_or("rock-paper-scissors: err: No winner yet")
+    }
+
+    /// Returns the winner of the game according to the match format of the settings, or `None` while nobody has won
+    /// yet. A drawn match, such as a `MatchFormat::FixedRounds` match with level scores, returns `Some(Winner::Tie)`.
+    ///
+    /// Scores past the end of the match still report the player who is ahead; use `evaluate()` to reject them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{Scores, Winner, GameSettings};
+    ///
+    /// let game_settings = GameSettings::from_first_to(3);
+    /// let mut scores = Scores::new();
+    /// assert_eq!(scores.winner(&game_settings), None);
+    ///
+    /// scores.enemy_wins = 3;
+    /// assert_eq!(scores.winner(&game_settings), Some(Winner::Enemy));
+    /// ```
+    pub fn winner(&self, game_settings: &GameSettings) -> Option<Winner> {
+        game_settings.format.winner_with(self, &game_settings.tie_policy)
+    }
+
+    /// Returns the winner of the game like `winner()`, but fails if the settings are unplayable or if the scores can
+    /// never occur under them, such as an overshot 4-1 in a first to 3.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{Error, GameSettings, MatchFormat, Scores, Winner};
+    ///
+    /// let game_settings = GameSettings::from_format(MatchFormat::BestOf(5));
+    ///
+    /// let scores = Scores { user_wins: 1, enemy_wins: 3, ties: 2 };
+    /// assert_eq!(scores.evaluate(&game_settings), Ok(Some(Winner::Enemy)));
+    ///
+    /// let scores = Scores { user_wins: 3, enemy_wins: 3, ties: 0 };
+    /// assert!(matches!(scores.evaluate(&game_settings), Err(Error::InvalidScores(_))));
+    /// ```
+    pub fn evaluate(&self, game_settings: &GameSettings) -> Result<Option<Winner>, Error> {
+        game_settings.format.check_with(self, &game_settings.tie_policy)
     }
 
     /// Resets the scores to zero.
@@ -476,50 +900,62 @@ impl Scores {
     /// let mut scores = Scores {
     ///     user_wins: 2,
     ///     enemy_wins: 3,
+    ///     ties: 1,
     /// };
     ///
     /// scores.reset();
     /// assert_eq!(scores.user_wins, 0);
     /// assert_eq!(scores.enemy_wins, 0);
+    /// assert_eq!(scores.ties, 0);
     /// ```
     #[allow(dead_code)]
     pub fn reset(&mut self) {
         self.user_wins = 0;
         self.enemy_wins = 0;
+        self.ties = 0;
+    }
+}
+
+impl Default for Scores {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 /// # GameSettings Struct
 ///
 /// The `GameSettings` struct provides a simple yet flexible mechanism to configure the win conditions for a "Rock, Paper, Scissors" game session.
-/// It allows developers to define the number of wins required to declare an overall game winner.
+/// It allows developers to choose the `MatchFormat` that declares an overall game winner.
 ///
 /// ## Fields
 ///
-/// - `from_first_to`
-///   - Specifies the number of round wins required for either the user or opponent to win the game.
-///   - This value defaults to `0` when initializing using `GameSettings::new()`.
+/// - `format`
+///   - Specifies when the match is over, e.g. `MatchFormat::FirstTo(3)` or `MatchFormat::BestOf(5)`.
+///   - This value defaults to `MatchFormat::FirstTo(1)` when initializing using `GameSettings::new()`.
+/// - `tie_policy`
+///   - Specifies what a tied round is worth, e.g. `TiePolicy::Replay` or `TiePolicy::HalfPoint`.
+///   - This value defaults to `TiePolicy::AwardNobody`.
 ///
 /// ## Methods
 ///
 /// ### `GameSettings::new()`
-/// Creates a new `GameSettings` instance with `from_first_to` set to `0`. This can act as a placeholder until specific settings are defined.
+/// Creates a new `GameSettings` instance where the first round win decides the game. This can act as a placeholder until specific settings are defined.
 ///
 /// ```rust
-/// use rock_paper_scissors::GameSettings;
+/// use rock_paper_scissors::{GameSettings, MatchFormat};
 ///
 /// let game_settings = GameSettings::new();
-/// assert_eq!(game_settings.first_to, 1);
+/// assert_eq!(game_settings.format, MatchFormat::FirstTo(1));
 /// ```
 ///
-/// ### `GameSettings::first_to_3()`
-/// Provides a predefined configuration where the game is set to end after 3 wins from either the user or the opponent.
+/// ### `GameSettings::from_first_to()`
+/// Provides a configuration where the game is set to end after the given number of wins from either the user or the opponent.
 ///
 /// ```rust
-/// use rock_paper_scissors::GameSettings;
+/// use rock_paper_scissors::{GameSettings, MatchFormat};
 ///
 /// let game_settings = GameSettings::from_first_to(3);
-/// assert_eq!(game_settings.first_to, 3);
+/// assert_eq!(game_settings.format, MatchFormat::FirstTo(3));
 /// ```
 ///
 /// ## Examples
@@ -528,13 +964,14 @@ impl Scores {
 /// Developers can define their own win conditions by directly instantiating the `GameSettings` struct:
 ///
 /// ```rust
-/// use rock_paper_scissors::GameSettings;
+/// use rock_paper_scissors::{GameSettings, MatchFormat, TiePolicy};
 ///
 /// let custom_game_settings = GameSettings {
-///     first_to: 5,
+///     format: MatchFormat::WinBy { target: 5, margin: 2 },
+///     tie_policy: TiePolicy::Replay,
 /// };
 ///
-/// assert_eq!(custom_game_settings.first_to, 5);
+/// assert_eq!(custom_game_settings.format.to_string(), "first to 5, win by 2");
 /// ```
 ///
 /// ### Combining with Scores
@@ -550,38 +987,43 @@ impl Scores {
 /// scores.user_wins = 3;
 ///
 /// // Check for game winner
-/// let winner = scores.check_for_winner(&game_settings);
-/// assert_eq!(winner, Ok(Winner::User));
+/// let winner = scores.winner(&game_settings);
+/// assert_eq!(winner, Some(Winner::User));
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameSettings {
-    pub first_to: u8,
+    pub format: MatchFormat,
+    pub tie_policy: TiePolicy,
 }
 
 impl GameSettings {
-    /// Creates a new game configuration with the default `from_first_to` value of `1`.
+    /// Creates a new game configuration with the default format of `MatchFormat::FirstTo(1)`.
     ///
     /// ## Examples
     /// ```rust
-    /// use rock_paper_scissors::GameSettings;
+    /// use rock_paper_scissors::{GameSettings, MatchFormat};
     ///
     /// let settings = GameSettings::new();
-    /// assert_eq!(settings.first_to, 1);
+    /// assert_eq!(settings.format, MatchFormat::FirstTo(1));
     /// ```
     pub fn new() -> GameSettings {
-        GameSettings { first_to: 1 }
+        GameSettings {
+            format: MatchFormat::default(),
+            tie_policy: TiePolicy::default(),
+        }
     }
 
     /// The `from_user_input` method allows users to customize the game settings by
-    /// providing input for the `first_to` win condition. The number entered defines how many
+    /// providing input for a `MatchFormat::FirstTo` win condition. The number entered defines how many
     /// victories are required to declare a winner in the game session.
     ///
     /// ## Description
     ///
     /// - This function prompts the user to input a number (representing the target win count).
     /// - It validates the input to ensure it's a valid positive integer (`u8`).
-    /// - If the input is valid, it returns a `GameSettings` instance with the `first_to` property set
-    ///   to the input value.
+    /// - If the input is valid, it returns a `GameSettings` instance with the `format` property set
+    ///   to `MatchFormat::FirstTo` the input value.
     /// - In case of invalid input, such as non-numeric values or parsing errors, it returns an error
     ///   message.
     ///
@@ -590,10 +1032,10 @@ impl GameSettings {
     /// - Reads a line of input from the console.
     /// - Tries to parse the trimmed input into a `u8` number.
     /// - If parsing succeeds:
-    ///     - The parsed number is assigned to the `first_to` field of the `GameSettings` struct.
+    ///     - The parsed number becomes the `MatchFormat::FirstTo` format of the `GameSettings` struct.
     ///     - Returns `Ok(GameSettings)`.
     /// - If parsing fails:
-    ///     - Returns an error with a descriptive message (e.g., `"Invalid input. Please enter a number."`).
+    ///     - Returns `Error::InvalidSetting` with a descriptive message (e.g., `"Invalid input. Please enter a number."`).
     ///
     /// ## Use Case
     ///
@@ -607,17 +1049,15 @@ impl GameSettings {
     ///
     /// When valid input is provided:
     /// ```rust
-    /// use rock_paper_scissors::GameSettings;
+    /// use rock_paper_scissors::{Error, GameSettings, MatchFormat};
     ///
     /// // Simulating valid user input:
     /// // Let's say user enters "5" (first to 5 wins).
     /// // let game_settings = GameSettings::from_user_input();
     /// // returns the following:
-    /// let game_settings: Result<GameSettings, &'static str> = Ok(GameSettings {
-    ///     first_to: 5,
-    /// });
+    /// let game_settings: Result<GameSettings, Error> = Ok(GameSettings::from_first_to(5));
     /// match game_settings {
-    ///     Ok(settings) => assert_eq!(settings.first_to, 5),
+    ///     Ok(settings) => assert_eq!(settings.format, MatchFormat::FirstTo(5)),
     ///     Err(_) => panic!("This should not happen for valid input"),
     /// }
     /// ```
@@ -625,20 +1065,28 @@ impl GameSettings {
     /// ### Invalid Input
     ///
     /// Example of invalid
-    pub fn from_user_input() -> Result<GameSettings, &'static str> {
-        let mut game_settings = GameSettings::new();
-        let mut user_input = String::new();
-
-        io::stdin()
-            .read_line(&mut user_input)
-            .expect("Failed to read line");
-
-        match user_input.trim().parse::<u8>() { // Result<u8, _>
-            Ok(first_to) => {
-                game_settings.first_to = first_to;
-                Ok(game_settings)
-            },
-            Err(_) => Err("Invalid input. Please enter a number.")
+    pub fn from_user_input() -> Result<GameSettings, Error> {
+        GameSettings::read_from(&mut io::stdin().lock())
+    }
+
+    /// Reads the number of wins needed as a line from `input`, like `from_user_input()` does from the console.
+    ///
+    /// Returns `Error::InvalidSetting` if the line is not a number, and `Error::Eof` or `Error::Io` when no line can
+    /// be read.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{Error, GameSettings, MatchFormat};
+    ///
+    /// let settings = GameSettings::read_from(&mut "5\n".as_bytes()).unwrap();
+    /// assert_eq!(settings.format, MatchFormat::FirstTo(5));
+    ///
+    /// assert!(matches!(GameSettings::read_from(&mut "five\n".as_bytes()), Err(Error::InvalidSetting(_))));
+    /// ```
+    pub fn read_from<R: BufRead + ?Sized>(input: &mut R) -> Result<GameSettings, Error> {
+        match input::read_line(input)?.parse::<u8>() {
+            Ok(first_to) => Ok(GameSettings::from_first_to(first_to)),
+            Err(_) => Err(Error::InvalidSetting("Invalid input. Please enter a number.".to_string())),
         }
     }
 
@@ -646,14 +1094,52 @@ impl GameSettings {
     ///
     /// ## Examples
     /// ```rust
-    /// use rock_paper_scissors::GameSettings;
+    /// use rock_paper_scissors::{GameSettings, MatchFormat};
     ///
     /// let settings = GameSettings::from_first_to(3);
-    /// assert_eq!(settings.first_to, 3);
+    /// assert_eq!(settings.format, MatchFormat::FirstTo(3));
     /// ```
     pub fn from_first_to(first_to: u8) -> GameSettings {
+        GameSettings::from_format(MatchFormat::FirstTo(first_to))
+    }
+
+    /// Configuration that plays the given match format.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{GameSettings, MatchFormat, Scores, Winner};
+    ///
+    /// let settings = GameSettings::from_format(MatchFormat::FixedRounds(3));
+    /// let scores = Scores { user_wins: 1, enemy_wins: 1, ties: 1 };
+    /// assert_eq!(scores.winner(&settings), Some(Winner::Tie));
+    /// ```
+    pub fn from_format(format: MatchFormat) -> GameSettings {
         GameSettings {
-            first_to
+            format,
+            tie_policy: TiePolicy::default(),
         }
     }
-}
\ No newline at end of file
+
+    /// Replaces how tied rounds are counted.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{GameSettings, TiePolicy, Tiebreaker};
+    ///
+    /// let settings = GameSettings::from_first_to(3).with_tie_policy(TiePolicy::CapThenTiebreak {
+    ///     max_ties: 2,
+    ///     tiebreaker: Tiebreaker::CoinFlip,
+    /// });
+    /// assert_eq!(settings.tie_policy, TiePolicy::CapThenTiebreak { max_ties: 2, tiebreaker: Tiebreaker::CoinFlip });
+    /// ```
+    pub fn with_tie_policy(mut self, tie_policy: TiePolicy) -> GameSettings {
+        self.tie_policy = tie_policy;
+        self
+    }
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
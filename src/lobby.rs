@@ -0,0 +1,567 @@
+use crate::server::Connection;
+use crate::{Error, GameSettings, Message, Player, PlayerId, RuleSet, Scores, Winner};
+use rand::Rng;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::{Shutdown, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+
+/// The characters of a room code. Letters and digits that are easily mixed up, like `O` and `0`, are left out.
+const CODE_CHARACTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_LENGTH: usize = 6;
+/// The number of random bytes in a session token, which is sent as hexadecimal digits.
+const TOKEN_BYTES: usize = 16;
+
+/// # Lobby Struct
+///
+/// The matchmaking of a `Server`. Players wait in named queues, each with its own settings and rules, and are paired
+/// in the order they join a queue. Players who want to play someone in particular open a private room instead and
+/// share its join code. Spectators follow live matches round by round, and players who lose their connection resume
+/// their match through the lobby with the token of their session. The messages for all of this are described under
+/// `Message`.
+///
+/// A lobby is a cheap handle: clones share the same queues, rooms and matches, so one can be kept to inspect a
+/// running server through `status()` or `admin()`. The lobby also gives every connection its player id, so players
+/// it pairs never share one, even when several servers share the lobby.
+///
+/// ## Methods
+///
+/// - `Lobby::new()`: A lobby without queues.
+/// - `with_queue(name, settings, rules)`: Adds a queue.
+/// - `status()`: Returns the queues, rooms and live matches.
+/// - `admin(command)`: Runs an admin command and returns its output.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{GameSettings, Lobby, MatchFormat, RuleSet};
+///
+/// let lobby = Lobby::new()
+///     .with_queue("first-to-3", GameSettings::from_first_to(3), RuleSet::classic())
+///     .with_queue("best-of-5", GameSettings::from_format(MatchFormat::BestOf(5)), RuleSet::classic())
+///     .with_queue("rpsls", GameSettings::from_first_to(3), RuleSet::lizard_spock());
+///
+/// assert_eq!(lobby.status().queues.len(), 3);
+/// assert!(lobby.admin("queues").unwrap().contains("best-of-5: best of 5, nobody waiting"));
+/// ```
+#[derive(Clone)]
+pub struct Lobby {
+    state: Arc<Mutex<State>>,
+}
+
+struct State {
+    queues: Vec<Queue>,
+    rooms: BTreeMap<String, Room>,
+    matches: BTreeMap<usize, LiveMatch>,
+    next_match: usize,
+    next_player: usize,
+}
+
+struct Queue {
+    name: String,
+    settings: GameSettings,
+    rules: RuleSet,
+    waiting: Option<Connection>,
+}
+
+struct Room {
+    queue: usize,
+    host: Connection,
+}
+
+struct LiveMatch {
+    queue: String,
+    players: [Player; 2],
+    settings: GameSettings,
+    rules: RuleSet,
+    results: Vec<Message>,
+    scores: Scores,
+    /// Each spectator is written to on a thread of their own, see `spectate()`.
+    spectators: Vec<Sender<Message>>,
+    tokens: [String; 2],
+    /// The sockets the match plays on. Shutting one down wakes a match thread that still waits on a dropped socket.
+    streams: [Option<TcpStream>; 2],
+    reconnects: Sender<(usize, Connection)>,
+}
+
+/// A match the lobby has paired, ready to be played.
+pub(crate) struct Pairing {
+    pub(crate) id: usize,
+    pub(crate) settings: GameSettings,
+    pub(crate) rules: RuleSet,
+    pub(crate) connections: [Connection; 2],
+    pub(crate) tokens: [String; 2],
+    /// Players coming back to the match, by side.
+    pub(crate) reconnects: Receiver<(usize, Connection)>,
+}
+
+/// What became of a connection handed to the lobby.
+pub(crate) enum Admission {
+    /// The lobby keeps the connection: it waits in a queue or room, or watches a match.
+    Waiting,
+    /// The connection completed a pair.
+    Paired(Box<Pairing>),
+    /// The lobby has answered the request, and the client can send another one.
+    Answered(Connection),
+}
+
+impl Lobby {
+    /// Creates a lobby without queues.
+    pub fn new() -> Lobby {
+        Lobby {
+            state: Arc::new(Mutex::new(State {
+                queues: Vec::new(),
+                rooms: BTreeMap::new(),
+                matches: BTreeMap::new(),
+                next_match: 0,
+                next_player: 0,
+            })),
+        }
+    }
+
+    /// Adds a queue for matches with `settings` and `rules`. Version 1 clients always join the first queue.
+    ///
+    /// A queue with the same name is replaced. Whitespace in the name is replaced with `-`, as names are sent as a
+    /// single word.
+    pub fn with_queue(self, name: &str, settings: GameSettings, rules: RuleSet) -> Lobby {
+        let name = name.split_whitespace().collect::<Vec<_>>().join("-");
+        let queue = Queue {
+            name,
+            settings,
+            rules,
+            waiting: None,
+        };
+
+        let mut state = self.lock();
+        match state.queues.iter().position(|existing| existing.name == queue.name) {
+            Some(index) => state.queues[index] = queue,
+            None => state.queues.push(queue),
+        }
+        drop(state);
+
+        self
+    }
+
+    /// Returns a snapshot of the queues, rooms and live matches.
+    pub fn status(&self) -> LobbyStatus {
+        let state = self.lock();
+
+        LobbyStatus {
+            queues: state
+                .queues
+                .iter()
+                .map(|queue| QueueStatus {
+                    name: queue.name.clone(),
+                    settings: queue.settings.clone(),
+                    rules: queue.rules.clone(),
+                    waiting: queue.waiting.as_ref().map(|connection| connection.player.clone()),
+                })
+                .collect(),
+            rooms: state
+                .rooms
+                .iter()
+                .map(|(code, room)| RoomStatus {
+                    code: code.clone(),
+                    queue: state.queues[room.queue].name.clone(),
+                    host: room.host.player.clone(),
+                })
+                .collect(),
+            matches: state
+                .matches
+                .iter()
+                .map(|(id, live)| MatchStatus {
+                    id: *id,
+                    queue: live.queue.clone(),
+                    players: live.players.clone(),
+                    rounds: live.results.len(),
+                    scores: live.scores.clone(),
+                    spectators: live.spectators.len(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Runs an admin command and returns what it prints.
+    ///
+    /// - `status`: The queues, rooms and live matches.
+    /// - `queues`, `rooms`, `matches`: Only that part of the status.
+    ///
+    /// Returns `Error::InvalidSetting` for any other command.
+    pub fn admin(&self, command: &str) -> Result<String, Error> {
+        let status = self.status();
+
+        let lines: Vec<String> = match command.trim() {
+            "status" => return Ok(status.to_string()),
+            "queues" => status.queues.iter().map(QueueStatus::to_string).collect(),
+            "rooms" => status.rooms.iter().map(RoomStatus::to_string).collect(),
+            "matches" => status.matches.iter().map(MatchStatus::to_string).collect(),
+            command => {
+                return Err(Error::InvalidSetting(format!(
+                    "Unknown admin command '{command}', expected status, queues, rooms or matches"
+                )))
+            }
+        };
+
+        match lines.is_empty() {
+            true => Ok(String::from("none")),
+            false => Ok(lines.join("\n")),
+        }
+    }
+
+    /// Returns the id of a player who has just connected.
+    pub(crate) fn new_player_id(&self) -> PlayerId {
+        let mut state = self.lock();
+        state.next_player += 1;
+        PlayerId(state.next_player - 1)
+    }
+
+    /// Returns the name of the queue version 1 clients join.
+    pub(crate) fn default_queue(&self) -> Option<String> {
+        self.lock().queues.first().map(|queue| queue.name.clone())
+    }
+
+    /// Carries out a request a client sent from the lobby.
+    ///
+    /// The lobby is locked meanwhile, so messages sent before letting go of it use `Connection::try_send()`: a client
+    /// that does not read its messages must not hold up every other client and match.
+    pub(crate) fn admit(&self, mut connection: Connection, request: Message) -> Admission {
+        let mut state = self.lock();
+
+        let reply = match request {
+            Message::Queue(name) => match state.queue(&name) {
+                Some(queue) => return state.queue_up(queue, connection),
+                None => format!("There is no queue named '{name}'"),
+            },
+            Message::Create(name) => match state.queue(&name) {
+                Some(queue) => {
+                    let code = state.new_code();
+
+                    if connection.try_send(&Message::Room(code.clone())).is_ok()
+                        && connection.try_send(&Message::Waiting).is_ok()
+                    {
+                        state.rooms.insert(code, Room { queue, host: connection });
+                    }
+
+                    return Admission::Waiting;
+                }
+                None => format!("There is no queue named '{name}'"),
+            },
+            Message::Join(code) => match state.rooms.remove(&code.to_uppercase()) {
+                Some(room) if !room.host.is_closed() => {
+                    return Admission::Paired(Box::new(state.pair(room.queue, [room.host, connection])))
+                }
+                _ => format!("There is no room with the code '{code}'"),
+            },
+            Message::Watch(id) => match state.matches.get_mut(&id) {
+                Some(live) => {
+                    let spectator = spectate(connection);
+
+                    if live.catch_up(id).into_iter().all(|message| spectator.send(message).is_ok()) {
+                        live.spectators.push(spectator);
+                    }
+
+                    return Admission::Waiting;
+                }
+                None => format!("There is no live match {id}"),
+            },
+            Message::Resume(token) => {
+                let seat = state.matches.values_mut().find_map(|live| {
+                    let side = live.tokens.iter().position(|known| *known == token)?;
+                    Some((live, side))
+                });
+
+                match seat {
+                    Some((live, side)) => {
+                        let stream = connection.stream();
+
+                        match live.reconnects.send((side, connection)) {
+                            Ok(()) => {
+                                if let Some(dropped) = std::mem::replace(&mut live.streams[side], stream) {
+                                    let _ = dropped.shutdown(Shutdown::Both);
+                                }
+
+                                return Admission::Waiting;
+                            }
+                            Err(mpsc::SendError((_, returned))) => {
+                                connection = returned;
+                                String::from("The match is over")
+                            }
+                        }
+                    }
+                    None => String::from("There is no match to resume for this session"),
+                }
+            }
+            Message::List => {
+                let ids = state.matches.keys().copied().collect();
+                drop(state);
+
+                return match connection.send(&Message::Matches(ids)) {
+                    Ok(()) => Admission::Answered(connection),
+                    Err(_) => Admission::Waiting,
+                };
+            }
+            other => format!("Expected QUEUE, CREATE, JOIN, WATCH, LIST or RESUME, got '{other}'"),
+        };
+
+        drop(state);
+
+        match connection.send(&Message::Error(reply)) {
+            Ok(()) => Admission::Answered(connection),
+            Err(_) => Admission::Waiting,
+        }
+    }
+
+    /// Records a round of a live match and sends its result to the spectators. `result` is the user's `Message::Result`.
+    pub(crate) fn record_round(&self, id: usize, result: Message) {
+        let mut state = self.lock();
+
+        if let Some(live) = state.matches.get_mut(&id) {
+            if let Message::Result { scores, .. } = &result {
+                live.scores = scores.clone();
+            }
+
+            live.spectators.retain(|spectator| spectator.send(result.clone()).is_ok());
+            live.results.push(result);
+        }
+    }
+
+    /// Ends a live match, telling its spectators how it ended from the user's point of view.
+    pub(crate) fn finish(&self, id: usize, winner: Winner, forfeit: bool) {
+        let live = self.lock().matches.remove(&id);
+
+        for spectator in live.into_iter().flat_map(|live| live.spectators) {
+            let _ = spectator.send(Message::Over { winner, forfeit });
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        // A match thread that panicked leaves the state as consistent as between any two requests.
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for Lobby {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl State {
+    fn queue(&self, name: &str) -> Option<usize> {
+        self.queues.iter().position(|queue| queue.name == name)
+    }
+
+    /// Pairs a connection with the player waiting in a queue, or lets it wait there. A waiting player who has
+    /// disconnected in the meantime gives up their place.
+    fn queue_up(&mut self, queue: usize, mut connection: Connection) -> Admission {
+        match self.queues[queue].waiting.take() {
+            Some(first) if !first.is_closed() => Admission::Paired(Box::new(self.pair(queue, [first, connection]))),
+            _ => {
+                if connection.try_send(&Message::Waiting).is_ok() {
+                    self.queues[queue].waiting = Some(connection);
+                }
+
+                Admission::Waiting
+            }
+        }
+    }
+
+    fn pair(&mut self, queue: usize, connections: [Connection; 2]) -> Pairing {
+        let id = self.next_match;
+        let queue = &self.queues[queue];
+        let tokens = [new_token(), new_token()];
+        let (sender, receiver) = mpsc::channel();
+        self.next_match += 1;
+
+        self.matches.insert(id, LiveMatch {
+            queue: queue.name.clone(),
+            players: [connections[0].player.clone(), connections[1].player.clone()],
+            settings: queue.settings.clone(),
+            rules: queue.rules.clone(),
+            results: Vec::new(),
+            scores: Scores::new(),
+            spectators: Vec::new(),
+            tokens: tokens.clone(),
+            streams: [connections[0].stream(), connections[1].stream()],
+            reconnects: sender,
+        });
+
+        Pairing {
+            id,
+            settings: queue.settings.clone(),
+            rules: queue.rules.clone(),
+            connections,
+            tokens,
+            reconnects: receiver,
+        }
+    }
+
+    fn new_code(&self) -> String {
+        let mut rng = rand::rng();
+
+        loop {
+            let code: String = (0..CODE_LENGTH)
+                .map(|_| char::from(CODE_CHARACTERS[rng.random_range(0..CODE_CHARACTERS.len())]))
+                .collect();
+
+            if !self.rooms.contains_key(&code) {
+                return code;
+            }
+        }
+    }
+}
+
+fn new_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    rand::rng().fill(&mut bytes);
+
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Writes the messages sent to the returned channel to a spectator on a thread of its own, so a spectator who stops
+/// reading holds up nobody but themselves. The connection is closed once the channel is; a spectator who cannot be
+/// written to is let go, and the channel with them.
+fn spectate(mut connection: Connection) -> Sender<Message> {
+    let (sender, receiver) = mpsc::channel::<Message>();
+
+    thread::spawn(move || {
+        for message in receiver {
+            if connection.send(&message).is_err() {
+                return;
+            }
+        }
+
+        connection.close();
+    });
+
+    sender
+}
+
+impl LiveMatch {
+    /// Returns the messages that greet a new spectator: the match, its settings and rules, and the rounds played so
+    /// far.
+    fn catch_up(&self, id: usize) -> Vec<Message> {
+        let greeting = [
+            Message::Watching {
+                id,
+                user: self.players[0].id,
+                enemy: self.players[1].id,
+            },
+            Message::Settings(self.settings.clone()),
+            Message::Rules(self.rules.gestures.clone()),
+        ];
+
+        greeting.into_iter().chain(self.results.iter().cloned()).collect()
+    }
+}
+
+/// # LobbyStatus Struct
+///
+/// A snapshot of a `Lobby`, as returned by `Lobby::status()`. `Display` prints it the way the `status` admin command
+/// does.
+///
+/// ## Fields
+///
+/// - `queues`: Every queue, in the order they were added.
+/// - `rooms`: The private rooms waiting for a second player, by code.
+/// - `matches`: The matches being played, by id.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LobbyStatus {
+    pub queues: Vec<QueueStatus>,
+    pub rooms: Vec<RoomStatus>,
+    pub matches: Vec<MatchStatus>,
+}
+
+/// # QueueStatus Struct
+///
+/// A queue of a `Lobby`: its name, the settings and rules of its matches and the player waiting in it, if any.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QueueStatus {
+    pub name: String,
+    pub settings: GameSettings,
+    pub rules: RuleSet,
+    pub waiting: Option<Player>,
+}
+
+/// # RoomStatus Struct
+///
+/// A private room of a `Lobby`: its join code, the queue whose settings it uses and the player who opened it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoomStatus {
+    pub code: String,
+    pub queue: String,
+    pub host: Player,
+}
+
+/// # MatchStatus Struct
+///
+/// A live match of a `Lobby`: its id, the queue it was paired from, its players (user first), the number of rounds
+/// played, the scores from the user's point of view and the number of spectators.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchStatus {
+    pub id: usize,
+    pub queue: String,
+    pub players: [Player; 2],
+    pub rounds: usize,
+    pub scores: Scores,
+    pub spectators: usize,
+}
+
+impl fmt::Display for LobbyStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "queues: {}", self.queues.len())?;
+        self.queues.iter().try_for_each(|queue| writeln!(f, "  {queue}"))?;
+        writeln!(f, "rooms: {}", self.rooms.len())?;
+        self.rooms.iter().try_for_each(|room| writeln!(f, "  {room}"))?;
+        write!(f, "matches: {}", self.matches.len())?;
+        self.matches.iter().try_for_each(|live| write!(f, "\n  {live}"))
+    }
+}
+
+impl fmt::Display for QueueStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.settings.format)?;
+
+        if self.rules != RuleSet::classic() {
+            write!(f, " with {} gestures", self.rules.gestures.len())?;
+        }
+
+        match &self.waiting {
+            Some(player) => write!(f, ", {} ({}) waiting", player, player.id),
+            None => write!(f, ", nobody waiting"),
+        }
+    }
+}
+
+impl fmt::Display for RoomStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {} ({}) waiting", self.code, self.queue, self.host, self.host.id)
+    }
+}
+
+impl fmt::Display for MatchStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [user, enemy] = &self.players;
+
+        write!(
+            f,
+            "{} ({}): {} ({}) {}-{} {} ({}) after {} rounds, {} watching",
+            self.id,
+            self.queue,
+            user,
+            user.id,
+            self.scores.user_wins,
+            self.scores.enemy_wins,
+            enemy,
+            enemy.id,
+            self.rounds,
+            self.spectators
+        )
+    }
+}
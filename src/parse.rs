@@ -0,0 +1,282 @@
+use crate::{input, Error, MoveType, RuleSet};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+/// # MoveParser Struct
+///
+/// Turns text typed by a player into a `MoveType` of a `RuleSet`.
+///
+/// A parser accepts, ignoring case and surrounding whitespace:
+///
+/// - The 1-based position of a gesture in the rules (`"1"` is `Rock` in the classic rules).
+/// - The full name of a gesture (`"rock"`, `"SCISSORS"`).
+/// - Any unambiguous start of a name (`"pap"`, `"scissor"`).
+/// - An entry of the alias table: single letters (`"r"`, `"p"`, `"s"`, `"l"`) and emoji (🪨 📄 ✂️ 🦎 🖖 and hand signs)
+///   out of the box, plus whatever is added with `add_alias()`.
+///
+/// Input that matches nothing fails with a `ParseMoveError` that suggests the closest gesture when the input looks like
+/// a typo.
+///
+/// ## Fields
+///
+/// - `rules`: The gestures that can be parsed. Aliases for gestures outside the rules are ignored.
+/// - `aliases`: Extra spellings, stored lowercase, mapped to their gesture.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{MoveParser, MoveType};
+///
+/// let mut parser = MoveParser::new();
+/// parser.add_alias("stone", MoveType::Rock);
+///
+/// assert_eq!(parser.parse("R"), Ok(MoveType::Rock));
+/// assert_eq!(parser.parse("Stone"), Ok(MoveType::Rock));
+/// assert_eq!(parser.parse("✂️"), Ok(MoveType::Scissors));
+///
+/// let error = parser.parse("sissors").unwrap_err();
+/// assert_eq!(error.to_string(), "rock-paper-scissors: err: 'sissors' is not a move, did you mean 'scissors'?");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveParser {
+    pub rules: RuleSet,
+    pub aliases: HashMap<String, MoveType>,
+}
+
+/// # ParseMoveError Struct
+///
+/// The error returned when text cannot be parsed as a move.
+///
+/// - `input`: The text that was rejected, trimmed.
+/// - `suggestion`: The lowercase name of the gesture the player most likely meant, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParseMoveError {
+    pub input: String,
+    pub suggestion: Option<String>,
+}
+
+impl MoveParser {
+    /// Creates a parser for the classic rules with the default aliases.
+    pub fn new() -> MoveParser {
+        MoveParser::with_rules(RuleSet::classic())
+    }
+
+    /// Creates a parser for the gestures of `rules` with the default aliases.
+    pub fn with_rules(rules: RuleSet) -> MoveParser {
+        let mut parser = MoveParser {
+            rules,
+            aliases: HashMap::new(),
+        };
+
+        let defaults = [
+            ("r", MoveType::Rock),
+            ("🪨", MoveType::Rock),
+            ("✊", MoveType::Rock),
+            ("👊", MoveType::Rock),
+            ("p", MoveType::Paper),
+            ("📄", MoveType::Paper),
+            ("✋", MoveType::Paper),
+            ("🖐", MoveType::Paper),
+            ("s", MoveType::Scissors),
+            ("✂", MoveType::Scissors),
+            ("✌", MoveType::Scissors),
+            ("l", MoveType::Lizard),
+            ("🦎", MoveType::Lizard),
+            ("🖖", MoveType::Spock),
+        ];
+
+        for (alias, move_type) in defaults {
+            parser.add_alias(alias, move_type);
+        }
+
+        parser
+    }
+
+    /// Adds an alias for `move_type`, replacing any earlier meaning of the same alias. Aliases are case-insensitive.
+    pub fn add_alias(&mut self, alias: &str, move_type: MoveType) {
+        self.aliases.insert(MoveParser::normalize(alias), move_type);
+    }
+
+    /// Parses `input` as a gesture of the rules.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{MoveParser, MoveType, RuleSet};
+    ///
+    /// let parser = MoveParser::with_rules(RuleSet::lizard_spock());
+    ///
+    /// assert_eq!(parser.parse("5"), Ok(MoveType::Spock));
+    /// assert_eq!(parser.parse("liz"), Ok(MoveType::Lizard));
+    /// assert_eq!(parser.parse("🖖"), Ok(MoveType::Spock));
+    /// assert!(parser.parse("Gesture 9").is_err());
+    /// ```
+    pub fn parse(&self, input: &str) -> Result<MoveType, ParseMoveError> {
+        let normalized = MoveParser::normalize(input);
+
+        if let Some(move_type) = self.aliases.get(&normalized).filter(|m| self.rules.contains(m)) {
+            return Ok(*move_type);
+        }
+
+        if let Some(move_type) = self.rules.parse_move(&normalized) {
+            return Ok(move_type);
+        }
+
+        let prefixed: Vec<MoveType> = self
+            .rules
+            .gestures
+            .iter()
+            .filter(|gesture| !normalized.is_empty() && self.rules.name(gesture).to_lowercase().starts_with(&normalized))
+            .copied()
+            .collect();
+
+        if let [move_type] = prefixed[..] {
+            return Ok(move_type);
+        }
+
+        Err(ParseMoveError {
+            input: input.trim().to_string(),
+            suggestion: match prefixed.first() {
+                Some(move_type) => Some(self.rules.name(move_type).to_lowercase()),
+                None => self.suggest(&normalized),
+            },
+        })
+    }
+
+    /// Prompts for a move on `output` and parses a line read from `input`.
+    ///
+    /// The prompt lists the gestures of the rules by position. Returns `Error::InvalidMove` for unknown moves, and
+    /// `Error::Eof` or `Error::Io` when no line can be read.
+    pub fn read_from<R: BufRead + ?Sized, W: Write + ?Sized>(
+        &self,
+        input: &mut R,
+        output: &mut W,
+    ) -> Result<MoveType, Error> {
+        let choices: Vec<String> = self
+            .rules
+            .gestures
+            .iter()
+            .enumerate()
+            .map(|(index, gesture)| format!("{} = {}", index + 1, self.rules.name(gesture)))
+            .collect();
+
+        writeln!(output, "Enter your move: ({})", choices.join(", "))?;
+        output.flush()?;
+
+        Ok(self.parse(&input::read_line(input)?)?)
+    }
+
+    /// Finds the gesture whose name or alias is closest to `input`, if it is close enough to be a typo.
+    fn suggest(&self, input: &str) -> Option<String> {
+        let names = self
+            .rules
+            .gestures
+            .iter()
+            .map(|gesture| (self.rules.name(gesture).to_lowercase(), *gesture));
+        let aliases = self
+            .aliases
+            .iter()
+            // Letters and emoji are too short to tell a typo from a different word.
+            .filter(|(alias, move_type)| alias.chars().count() > 2 && self.rules.contains(move_type))
+            .map(|(alias, move_type)| (alias.clone(), *move_type));
+
+        let (distance, move_type) = names
+            .chain(aliases)
+            .map(|(candidate, move_type)| (edit_distance(input, &candidate), move_type))
+            .min_by_key(|(distance, _)| *distance)?;
+
+        // Allow roughly one mistake for every three characters typed.
+        match distance > 0 && distance <= (input.chars().count() / 3).max(1) {
+            true => Some(self.rules.name(&move_type).to_lowercase()),
+            false => None,
+        }
+    }
+
+    /// Lowercases `text`, trims it and drops emoji variation selectors so "✂️" and "✂" are the same alias.
+    fn normalize(text: &str) -> String {
+        text.trim().chars().filter(|c| *c != '\u{fe0f}').collect::<String>().to_lowercase()
+    }
+}
+
+impl Default for MoveParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParseMoveError {
+    fn describe(&self) -> String {
+        match &self.suggestion {
+            Some(suggestion) => format!("'{}' is not a move, did you mean '{}'?", self.input, suggestion),
+            None => format!("'{}' is not a move", self.input),
+        }
+    }
+}
+
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rock-paper-scissors: err: {}", self.describe())
+    }
+}
+
+impl std::error::Error for ParseMoveError {}
+
+impl From<ParseMoveError> for Error {
+    fn from(err: ParseMoveError) -> Self {
+        Error::InvalidMove(err.describe())
+    }
+}
+
+/// Parses any gesture of Rock-Paper-Scissors-Lizard-Spock with the default aliases, see `MoveParser`.
+impl FromStr for MoveType {
+    type Err = ParseMoveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MoveParser::with_rules(RuleSet::lizard_spock()).parse(s)
+    }
+}
+
+impl TryFrom<&str> for MoveType {
+    type Error = ParseMoveError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Edit distance between two strings, counted in characters. Swapping two neighbouring characters counts as a single
+/// edit, since that is the most common typo.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j - 1] + cost)
+                .min(distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
@@ -0,0 +1,89 @@
+use std::fmt;
+
+/// # PlayerId Struct
+///
+/// Identifies a player within a match. Scores and results are keyed by it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerId(pub usize);
+
+/// # PlayerKind Enum
+///
+/// Who is behind a player's moves.
+///
+/// - `PlayerKind::Human`: A person at this machine.
+/// - `PlayerKind::Bot`: A `Strategy` picking moves.
+/// - `PlayerKind::Remote`: Someone playing over a network connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayerKind {
+    Human,
+    Bot,
+    Remote,
+}
+
+/// # Player Struct
+///
+/// A participant in a match.
+///
+/// ## Fields
+///
+/// - `id`: Identifies the player within the match.
+/// - `name`: The name shown to people.
+/// - `kind`: Whether the player is a human, a bot or a remote player.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{Player, PlayerId, PlayerKind};
+///
+/// let player = Player::human(PlayerId(0), "Ada");
+///
+/// assert_eq!(player.kind, PlayerKind::Human);
+/// assert_eq!(player.to_string(), "Ada");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Player {
+    pub id: PlayerId,
+    pub name: String,
+    pub kind: PlayerKind,
+}
+
+impl Player {
+    /// Creates a player.
+    pub fn new(id: PlayerId, name: &str, kind: PlayerKind) -> Player {
+        Player {
+            id,
+            name: name.to_string(),
+            kind,
+        }
+    }
+
+    /// Creates a human player.
+    pub fn human(id: PlayerId, name: &str) -> Player {
+        Player::new(id, name, PlayerKind::Human)
+    }
+
+    /// Creates a bot player.
+    pub fn bot(id: PlayerId, name: &str) -> Player {
+        Player::new(id, name, PlayerKind::Bot)
+    }
+
+    /// Creates a remote player.
+    pub fn remote(id: PlayerId, name: &str) -> Player {
+        Player::new(id, name, PlayerKind::Remote)
+    }
+}
+
+impl fmt::Display for PlayerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+impl fmt::Display for Player {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
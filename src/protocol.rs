@@ -0,0 +1,365 @@
+use crate::input::read_line;
+use crate::replay::{gesture, index, parse_format, parse_tie_policy, write_format, write_tie_policy};
+use crate::{Commitment, Error, GameSettings, MoveType, PlayerId, PlayerMoves, Reveal, Scores, Winner};
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+/// The protocol versions this crate speaks, oldest first.
+pub const PROTOCOL_VERSIONS: &[u32] = &[1, 2, 3];
+
+/// # Message Enum
+///
+/// A message of the line-based protocol spoken between a `Server` and its `Client`s. Every message is one line of
+/// UTF-8 text: an upper-case keyword followed by its arguments, separated by spaces. Moves are sent as
+/// `MoveType::index()`, and every message is written from the point of view of the player receiving it, so `win`
+/// means the receiver won.
+///
+/// A session runs as follows:
+///
+/// 1. The client opens with `HELLO <versions> <name>`, listing the protocol versions it speaks separated by commas.
+/// 2. The server picks the newest version both sides speak and answers `WELCOME <version> <player id>`, or
+///    `ERROR <reason>` before closing the connection when there is none.
+/// 3. From version 2 on, the client tells the lobby what it wants to do:
+///    - `QUEUE <queue>` waits in a queue of the server for the next player to join it.
+///    - `CREATE <queue>` opens a private room with the settings of a queue. The server answers `ROOM <code>`, and the
+///      match starts once another client sends `JOIN <code>`.
+///    - `LIST` asks for the live matches, answered with `MATCHES <match ids>`, after which the client picks again.
+///    - `WATCH <match id>` follows a live match as a spectator. The server answers
+///      `WATCHING <match id> <user id> <enemy id>`, `SETTINGS` and `RULES`, then sends every `RESULT` so far and each
+///      one to come, followed by `OVER`, all from the point of view of the match's user.
+///
+///    - From version 3 on, `RESUME <token>` takes a player back into a match they lost their connection to, see
+///      below.
+///
+///    A command the lobby cannot carry out is answered with `ERROR <reason>`, and the client can pick again. Version 1
+///    clients join the server's first queue right away.
+/// 4. The server sends `WAITING` while it looks for an opponent, then `MATCH <opponent id> <opponent name>`,
+///    `SETTINGS <format> <tie policy>` and `RULES <gestures>`. From version 3 on, it then sends `SESSION <token>`.
+/// 5. Every round is played by commit–reveal, so no client hands its move over before the opponent is bound to theirs.
+///    The server sends `ROUND <number>` and the client answers `COMMIT <commitment>`, see `Reveal`. Once both players
+///    are bound, the server sends each client the opponent's `COMMIT`, and the client answers
+///    `REVEAL <gesture> <nonce>`. The server then sends it the opponent's `REVEAL`, and both players get
+///    `RESULT <round> <own move> <opponent's move> <win|loss|tie> <own wins> <opponent's wins> <ties>`.
+///
+///    A client may answer `ROUND` with `MOVE <gesture>` instead, trusting the server with its move: the server commits
+///    to it on the client's behalf and neither asks for nor sends it a reveal. A rejected message is answered with
+///    `ERROR <reason>` and can be sent again, but a reveal that does not match its commitment, or reveals a move outside
+///    the rules, forfeits the match as if the player had quit. A player who has not committed, or revealed, within the
+///    server's move timeout is disconnected, as if their connection had dropped.
+/// 6. The server ends the match with `OVER <win|loss|draw>`, followed by ` forfeit` when the opponent quit or lost
+///    their connection, and closes the connection. Like any `Game`, a match that is still undecided once a player has
+///    won 255 rounds, or 255 rounds were tied, goes to the leader or is drawn, see `MatchFormat`.
+///
+/// A client may send `QUIT` at any point to give up the match.
+///
+/// A version 3 player whose connection drops has the server's grace period to reconnect, say `HELLO` again and send
+/// `RESUME <token>` with the token of their session. Meanwhile their opponent gets `WAITING`. The server answers
+/// `RESUMED <player id> <round> <own wins> <opponent's wins> <ties> [<commitment>]`, then `MATCH`, `SETTINGS` and
+/// `RULES` again, and `ROUND <number>` unless the player's move or commitment for that round arrived before the
+/// connection dropped; it still counts. `RESUMED` ends with the player's own commitment for the open round when they
+/// had made one, and the server sends the opponent's `COMMIT` again if it is time to reveal. Only the player knows the
+/// reveal, so a client that resumes on their behalf has to be handed it. A player who does not return in time forfeits
+/// the match under the server's `ForfeitRule`.
+///
+/// | Message | Sent by | Since | Example |
+/// |---|---|---|---|
+/// | `Message::Hello` | client | 1 | `HELLO 1,2 Ada` |
+/// | `Message::Welcome` | server | 1 | `WELCOME 2 0` |
+/// | `Message::Queue` | client | 2 | `QUEUE best-of-5` |
+/// | `Message::Create` | client | 2 | `CREATE rpsls` |
+/// | `Message::Room` | server | 2 | `ROOM K7QX2M` |
+/// | `Message::Join` | client | 2 | `JOIN K7QX2M` |
+/// | `Message::List` | client | 2 | `LIST` |
+/// | `Message::Matches` | server | 2 | `MATCHES 0 3` |
+/// | `Message::Watch` | client | 2 | `WATCH 3` |
+/// | `Message::Watching` | server | 2 | `WATCHING 3 6 7` |
+/// | `Message::Resume` | client | 3 | `RESUME 9f86d081884c7d659a2feaa0c55ad015` |
+/// | `Message::Resumed` | server | 3 | `RESUMED 0 4 2 1 0` |
+/// | `Message::Waiting` | server | 1 | `WAITING` |
+/// | `Message::Match` | server | 1 | `MATCH 1 Grace` |
+/// | `Message::Settings` | server | 1 | `SETTINGS first-to 3 award-nobody` |
+/// | `Message::Rules` | server | 1 | `RULES 0 1 2` |
+/// | `Message::Session` | server | 3 | `SESSION 9f86d081884c7d659a2feaa0c55ad015` |
+/// | `Message::Round` | server | 1 | `ROUND 1` |
+/// | `Message::Move` | client | 1 | `MOVE 1` |
+/// | `Message::Commit` | both | 1 | `COMMIT 5d41402abc4b2a76b9719d911017c592...` (64 digits) |
+/// | `Message::Reveal` | both | 1 | `REVEAL 1 7c211433f02071597741e6ff5a8ea34789abbf43...` (64 digits) |
+/// | `Message::Result` | server | 1 | `RESULT 1 1 0 win 1 0 0` |
+/// | `Message::Over` | server | 1 | `OVER win` |
+/// | `Message::Error` | both | 1 | `ERROR Lizard is not part of the rules` |
+/// | `Message::Quit` | client | 1 | `QUIT` |
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{Message, MoveType};
+///
+/// let message: Message = "MOVE 2".parse().unwrap();
+/// assert_eq!(message, Message::Move(MoveType::Scissors));
+/// assert_eq!(message.to_string(), "MOVE 2");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Message {
+    Hello { versions: Vec<u32>, name: String },
+    Welcome { version: u32, player: PlayerId },
+    Queue(String),
+    Create(String),
+    Room(String),
+    Join(String),
+    List,
+    Matches(Vec<usize>),
+    Watch(usize),
+    Watching { id: usize, user: PlayerId, enemy: PlayerId },
+    Resume(String),
+    Resumed { player: PlayerId, round: usize, scores: Scores, commitment: Option<Commitment> },
+    Waiting,
+    Match { opponent: PlayerId, name: String },
+    Settings(GameSettings),
+    Rules(Vec<MoveType>),
+    Session(String),
+    Round(usize),
+    Move(MoveType),
+    Commit(Commitment),
+    Reveal(Reveal),
+    Result { round: usize, moves: PlayerMoves, winner: Winner, scores: Scores },
+    Over { winner: Winner, forfeit: bool },
+    Error(String),
+    Quit,
+}
+
+impl Message {
+    /// Writes the message as one line to `output`.
+    pub fn write_to<W: Write + ?Sized>(&self, output: &mut W) -> Result<(), Error> {
+        writeln!(output, "{self}")?;
+        output.flush()?;
+        Ok(())
+    }
+
+    /// Reads one message from `input`, failing with `Error::Eof` once the connection is closed and with
+    /// `Error::Protocol` for a line that is not a message.
+    pub fn read_from<R: BufRead + ?Sized>(input: &mut R) -> Result<Message, Error> {
+        read_line(input)?.parse()
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Message::Hello { versions, name } => {
+                let versions: Vec<String> = versions.iter().map(u32::to_string).collect();
+                write!(f, "HELLO {} {}", versions.join(","), one_line(name))
+            }
+            Message::Welcome { version, player } => write!(f, "WELCOME {version} {}", player.0),
+            Message::Queue(queue) => write!(f, "QUEUE {}", one_word(queue)),
+            Message::Create(queue) => write!(f, "CREATE {}", one_word(queue)),
+            Message::Room(code) => write!(f, "ROOM {}", one_word(code)),
+            Message::Join(code) => write!(f, "JOIN {}", one_word(code)),
+            Message::List => write!(f, "LIST"),
+            Message::Matches(ids) => {
+                let ids: Vec<String> = ids.iter().map(usize::to_string).collect();
+                write!(f, "MATCHES {}", ids.join(" "))
+            }
+            Message::Watch(id) => write!(f, "WATCH {id}"),
+            Message::Watching { id, user, enemy } => write!(f, "WATCHING {id} {} {}", user.0, enemy.0),
+            Message::Resume(token) => write!(f, "RESUME {}", one_word(token)),
+            Message::Resumed {
+                player,
+                round,
+                scores,
+                commitment,
+            } => {
+                write!(
+                    f,
+                    "RESUMED {} {round} {} {} {}",
+                    player.0, scores.user_wins, scores.enemy_wins, scores.ties
+                )?;
+
+                match commitment {
+                    Some(commitment) => write!(f, " {commitment}"),
+                    None => Ok(()),
+                }
+            }
+            Message::Waiting => write!(f, "WAITING"),
+            Message::Match { opponent, name } => write!(f, "MATCH {} {}", opponent.0, one_line(name)),
+            Message::Settings(settings) => write!(
+                f,
+                "SETTINGS {} {}",
+                write_format(&settings.format),
+                write_tie_policy(&settings.tie_policy)
+            ),
+            Message::Rules(gestures) => {
+                let gestures: Vec<String> = gestures.iter().map(index).collect();
+                write!(f, "RULES {}", gestures.join(" "))
+            }
+            Message::Session(token) => write!(f, "SESSION {}", one_word(token)),
+            Message::Round(round) => write!(f, "ROUND {round}"),
+            Message::Move(move_type) => write!(f, "MOVE {}", index(move_type)),
+            Message::Commit(commitment) => write!(f, "COMMIT {commitment}"),
+            Message::Reveal(reveal) => write!(f, "REVEAL {reveal}"),
+            Message::Result {
+                round,
+                moves,
+                winner,
+                scores,
+            } => write!(
+                f,
+                "RESULT {round} {} {} {} {} {} {}",
+                index(&moves.user_move),
+                index(&moves.enemy_move),
+                match winner {
+                    Winner::User => "win",
+                    Winner::Enemy => "loss",
+                    Winner::Tie => "tie",
+                },
+                scores.user_wins,
+                scores.enemy_wins,
+                scores.ties
+            ),
+            Message::Over { winner, forfeit } => {
+                let result = match winner {
+                    Winner::User => "win",
+                    Winner::Enemy => "loss",
+                    Winner::Tie => "draw",
+                };
+
+                match forfeit {
+                    true => write!(f, "OVER {result} forfeit"),
+                    false => write!(f, "OVER {result}"),
+                }
+            }
+            Message::Error(message) => write!(f, "ERROR {}", one_line(message)),
+            Message::Quit => write!(f, "QUIT"),
+        }
+    }
+}
+
+impl FromStr for Message {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let line = line.trim();
+        let invalid = || Error::Protocol(format!("'{line}' is not a message"));
+        let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let mut words = rest.split_whitespace();
+
+        let message = match keyword {
+            "HELLO" => {
+                let (versions, name) = rest.split_once(' ').unwrap_or((rest, ""));
+                let versions = versions.split(',').map(|version| version.parse().ok()).collect::<Option<_>>();
+
+                Message::Hello {
+                    versions: versions.ok_or_else(invalid)?,
+                    name: name.to_string(),
+                }
+            }
+            "WELCOME" => Message::Welcome {
+                version: number(words.next()).ok_or_else(invalid)?,
+                player: PlayerId(number(words.next()).ok_or_else(invalid)?),
+            },
+            "QUEUE" => Message::Queue(words.next().ok_or_else(invalid)?.to_string()),
+            "CREATE" => Message::Create(words.next().ok_or_else(invalid)?.to_string()),
+            "ROOM" => Message::Room(words.next().ok_or_else(invalid)?.to_string()),
+            "JOIN" => Message::Join(words.next().ok_or_else(invalid)?.to_string()),
+            "LIST" => Message::List,
+            "MATCHES" => Message::Matches(words.map(|id| id.parse().ok()).collect::<Option<_>>().ok_or_else(invalid)?),
+            "WATCH" => Message::Watch(number(words.next()).ok_or_else(invalid)?),
+            "WATCHING" => Message::Watching {
+                id: number(words.next()).ok_or_else(invalid)?,
+                user: PlayerId(number(words.next()).ok_or_else(invalid)?),
+                enemy: PlayerId(number(words.next()).ok_or_else(invalid)?),
+            },
+            "RESUME" => Message::Resume(words.next().ok_or_else(invalid)?.to_string()),
+            "RESUMED" => Message::Resumed {
+                player: PlayerId(number(words.next()).ok_or_else(invalid)?),
+                round: number(words.next()).ok_or_else(invalid)?,
+                scores: Scores {
+                    user_wins: number(words.next()).ok_or_else(invalid)?,
+                    enemy_wins: number(words.next()).ok_or_else(invalid)?,
+                    ties: number(words.next()).ok_or_else(invalid)?,
+                },
+                commitment: words.next().map(str::parse).transpose().map_err(|_| invalid())?,
+            },
+            "WAITING" => Message::Waiting,
+            "MATCH" => {
+                let (opponent, name) = rest.split_once(' ').unwrap_or((rest, ""));
+
+                Message::Match {
+                    opponent: PlayerId(opponent.parse().map_err(|_| invalid())?),
+                    name: name.to_string(),
+                }
+            }
+            "SETTINGS" => {
+                let format = parse_format(&mut words).ok_or_else(invalid)?;
+                let tie_policy = parse_tie_policy(&mut words).ok_or_else(invalid)?;
+
+                Message::Settings(GameSettings::from_format(format).with_tie_policy(tie_policy))
+            }
+            "RULES" => Message::Rules(words.map(gesture).collect::<Option<_>>().ok_or_else(invalid)?),
+            "SESSION" => Message::Session(words.next().ok_or_else(invalid)?.to_string()),
+            "ROUND" => Message::Round(number(words.next()).ok_or_else(invalid)?),
+            "MOVE" => Message::Move(words.next().and_then(gesture).ok_or_else(invalid)?),
+            "COMMIT" => Message::Commit(rest.parse().map_err(|_| invalid())?),
+            "REVEAL" => Message::Reveal(rest.parse().map_err(|_| invalid())?),
+            "RESULT" => {
+                let round = number(words.next()).ok_or_else(invalid)?;
+                let user_move = words.next().and_then(gesture).ok_or_else(invalid)?;
+                let enemy_move = words.next().and_then(gesture).ok_or_else(invalid)?;
+                let winner = match words.next() {
+                    Some("win") => Winner::User,
+                    Some("loss") => Winner::Enemy,
+                    Some("tie") => Winner::Tie,
+                    _ => return Err(invalid()),
+                };
+
+                Message::Result {
+                    round,
+                    moves: PlayerMoves { user_move, enemy_move },
+                    winner,
+                    scores: Scores {
+                        user_wins: number(words.next()).ok_or_else(invalid)?,
+                        enemy_wins: number(words.next()).ok_or_else(invalid)?,
+                        ties: number(words.next()).ok_or_else(invalid)?,
+                    },
+                }
+            }
+            "OVER" => {
+                let winner = match words.next() {
+                    Some("win") => Winner::User,
+                    Some("loss") => Winner::Enemy,
+                    Some("draw") => Winner::Tie,
+                    _ => return Err(invalid()),
+                };
+
+                Message::Over {
+                    winner,
+                    forfeit: words.next() == Some("forfeit"),
+                }
+            }
+            "ERROR" => Message::Error(rest.to_string()),
+            "QUIT" => Message::Quit,
+            _ => return Err(invalid()),
+        };
+
+        Ok(message)
+    }
+}
+
+/// Picks the newest protocol version both sides speak.
+pub(crate) fn negotiate(versions: &[u32]) -> Option<u32> {
+    PROTOCOL_VERSIONS.iter().rev().find(|version| versions.contains(version)).copied()
+}
+
+fn number<T: FromStr>(word: Option<&str>) -> Option<T> {
+    word?.parse().ok()
+}
+
+fn one_line(text: &str) -> String {
+    text.replace(['\n', '\r'], " ")
+}
+
+/// Queue names, room codes and session tokens are single words, so whitespace inside one is replaced.
+fn one_word(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join("-")
+}
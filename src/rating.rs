@@ -0,0 +1,441 @@
+use crate::{Error, Game, GameSettings, MatchResult, PlayerId, Scores, Winner};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// The factor between the Glicko scale and the Glicko-2 scale.
+const GLICKO2_SCALE: f64 = 173.7178;
+
+/// How closely the Glicko-2 volatility iteration has to converge.
+const CONVERGENCE: f64 = 0.000001;
+
+/// # RatingSystem Trait
+///
+/// Keeps ratings for players across matches. `record()` is the only method an implementation has to provide; the other
+/// methods turn a finished `Game`, `Scores` or tournament `MatchResult` into a call to it.
+///
+/// A score is from the point of view of the first player: `1.0` for a win, `0.5` for a draw and `0.0` for a loss.
+pub trait RatingSystem {
+    /// Records a match between `player` and `opponent` in which `player` scored `score`.
+    fn record(&mut self, player: PlayerId, opponent: PlayerId, score: f64);
+
+    /// Records a finished `Game`.
+    ///
+    /// Returns an error if the match is not over yet.
+    fn record_game(&mut self, game: &Game) -> Result<(), Error> {
+        let [user, enemy] = game.players();
+
+        match game.winner() {
+            Some(winner) => {
+                self.record(user.id, enemy.id, score_of(winner));
+                Ok(())
+            }
+            None => Err(Error::InvalidScores(String::from("The match is not over yet"))),
+        }
+    }
+
+    /// Records a match played with `Scores` and `GameSettings`, as reported by `Scores::check_for_winner()`.
+    ///
+    /// Returns an error if the match is not over yet.
+    fn record_scores(
+        &mut self,
+        user: PlayerId,
+        enemy: PlayerId,
+        scores: &Scores,
+        settings: &GameSettings,
+    ) -> Result<(), Error> {
+        match scores.winner(settings) {
+            Some(winner) => {
+                self.record(user, enemy, score_of(winner));
+                Ok(())
+            }
+            None => Err(Error::InvalidScores(String::from("The match is not over yet"))),
+        }
+    }
+
+    /// Records a match of a `Tournament`.
+    fn record_match(&mut self, result: &MatchResult) {
+        let [player, opponent] = result.players;
+        let score = match result.winner {
+            Some(winner) if winner == player => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        };
+
+        self.record(player, opponent, score);
+    }
+}
+
+fn score_of(winner: Winner) -> f64 {
+    match winner {
+        Winner::User => 1.0,
+        Winner::Enemy => 0.0,
+        Winner::Tie => 0.5,
+    }
+}
+
+/// # LeaderboardEntry Struct
+///
+/// A player's place on a leaderboard.
+///
+/// - `rank`: The 1-based position, best first.
+/// - `player`: The player.
+/// - `rating`: The player's rating: an `f64` for `Elo`, a `Glicko2Rating` for `Glicko2`.
+/// - `matches`: How many matches have been rated for the player.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LeaderboardEntry<R> {
+    pub rank: usize,
+    pub player: PlayerId,
+    pub rating: R,
+    pub matches: usize,
+}
+
+/// Sorts players by rating, best first, and by id when ratings are equal.
+fn leaderboard<R: Clone>(
+    ratings: &HashMap<PlayerId, R>,
+    matches: &HashMap<PlayerId, usize>,
+    value: impl Fn(&R) -> f64,
+) -> Vec<LeaderboardEntry<R>> {
+    let mut players: Vec<(&PlayerId, &R)> = ratings.iter().collect();
+    players.sort_by(|(a, x), (b, y)| value(y).total_cmp(&value(x)).then(a.cmp(b)));
+
+    players
+        .into_iter()
+        .enumerate()
+        .map(|(index, (player, rating))| LeaderboardEntry {
+            rank: index + 1,
+            player: *player,
+            rating: rating.clone(),
+            matches: matches.get(player).copied().unwrap_or(0),
+        })
+        .collect()
+}
+
+/// # Elo Struct
+///
+/// The Elo rating system. Every recorded match moves both players' ratings at once by `k_factor` times the difference
+/// between the score and the expected score.
+///
+/// ## Fields
+///
+/// - `k_factor`: The most a rating can change in one match. Defaults to `32`.
+/// - `initial_rating`: The rating of a player who has not been rated yet. Defaults to `1500`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{Elo, PlayerId, RatingSystem};
+///
+/// let mut elo = Elo::new();
+/// elo.record(PlayerId(0), PlayerId(1), 1.0);
+///
+/// assert_eq!(elo.rating(PlayerId(0)), 1516.0);
+/// assert_eq!(elo.rating(PlayerId(1)), 1484.0);
+/// assert_eq!(elo.leaderboard()[0].player, PlayerId(0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Elo {
+    pub k_factor: f64,
+    pub initial_rating: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::save::entries"))]
+    ratings: HashMap<PlayerId, f64>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::save::entries"))]
+    matches: HashMap<PlayerId, usize>,
+}
+
+impl Elo {
+    /// Creates an Elo system with a K-factor of 32 and an initial rating of 1500.
+    pub fn new() -> Elo {
+        Elo::with_k_factor(32.0)
+    }
+
+    /// Creates an Elo system with the given K-factor and an initial rating of 1500.
+    pub fn with_k_factor(k_factor: f64) -> Elo {
+        Elo {
+            k_factor,
+            initial_rating: 1500.0,
+            ratings: HashMap::new(),
+            matches: HashMap::new(),
+        }
+    }
+
+    /// Returns a player's rating, or `initial_rating` if the player has not been rated yet.
+    pub fn rating(&self, player: PlayerId) -> f64 {
+        self.ratings.get(&player).copied().unwrap_or(self.initial_rating)
+    }
+
+    /// Sets a player's rating, for example to carry it over from an earlier session.
+    pub fn set_rating(&mut self, player: PlayerId, rating: f64) {
+        self.ratings.insert(player, rating);
+    }
+
+    /// Returns the score `player` is expected to make against `opponent`, between `0.0` and `1.0`.
+    pub fn expected_score(&self, player: PlayerId, opponent: PlayerId) -> f64 {
+        1.0 / (1.0 + 10f64.powf((self.rating(opponent) - self.rating(player)) / 400.0))
+    }
+
+    /// Returns every rated player, best first.
+    pub fn leaderboard(&self) -> Vec<LeaderboardEntry<f64>> {
+        leaderboard(&self.ratings, &self.matches, |rating| *rating)
+    }
+}
+
+impl Default for Elo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RatingSystem for Elo {
+    fn record(&mut self, player: PlayerId, opponent: PlayerId, score: f64) {
+        let change = self.k_factor * (score - self.expected_score(player, opponent));
+
+        self.set_rating(player, self.rating(player) + change);
+        self.set_rating(opponent, self.rating(opponent) - change);
+
+        for id in [player, opponent] {
+            *self.matches.entry(id).or_insert(0) += 1;
+        }
+    }
+}
+
+/// # Glicko2Rating Struct
+///
+/// A player's Glicko-2 rating.
+///
+/// - `rating`: The strength of the player, on the same scale as Elo.
+/// - `deviation`: How uncertain the rating is. A player's true strength lies within about two deviations of the
+///   rating. It shrinks as the player plays and grows during rating periods the player sits out.
+/// - `volatility`: How erratic the player's results are.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Glicko2Rating {
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+}
+
+impl Glicko2Rating {
+    /// Returns the rating of a new player: 1500, with a deviation of 350 and a volatility of 0.06.
+    pub fn new() -> Glicko2Rating {
+        Glicko2Rating {
+            rating: 1500.0,
+            deviation: 350.0,
+            volatility: 0.06,
+        }
+    }
+
+    fn mu(&self) -> f64 {
+        (self.rating - 1500.0) / GLICKO2_SCALE
+    }
+
+    fn phi(&self) -> f64 {
+        self.deviation / GLICKO2_SCALE
+    }
+}
+
+impl Default for Glicko2Rating {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// # Glicko2 Struct
+///
+/// The Glicko-2 rating system. Matches are collected into a rating period and all ratings are updated together when
+/// the period ends, each player's against the ratings their opponents had at the start of the period.
+///
+/// ## Fields
+///
+/// - `tau`: Limits how quickly volatility changes. Reasonable values lie between `0.3` and `1.2`. Defaults to `0.5`.
+/// - `initial_rating`: The rating of a player who has not been rated yet.
+/// - `period_length`: With `Some(n)`, a rating period ends by itself after every `n` recorded matches. With `None`, it
+///   ends when `end_period()` is called. Glicko-2 works best with periods in which every player plays several matches.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{Glicko2, PlayerId, RatingSystem};
+///
+/// let mut glicko = Glicko2::new();
+/// glicko.record(PlayerId(0), PlayerId(1), 1.0);
+///
+/// // Nothing changes until the rating period ends.
+/// assert_eq!(glicko.rating(PlayerId(0)).rating, 1500.0);
+///
+/// glicko.end_period();
+///
+/// assert!(glicko.rating(PlayerId(0)).rating > 1500.0);
+/// assert!(glicko.rating(PlayerId(1)).deviation < 350.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Glicko2 {
+    pub tau: f64,
+    pub initial_rating: Glicko2Rating,
+    pub period_length: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::save::entries"))]
+    ratings: HashMap<PlayerId, Glicko2Rating>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::save::entries"))]
+    matches: HashMap<PlayerId, usize>,
+    pending: Vec<(PlayerId, PlayerId, f64)>,
+}
+
+impl Glicko2 {
+    /// Creates a Glicko-2 system with a `tau` of 0.5 whose rating periods end when `end_period()` is called.
+    pub fn new() -> Glicko2 {
+        Glicko2 {
+            tau: 0.5,
+            initial_rating: Glicko2Rating::new(),
+            period_length: None,
+            ratings: HashMap::new(),
+            matches: HashMap::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Creates a Glicko-2 system whose rating periods end after every `matches` recorded matches.
+    pub fn with_period_length(matches: usize) -> Glicko2 {
+        Glicko2 {
+            period_length: Some(matches),
+            ..Glicko2::new()
+        }
+    }
+
+    /// Returns a player's rating as of the end of the last rating period, or `initial_rating` if the player has not
+    /// been rated yet.
+    pub fn rating(&self, player: PlayerId) -> Glicko2Rating {
+        self.ratings.get(&player).copied().unwrap_or(self.initial_rating)
+    }
+
+    /// Sets a player's rating, for example to carry it over from an earlier session.
+    pub fn set_rating(&mut self, player: PlayerId, rating: Glicko2Rating) {
+        self.ratings.insert(player, rating);
+    }
+
+    /// Returns how many matches have been recorded in the current rating period.
+    pub fn pending_matches(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Ends the rating period: updates the rating of every player from the matches recorded since the last period
+    /// ended. Players who did not play get less certain ratings.
+    pub fn end_period(&mut self) {
+        let pending = std::mem::take(&mut self.pending);
+        let mut games: HashMap<PlayerId, Vec<(Glicko2Rating, f64)>> = HashMap::new();
+
+        for (player, opponent, score) in pending {
+            for (id, other, result) in [(player, opponent, score), (opponent, player, 1.0 - score)] {
+                self.ratings.entry(id).or_insert(self.initial_rating);
+                games.entry(id).or_default().push((self.rating(other), result));
+            }
+        }
+
+        let updated: Vec<(PlayerId, Glicko2Rating)> = self
+            .ratings
+            .iter()
+            .map(|(id, rating)| (*id, self.updated(rating, games.get(id).map_or(&[], Vec::as_slice))))
+            .collect();
+
+        self.ratings.extend(updated);
+    }
+
+    /// Returns every rated player, best first, as of the end of the last rating period.
+    pub fn leaderboard(&self) -> Vec<LeaderboardEntry<Glicko2Rating>> {
+        leaderboard(&self.ratings, &self.matches, |rating| rating.rating)
+    }
+
+    /// Applies one rating period to a rating, following Glickman's description of the algorithm.
+    fn updated(&self, rating: &Glicko2Rating, games: &[(Glicko2Rating, f64)]) -> Glicko2Rating {
+        let (mu, phi, sigma) = (rating.mu(), rating.phi(), rating.volatility);
+
+        if games.is_empty() {
+            return Glicko2Rating {
+                deviation: (phi * phi + sigma * sigma).sqrt() * GLICKO2_SCALE,
+                ..*rating
+            };
+        }
+
+        let g = |phi: f64| 1.0 / (1.0 + 3.0 * phi * phi / (PI * PI)).sqrt();
+        let expected = |opponent: &Glicko2Rating| 1.0 / (1.0 + (-g(opponent.phi()) * (mu - opponent.mu())).exp());
+
+        let v = 1.0
+            / games
+                .iter()
+                .map(|(opponent, _)| {
+                    let e = expected(opponent);
+                    g(opponent.phi()).powi(2) * e * (1.0 - e)
+                })
+                .sum::<f64>();
+        let improvement: f64 = games
+            .iter()
+            .map(|(opponent, score)| g(opponent.phi()) * (score - expected(opponent)))
+            .sum();
+        let delta = v * improvement;
+
+        let a = (sigma * sigma).ln();
+        let f = |x: f64| {
+            let ex = x.exp();
+            ex * (delta * delta - phi * phi - v - ex) / (2.0 * (phi * phi + v + ex).powi(2)) - (x - a) / (self.tau * self.tau)
+        };
+
+        let mut low = a;
+        let mut high = match delta * delta > phi * phi + v {
+            true => (delta * delta - phi * phi - v).ln(),
+            false => {
+                let mut k = 1.0;
+                while f(a - k * self.tau) < 0.0 {
+                    k += 1.0;
+                }
+                a - k * self.tau
+            }
+        };
+        let (mut f_low, mut f_high) = (f(low), f(high));
+
+        while (high - low).abs() > CONVERGENCE {
+            let next = low + (low - high) * f_low / (f_high - f_low);
+            let f_next = f(next);
+
+            if f_next * f_high <= 0.0 {
+                low = high;
+                f_low = f_high;
+            } else {
+                f_low /= 2.0;
+            }
+
+            high = next;
+            f_high = f_next;
+        }
+
+        let volatility = (low / 2.0).exp();
+        let phi_star = (phi * phi + volatility * volatility).sqrt();
+        let new_phi = 1.0 / (1.0 / (phi_star * phi_star) + 1.0 / v).sqrt();
+
+        Glicko2Rating {
+            rating: (mu + new_phi * new_phi * improvement) * GLICKO2_SCALE + 1500.0,
+            deviation: new_phi * GLICKO2_SCALE,
+            volatility,
+        }
+    }
+}
+
+impl Default for Glicko2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RatingSystem for Glicko2 {
+    fn record(&mut self, player: PlayerId, opponent: PlayerId, score: f64) {
+        self.pending.push((player, opponent, score));
+
+        for id in [player, opponent] {
+            *self.matches.entry(id).or_insert(0) += 1;
+        }
+
+        if self.period_length.is_some_and(|length| self.pending.len() >= length) {
+            self.end_period();
+        }
+    }
+}
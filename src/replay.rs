@@ -0,0 +1,474 @@
+use crate::{
+    Error, Game, GameSettings, MatchFormat, MoveType, Player, PlayerId, PlayerKind, PlayerMoves, Round, RuleSet,
+    Scores, TiePolicy, Tiebreaker, Winner,
+};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// The first line of every replay, followed by the format version.
+const MAGIC: &str = "rock-paper-scissors replay";
+
+/// # Replay Struct
+///
+/// A complete record of a match that can be stored, shared and checked later, e.g. to settle a dispute or as a
+/// regression fixture.
+///
+/// A replay is written as text, one record per line: a versioned header with the seed, rules, settings and players,
+/// then one line per round with both moves (as `MoveType::index()`) and the recorded winner, then the final scores.
+/// The last line holds the SHA-256 hash of everything before it, so any change to the file is noticed when it is read.
+///
+/// ```text
+/// rock-paper-scissors replay 1
+/// seed 7
+/// gestures 0 1 2
+/// beats 0>2 1>0 2>1
+/// verb 0 2 crushes
+/// verb 1 0 covers
+/// verb 2 1 cuts
+/// format first-to 1
+/// ties award-nobody
+/// player 0 human Ada
+/// player 1 bot Marvin
+/// round 0 0 tie
+/// round 1 0 user
+/// scores 1 0 1
+/// sha256 5d41...
+/// ```
+///
+/// `Replay::verify()` plays the recorded moves through a new `Game` and checks that every round and the final scores
+/// come out as recorded. Ties decided by a tiebreaker are re-drawn from the seed.
+///
+/// ## Fields
+///
+/// - `rules`: The rules of the match, including gesture names and verbs.
+/// - `settings`: The match format and tie policy.
+/// - `players`: The user and the enemy.
+/// - `seed`: The seed of the game's random number generator.
+/// - `rounds`: Every round in order, with its recorded winner.
+/// - `scores`: The recorded final scores.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{ConstantStrategy, Game, GameSettings, MoveType, Replay};
+///
+/// let mut game = Game::with_seed(GameSettings::from_first_to(2), 7).with_opponent(ConstantStrategy::new(MoveType::Rock));
+///
+/// while !game.is_over() {
+///     game.play_round(MoveType::Paper).unwrap();
+/// }
+///
+/// let text = Replay::from_game(&game).to_string();
+/// let replay = Replay::parse(&text).unwrap();
+///
+/// let replayed = replay.verify().unwrap();
+/// assert_eq!(replayed.scores(), game.scores());
+///
+/// // Changing a single move breaks the hash.
+/// let tampered = text.replacen("round 1 0 user", "round 0 0 user", 1);
+/// assert!(Replay::parse(&tampered).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Replay {
+    pub rules: RuleSet,
+    pub settings: GameSettings,
+    pub players: [Player; 2],
+    pub seed: u64,
+    pub rounds: Vec<Round>,
+    pub scores: Scores,
+}
+
+impl Replay {
+    /// The version of the replay format written by this crate.
+    pub const VERSION: u32 = 1;
+
+    /// Records a game, finished or not.
+    pub fn from_game(game: &Game) -> Replay {
+        Replay {
+            rules: game.rules().clone(),
+            settings: game.settings().clone(),
+            players: game.players().clone(),
+            seed: game.seed(),
+            rounds: game.history().to_vec(),
+            scores: game.scores().clone(),
+        }
+    }
+
+    /// Returns the SHA-256 hash of the replay, as written on its last line.
+    pub fn hash(&self) -> String {
+        hex(&Sha256::digest(self.body()))
+    }
+
+    /// Reads a replay and checks its version and hash. The moves are not played; use `verify()` for that.
+    pub fn parse(text: &str) -> Result<Replay, Error> {
+        let trimmed = text.trim_end();
+        let (body, last) = match trimmed.rfind('\n') {
+            Some(position) => trimmed.split_at(position + 1),
+            None => return Err(invalid("The replay is empty")),
+        };
+
+        match last.strip_prefix("sha256 ") {
+            Some(hash) if hash == hex(&Sha256::digest(body)) => (),
+            Some(_) => return Err(invalid("The replay does not match its hash")),
+            None => return Err(invalid("The replay has no hash")),
+        }
+
+        let mut lines = body.lines();
+
+        match lines.next().and_then(|line| line.strip_prefix(MAGIC)).map(str::trim) {
+            Some(version) if version == Replay::VERSION.to_string() => (),
+            Some(version) => return Err(invalid(&format!("Unsupported replay version {version}"))),
+            None => return Err(invalid("Not a replay")),
+        }
+
+        let mut seed = None;
+        let mut rules = RuleSet::new(Vec::new(), Vec::new());
+        let mut format = None;
+        let mut tie_policy = None;
+        let mut players = Vec::new();
+        let mut rounds = Vec::new();
+        let mut scores = None;
+
+        for (index, line) in lines.enumerate() {
+            let context = |message: &str| invalid(&format!("Line {}: {message}", index + 2));
+            let (key, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let mut words = rest.split_whitespace();
+
+            match key {
+                "seed" => seed = Some(number(words.next()).ok_or_else(|| context("Invalid seed"))?),
+                "gestures" => {
+                    rules.gestures = words.map(gesture).collect::<Option<_>>().ok_or_else(|| context("Invalid gesture"))?
+                }
+                "beats" => {
+                    rules.beats = words
+                        .map(|pair| pair.split_once('>').and_then(|(winner, loser)| Some((gesture(winner)?, gesture(loser)?))))
+                        .collect::<Option<_>>()
+                        .ok_or_else(|| context("Invalid beats"))?
+                }
+                "name" => {
+                    let (move_type, name) = rest.split_once(' ').ok_or_else(|| context("Invalid name"))?;
+                    rules
+                        .names
+                        .insert(gesture(move_type).ok_or_else(|| context("Invalid gesture"))?, name.to_string());
+                }
+                "verb" => {
+                    let mut parts = rest.splitn(3, ' ');
+                    let (winner, loser) = (parts.next().and_then(gesture), parts.next().and_then(gesture));
+
+                    match (winner, loser, parts.next()) {
+                        (Some(winner), Some(loser), Some(verb)) => rules.verbs.insert((winner, loser), verb.to_string()),
+                        _ => return Err(context("Invalid verb")),
+                    };
+                }
+                "format" => format = Some(parse_format(&mut words).ok_or_else(|| context("Invalid match format"))?),
+                "ties" => tie_policy = Some(parse_tie_policy(&mut words).ok_or_else(|| context("Invalid tie policy"))?),
+                "player" => {
+                    let mut parts = rest.splitn(3, ' ');
+                    let id = parts.next().and_then(|id| id.parse().ok()).map(PlayerId);
+                    let kind = parts.next().and_then(parse_kind);
+
+                    match (id, kind, parts.next()) {
+                        (Some(id), Some(kind), Some(name)) => players.push(Player::new(id, name, kind)),
+                        _ => return Err(context("Invalid player")),
+                    }
+                }
+                "round" => {
+                    let user_move = words.next().and_then(gesture);
+                    let enemy_move = words.next().and_then(gesture);
+                    let winner = words.next().and_then(parse_winner);
+
+                    match (user_move, enemy_move, winner) {
+                        (Some(user_move), Some(enemy_move), Some(winner)) => rounds.push(Round {
+                            moves: PlayerMoves { user_move, enemy_move },
+                            winner,
+                            winner_id: None,
+                        }),
+                        _ => return Err(context("Invalid round")),
+                    }
+                }
+                "scores" => {
+                    let (user_wins, enemy_wins, ties) = (number(words.next()), number(words.next()), number(words.next()));
+
+                    match (user_wins, enemy_wins, ties) {
+                        (Some(user_wins), Some(enemy_wins), Some(ties)) => scores = Some(Scores {
+                            user_wins,
+                            enemy_wins,
+                            ties,
+                        }),
+                        _ => return Err(context("Invalid scores")),
+                    }
+                }
+                _ => return Err(context(&format!("Unknown record '{key}'"))),
+            }
+        }
+
+        let players: [Player; 2] = players.try_into().map_err(|_| invalid("A replay needs exactly 2 players"))?;
+
+        // Rounds are recorded by side; the players they stand for are only known once the whole replay is read.
+        for round in &mut rounds {
+            round.winner_id = match round.winner {
+                Winner::User => Some(players[0].id),
+                Winner::Enemy => Some(players[1].id),
+                Winner::Tie => None,
+            };
+        }
+
+        match (seed, format, tie_policy, scores) {
+            (Some(seed), Some(format), Some(tie_policy), Some(scores)) => Ok(Replay {
+                rules,
+                settings: GameSettings::from_format(format).with_tie_policy(tie_policy),
+                players,
+                seed,
+                rounds,
+                scores,
+            }),
+            _ => Err(invalid("The replay is missing its seed, format, tie policy or scores")),
+        }
+    }
+
+    /// Reads a replay from `input` until it ends. See `parse()`.
+    pub fn read_from<R: BufRead + ?Sized>(input: &mut R) -> Result<Replay, Error> {
+        let mut text = String::new();
+        input.read_to_string(&mut text)?;
+        Replay::parse(&text)
+    }
+
+    /// Writes the replay to `output`.
+    pub fn write_to<W: Write + ?Sized>(&self, output: &mut W) -> Result<(), Error> {
+        output.write_all(self.to_string().as_bytes())?;
+        Ok(())
+    }
+
+    /// Writes the replay to a file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    /// Reads a replay from a file. See `parse()`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Replay, Error> {
+        Replay::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Plays the recorded moves through a new `Game` with the recorded seed, rules, settings and players, and checks
+    /// that every round has the recorded winner and that the match ends with the recorded scores. Returns the replayed
+    /// game.
+    ///
+    /// Returns `Error::InvalidReplay` naming the first round or score that does not match.
+    pub fn verify(&self) -> Result<Game, Error> {
+        let [user, enemy] = self.players.clone();
+        let mut game = Game::with_seed(self.settings.clone(), self.seed)
+            .with_rules(self.rules.clone())
+            .with_players(user, enemy)
+            .map_err(|err| invalid(&err.to_string()))?;
+
+        for (index, round) in self.rounds.iter().enumerate() {
+            let number = index + 1;
+            let outcome = match game.play_moves(round.moves) {
+                Ok(outcome) => outcome,
+                Err(Error::MatchAlreadyOver) => {
+                    return Err(invalid(&format!("Round {number} was played after the match was decided")))
+                }
+                Err(Error::InvalidMove(message)) => return Err(invalid(&format!("Round {number}: {message}"))),
+                Err(err) => return Err(err),
+            };
+
+            if outcome.winner != round.winner {
+                return Err(invalid(&format!(
+                    "Round {number} was recorded as won by {} but is won by {}",
+                    round.winner.convert_to_string(),
+                    outcome.winner.convert_to_string()
+                )));
+            }
+        }
+
+        if game.scores() != &self.scores {
+            return Err(invalid("The recorded scores do not match the rounds"));
+        }
+
+        Ok(game)
+    }
+
+    /// Every line of the replay but the hash.
+    fn body(&self) -> String {
+        let mut body = format!("{MAGIC} {}\nseed {}\n", Replay::VERSION, self.seed);
+        let gestures: Vec<String> = self.rules.gestures.iter().map(index).collect();
+        let beats: Vec<String> = self.rules.beats.iter().map(|(w, l)| format!("{}>{}", index(w), index(l))).collect();
+
+        body += &format!("gestures {}\nbeats {}\n", gestures.join(" "), beats.join(" "));
+
+        let mut names: Vec<(&MoveType, &String)> = self.rules.names.iter().collect();
+        names.sort_by_key(|(move_type, _)| move_type.index());
+
+        for (move_type, name) in names {
+            body += &format!("name {} {}\n", index(move_type), one_line(name));
+        }
+
+        let mut verbs: Vec<(&(MoveType, MoveType), &String)> = self.rules.verbs.iter().collect();
+        verbs.sort_by_key(|((winner, loser), _)| (winner.index(), loser.index()));
+
+        for ((winner, loser), verb) in verbs {
+            body += &format!("verb {} {} {}\n", index(winner), index(loser), one_line(verb));
+        }
+
+        body += &format!("format {}\n", write_format(&self.settings.format));
+        body += &format!("ties {}\n", write_tie_policy(&self.settings.tie_policy));
+
+        for player in &self.players {
+            body += &format!("player {} {} {}\n", player.id.0, write_kind(player.kind), one_line(&player.name));
+        }
+
+        for round in &self.rounds {
+            body += &format!(
+                "round {} {} {}\n",
+                index(&round.moves.user_move),
+                index(&round.moves.enemy_move),
+                write_winner(round.winner)
+            );
+        }
+
+        body + &format!("scores {} {} {}\n", self.scores.user_wins, self.scores.enemy_wins, self.scores.ties)
+    }
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}sha256 {}", self.body(), self.hash())?;
+        writeln!(f)
+    }
+}
+
+fn invalid(message: &str) -> Error {
+    Error::InvalidReplay(message.to_string())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn number<T: FromStr>(word: Option<&str>) -> Option<T> {
+    word?.parse().ok()
+}
+
+fn one_line(text: &str) -> String {
+    text.replace(['\n', '\r'], " ")
+}
+
+pub(crate) fn index(move_type: &MoveType) -> String {
+    match move_type.index() {
+        Some(index) => index.to_string(),
+        None => String::from("none"),
+    }
+}
+
+pub(crate) fn gesture(word: &str) -> Option<MoveType> {
+    match word {
+        "none" => Some(MoveType::None),
+        _ => word.parse().ok().map(MoveType::from_index),
+    }
+}
+
+pub(crate) fn write_format(format: &MatchFormat) -> String {
+    match format {
+        MatchFormat::FirstTo(n) => format!("first-to {n}"),
+        MatchFormat::BestOf(n) => format!("best-of {n}"),
+        MatchFormat::WinBy { target, margin } => format!("win-by {target} {margin}"),
+        MatchFormat::FixedRounds(n) => format!("fixed-rounds {n}"),
+        MatchFormat::SuddenDeath { first_to, max_rounds } => format!("sudden-death {first_to} {max_rounds}"),
+    }
+}
+
+pub(crate) fn parse_format<'a>(words: &mut impl Iterator<Item = &'a str>) -> Option<MatchFormat> {
+    let format = match words.next()? {
+        "first-to" => MatchFormat::FirstTo(number(words.next())?),
+        "best-of" => MatchFormat::BestOf(number(words.next())?),
+        "win-by" => MatchFormat::WinBy {
+            target: number(words.next())?,
+            margin: number(words.next())?,
+        },
+        "fixed-rounds" => MatchFormat::FixedRounds(number(words.next())?),
+        "sudden-death" => MatchFormat::SuddenDeath {
+            first_to: number(words.next())?,
+            max_rounds: number(words.next())?,
+        },
+        _ => return None,
+    };
+
+    Some(format)
+}
+
+pub(crate) fn write_tie_policy(policy: &TiePolicy) -> String {
+    match policy {
+        TiePolicy::Replay => String::from("replay"),
+        TiePolicy::AwardNobody => String::from("award-nobody"),
+        TiePolicy::HalfPoint => String::from("half-point"),
+        TiePolicy::CapThenTiebreak { max_ties, tiebreaker } => {
+            let tiebreaker = match tiebreaker {
+                Tiebreaker::CoinFlip => "coin-flip",
+                Tiebreaker::FavorUser => "favor-user",
+                Tiebreaker::FavorEnemy => "favor-enemy",
+            };
+
+            format!("cap-then-tiebreak {max_ties} {tiebreaker}")
+        }
+    }
+}
+
+pub(crate) fn parse_tie_policy<'a>(words: &mut impl Iterator<Item = &'a str>) -> Option<TiePolicy> {
+    let policy = match words.next()? {
+        "replay" => TiePolicy::Replay,
+        "award-nobody" => TiePolicy::AwardNobody,
+        "half-point" => TiePolicy::HalfPoint,
+        "cap-then-tiebreak" => TiePolicy::CapThenTiebreak {
+            max_ties: number(words.next())?,
+            tiebreaker: match words.next()? {
+                "coin-flip" => Tiebreaker::CoinFlip,
+                "favor-user" => Tiebreaker::FavorUser,
+                "favor-enemy" => Tiebreaker::FavorEnemy,
+                _ => return None,
+            },
+        },
+        _ => return None,
+    };
+
+    Some(policy)
+}
+
+fn write_kind(kind: PlayerKind) -> &'static str {
+    match kind {
+        PlayerKind::Human => "human",
+        PlayerKind::Bot => "bot",
+        PlayerKind::Remote => "remote",
+    }
+}
+
+fn parse_kind(word: &str) -> Option<PlayerKind> {
+    match word {
+        "human" => Some(PlayerKind::Human),
+        "bot" => Some(PlayerKind::Bot),
+        "remote" => Some(PlayerKind::Remote),
+        _ => None,
+    }
+}
+
+fn write_winner(winner: Winner) -> &'static str {
+    match winner {
+        Winner::User => "user",
+        Winner::Enemy => "enemy",
+        Winner::Tie => "tie",
+    }
+}
+
+fn parse_winner(word: &str) -> Option<Winner> {
+    match word {
+        "user" => Some(Winner::User),
+        "enemy" => Some(Winner::Enemy),
+        "tie" => Some(Winner::Tie),
+        _ => None,
+    }
+}
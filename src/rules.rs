@@ -0,0 +1,408 @@
+use crate::{Error, MoveType, Winner};
+use rand::Rng;
+use std::collections::HashMap;
+
+/// # RuleSet Struct
+///
+/// Describes a variant of the game: which gestures are in play and which gesture beats which.
+///
+/// ## Fields
+///
+/// - `gestures`: The moves that are valid under these rules.
+/// - `beats`: Every `(winner, loser)` pair of the "beats" relation.
+/// - `names`: Optional display names overriding `MoveType::convert_to_string()`, e.g. for the generated `Gesture` moves.
+/// - `verbs`: Optional verbs describing a `(winner, loser)` pair, e.g. "vaporizes" for Spock and Rock.
+///
+/// ## Presets
+///
+/// - **`RuleSet::classic()`**: Rock, Paper, Scissors. This is also the `Default`.
+/// - **`RuleSet::lizard_spock()`**: Rock, Paper, Scissors, Lizard, Spock.
+/// - **`RuleSet::balanced(n)`**: A generated RPS-N game for any odd `n`, where every gesture beats exactly half of the others.
+///
+/// ## Examples
+///
+/// ### Using a Preset
+///
+/// ```rust
+/// use rock_paper_scissors::{MoveType, RuleSet};
+///
+/// let rules = RuleSet::lizard_spock();
+/// assert!(rules.beats(&MoveType::Lizard, &MoveType::Spock));
+/// assert!(!rules.beats(&MoveType::Spock, &MoveType::Lizard));
+/// ```
+///
+/// ### Defining Custom Rules
+///
+/// ```rust
+/// use rock_paper_scissors::{MoveType, PlayerMoves, RuleSet, Winner};
+///
+/// // A lopsided variant where Rock beats everything.
+/// let rules = RuleSet::new(
+///     vec![MoveType::Rock, MoveType::Paper, MoveType::Scissors],
+///     vec![
+///         (MoveType::Rock, MoveType::Paper),
+///         (MoveType::Rock, MoveType::Scissors),
+///         (MoveType::Scissors, MoveType::Paper),
+///     ],
+/// );
+///
+/// let player_moves = PlayerMoves {
+///     user_move: MoveType::Rock,
+///     enemy_move: MoveType::Paper,
+/// };
+///
+/// assert_eq!(player_moves.check_who_wins_round_with(&rules), Winner::User);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleSet {
+    pub gestures: Vec<MoveType>,
+    pub beats: Vec<(MoveType, MoveType)>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::save::entries"))]
+    pub names: HashMap<MoveType, String>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::save::entries"))]
+    pub verbs: HashMap<(MoveType, MoveType), String>,
+}
+
+impl RuleSet {
+    /// Creates a rule set from its gestures and `(winner, loser)` pairs, without custom names or verbs.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, RuleSet};
+    ///
+    /// let rules = RuleSet::new(
+    ///     vec![MoveType::Rock, MoveType::Paper],
+    ///     vec![(MoveType::Paper, MoveType::Rock)],
+    /// );
+    /// assert!(rules.beats(&MoveType::Paper, &MoveType::Rock));
+    /// assert!(rules.names.is_empty());
+    /// ```
+    pub fn new(gestures: Vec<MoveType>, beats: Vec<(MoveType, MoveType)>) -> RuleSet {
+        RuleSet {
+            gestures,
+            beats,
+            names: HashMap::new(),
+            verbs: HashMap::new(),
+        }
+    }
+
+    /// The classic rules: Rock crushes Scissors, Paper covers Rock, Scissors cuts Paper.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, RuleSet};
+    ///
+    /// let rules = RuleSet::classic();
+    /// assert_eq!(rules.gestures, vec![MoveType::Rock, MoveType::Paper, MoveType::Scissors]);
+    /// assert!(rules.beats(&MoveType::Paper, &MoveType::Rock));
+    /// ```
+    pub fn classic() -> RuleSet {
+        RuleSet::with_verbs(
+            vec![MoveType::Rock, MoveType::Paper, MoveType::Scissors],
+            &[
+                (MoveType::Rock, "crushes", MoveType::Scissors),
+                (MoveType::Paper, "covers", MoveType::Rock),
+                (MoveType::Scissors, "cuts", MoveType::Paper),
+            ],
+        )
+    }
+
+    /// Rock-Paper-Scissors-Lizard-Spock, where every gesture beats exactly two others.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, RuleSet};
+    ///
+    /// let rules = RuleSet::lizard_spock();
+    /// assert_eq!(rules.gestures.len(), 5);
+    /// assert!(rules.beats(&MoveType::Spock, &MoveType::Rock));
+    /// ```
+    pub fn lizard_spock() -> RuleSet {
+        RuleSet::with_verbs(
+            vec![
+                MoveType::Rock,
+                MoveType::Paper,
+                MoveType::Scissors,
+                MoveType::Lizard,
+                MoveType::Spock,
+            ],
+            &[
+                (MoveType::Scissors, "cuts", MoveType::Paper),
+                (MoveType::Paper, "covers", MoveType::Rock),
+                (MoveType::Rock, "crushes", MoveType::Lizard),
+                (MoveType::Lizard, "poisons", MoveType::Spock),
+                (MoveType::Spock, "smashes", MoveType::Scissors),
+                (MoveType::Scissors, "decapitates", MoveType::Lizard),
+                (MoveType::Lizard, "eats", MoveType::Paper),
+                (MoveType::Paper, "disproves", MoveType::Spock),
+                (MoveType::Spock, "vaporizes", MoveType::Rock),
+                (MoveType::Rock, "crushes", MoveType::Scissors),
+            ],
+        )
+    }
+
+    /// Generates a balanced RPS-N game with `gesture_count` gestures, where every gesture beats exactly half of the others.
+    ///
+    /// Gestures are taken in the order of `MoveType::index()`, and a gesture beats another when the difference of
+    /// their indices (modulo `gesture_count`) is odd. `balanced(3)` and `balanced(5)` return `classic()` and
+    /// `lizard_spock()` respectively, including their verbs. Larger games (RPS-7, RPS-15, RPS-25, RPS-101, ...) use
+    /// `MoveType::Gesture` for their extra gestures; give them names through the `names` field.
+    ///
+    /// Returns an error if `gesture_count` is even or smaller than 3, since no balanced game exists then.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, RuleSet};
+    ///
+    /// let rules = RuleSet::balanced(101).unwrap();
+    /// assert_eq!(rules.gestures.len(), 101);
+    ///
+    /// let wins = rules.gestures.iter().filter(|other| rules.beats(&MoveType::Gesture(42), other)).count();
+    /// assert_eq!(wins, 50);
+    ///
+    /// assert!(RuleSet::balanced(4).is_err());
+    /// ```
+    pub fn balanced(gesture_count: u8) -> Result<RuleSet, Error> {
+        match gesture_count {
+            3 => return Ok(RuleSet::classic()),
+            5 => return Ok(RuleSet::lizard_spock()),
+            n if n < 3 || n % 2 == 0 => {
+                return Err(Error::InvalidRules(
+                    "A balanced game needs an odd number of at least 3 gestures".to_string(),
+                ))
+            }
+            _ => (),
+        }
+
+        let gestures: Vec<MoveType> = (0..gesture_count).map(MoveType::from_index).collect();
+        let mut beats = Vec::new();
+
+        for winner in 0..gesture_count {
+            for loser in 0..gesture_count {
+                let distance = (winner as u16 + gesture_count as u16 - loser as u16) % gesture_count as u16;
+
+                if distance % 2 == 1 {
+                    beats.push((MoveType::from_index(winner), MoveType::from_index(loser)));
+                }
+            }
+        }
+
+        Ok(RuleSet::new(gestures, beats))
+    }
+
+    fn with_verbs(gestures: Vec<MoveType>, rules: &[(MoveType, &str, MoveType)]) -> RuleSet {
+        let mut rule_set = RuleSet::new(gestures, Vec::new());
+
+        for (winner, verb, loser) in rules {
+            rule_set.beats.push((*winner, *loser));
+            rule_set.verbs.insert((*winner, *loser), verb.to_string());
+        }
+
+        rule_set
+    }
+
+    /// Returns `true` if `move_type` is one of the gestures in play.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, RuleSet};
+    ///
+    /// assert!(RuleSet::classic().contains(&MoveType::Rock));
+    /// assert!(!RuleSet::classic().contains(&MoveType::Spock));
+    /// ```
+    pub fn contains(&self, move_type: &MoveType) -> bool {
+        self.gestures.contains(move_type)
+    }
+
+    /// Returns `true` if `winner` beats `loser` under these rules.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, RuleSet};
+    ///
+    /// let rules = RuleSet::classic();
+    /// assert!(rules.beats(&MoveType::Rock, &MoveType::Scissors));
+    /// assert!(!rules.beats(&MoveType::Scissors, &MoveType::Rock));
+    /// ```
+    pub fn beats(&self, winner: &MoveType, loser: &MoveType) -> bool {
+        self.beats.iter().any(|(w, l)| w == winner && l == loser)
+    }
+
+    /// Determines the winner of a round between `user_move` and `enemy_move`.
+    ///
+    /// Two valid gestures that do not beat each other are a tie. A move that is not part of the
+    /// rule set (such as `MoveType::None`) never wins, so the round goes to the enemy.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, RuleSet, Winner};
+    ///
+    /// let rules = RuleSet::lizard_spock();
+    /// assert_eq!(rules.check_who_wins(&MoveType::Lizard, &MoveType::Paper), Winner::User);
+    /// assert_eq!(rules.check_who_wins(&MoveType::Lizard, &MoveType::Rock), Winner::Enemy);
+    /// assert_eq!(rules.check_who_wins(&MoveType::Lizard, &MoveType::Lizard), Winner::Tie);
+    /// ```
+    pub fn check_who_wins(&self, user_move: &MoveType, enemy_move: &MoveType) -> Winner {
+        if self.beats(user_move, enemy_move) {
+            Winner::User
+        } else if self.beats(enemy_move, user_move) {
+            Winner::Enemy
+        } else if self.contains(user_move) && self.contains(enemy_move) {
+            Winner::Tie
+        } else {
+            Winner::Enemy
+        }
+    }
+
+    /// Returns the gestures that lose a round in which everyone in a group throws at once, in the order of `gestures`.
+    ///
+    /// A thrown gesture loses if it is beaten by another thrown gesture that nothing thrown beats. With the classic
+    /// rules this means: if exactly two gestures appear, the beaten one loses; if one or all three appear, nobody does.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, RuleSet};
+    ///
+    /// let rules = RuleSet::classic();
+    ///
+    /// assert_eq!(rules.losing_gestures(&[MoveType::Rock, MoveType::Scissors, MoveType::Rock]), vec![MoveType::Scissors]);
+    /// assert!(rules.losing_gestures(&[MoveType::Rock, MoveType::Paper, MoveType::Scissors]).is_empty());
+    /// assert!(rules.losing_gestures(&[MoveType::Paper, MoveType::Paper]).is_empty());
+    /// ```
+    pub fn losing_gestures(&self, thrown: &[MoveType]) -> Vec<MoveType> {
+        let present: Vec<MoveType> = self.gestures.iter().filter(|gesture| thrown.contains(gesture)).copied().collect();
+        let unbeaten: Vec<MoveType> = present
+            .iter()
+            .filter(|gesture| !present.iter().any(|other| self.beats(other, gesture)))
+            .copied()
+            .collect();
+
+        present
+            .iter()
+            .filter(|gesture| unbeaten.iter().any(|winner| self.beats(winner, gesture)))
+            .copied()
+            .collect()
+    }
+
+    /// Returns every gesture that beats `move_type`, in the order of `gestures`.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, RuleSet};
+    ///
+    /// assert_eq!(RuleSet::classic().counters(&MoveType::Rock), vec![MoveType::Paper]);
+    /// assert_eq!(RuleSet::lizard_spock().counters(&MoveType::Rock), vec![MoveType::Paper, MoveType::Spock]);
+    /// ```
+    pub fn counters(&self, move_type: &MoveType) -> Vec<MoveType> {
+        self.gestures
+            .iter()
+            .filter(|gesture| self.beats(gesture, move_type))
+            .copied()
+            .collect()
+    }
+
+    /// Returns the display name of a move, preferring the `names` table over `MoveType::convert_to_string()`.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, RuleSet};
+    ///
+    /// let mut rules = RuleSet::balanced(7).unwrap();
+    /// rules.names.insert(MoveType::Gesture(5), "Fire".to_string());
+    ///
+    /// assert_eq!(rules.name(&MoveType::Gesture(5)), "Fire");
+    /// assert_eq!(rules.name(&MoveType::Gesture(6)), "Gesture 7");
+    /// ```
+    pub fn name(&self, move_type: &MoveType) -> String {
+        match self.names.get(move_type) {
+            Some(name) => name.clone(),
+            None => move_type.convert_to_string(),
+        }
+    }
+
+    /// Describes how `winner` beats `loser`, e.g. "Spock vaporizes Rock".
+    ///
+    /// Falls back to the verb "beats" when no verb is registered, and returns `None` if `winner` does not beat `loser`.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, RuleSet};
+    ///
+    /// let rules = RuleSet::lizard_spock();
+    /// assert_eq!(rules.describe(&MoveType::Spock, &MoveType::Rock), Some("Spock vaporizes Rock".to_string()));
+    /// assert_eq!(rules.describe(&MoveType::Rock, &MoveType::Spock), None);
+    ///
+    /// let rules = RuleSet::balanced(7).unwrap();
+    /// assert_eq!(rules.describe(&MoveType::Gesture(5), &MoveType::Rock), Some("Gesture 6 beats Rock".to_string()));
+    /// ```
+    pub fn describe(&self, winner: &MoveType, loser: &MoveType) -> Option<String> {
+        if !self.beats(winner, loser) {
+            return None;
+        }
+
+        let verb = match self.verbs.get(&(*winner, *loser)) {
+            Some(verb) => verb.as_str(),
+            None => "beats",
+        };
+
+        Some(format!("{} {} {}", self.name(winner), verb, self.name(loser)))
+    }
+
+    /// Picks one of the gestures of this rule set uniformly at random.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::RuleSet;
+    ///
+    /// let rules = RuleSet::balanced(15).unwrap();
+    /// assert!(rules.contains(&rules.random_move()));
+    /// ```
+    pub fn random_move(&self) -> MoveType {
+        self.random_move_with(&mut rand::rng())
+    }
+
+    /// Picks one of the gestures of this rule set uniformly at random, drawing from the given random number generator.
+    pub fn random_move_with<R: Rng + ?Sized>(&self, rng: &mut R) -> MoveType {
+        match self.gestures.len() {
+            0 => MoveType::None,
+            len => self.gestures[rng.random_range(0..len)],
+        }
+    }
+
+    /// Parses a move for this rule set from text.
+    ///
+    /// Accepts either the 1-based position of the gesture in `gestures` (so "1", "2" and "3" keep meaning Rock,
+    /// Paper and Scissors in the classic game) or the gesture's name, ignoring case and surrounding whitespace.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, RuleSet};
+    ///
+    /// let rules = RuleSet::lizard_spock();
+    /// assert_eq!(rules.parse_move("5"), Some(MoveType::Spock));
+    /// assert_eq!(rules.parse_move(" lizard "), Some(MoveType::Lizard));
+    /// assert_eq!(rules.parse_move("fire"), None);
+    /// ```
+    pub fn parse_move(&self, input: &str) -> Option<MoveType> {
+        let input = input.trim();
+
+        if let Ok(position) = input.parse::<usize>() {
+            return match position {
+                0 => None,
+                _ => self.gestures.get(position - 1).copied(),
+            };
+        }
+
+        self.gestures
+            .iter()
+            .find(|gesture| self.name(gesture).eq_ignore_ascii_case(input))
+            .copied()
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{Error, Game, GameState};
+use std::fs;
+use std::path::Path;
+
+/// Saving and loading whole game sessions. Only available with the `serde` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{Game, GameSettings, MoveType};
+///
+/// let mut game = Game::with_seed(GameSettings::from_first_to(3), 9);
+/// game.play_round(MoveType::Rock).unwrap();
+///
+/// let json = game.to_json().unwrap();
+/// let mut resumed = Game::from_json(&json).unwrap();
+///
+/// assert_eq!(resumed.scores(), game.scores());
+/// assert_eq!(resumed.play_round(MoveType::Paper), game.play_round(MoveType::Paper));
+/// ```
+impl Game {
+    /// Writes the game's `GameState` as JSON.
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(&self.state()).map_err(|err| Error::InvalidSave(err.to_string()))
+    }
+
+    /// Resumes a game from JSON written by `Game::to_json()`, against a random opponent.
+    pub fn from_json(json: &str) -> Result<Game, Error> {
+        let state: GameState = serde_json::from_str(json).map_err(|err| Error::InvalidSave(err.to_string()))?;
+        Game::from_state(state)
+    }
+
+    /// Writes the game's `GameState` as TOML.
+    pub fn to_toml(&self) -> Result<String, Error> {
+        toml::to_string(&self.state()).map_err(|err| Error::InvalidSave(err.to_string()))
+    }
+
+    /// Resumes a game from TOML written by `Game::to_toml()`, against a random opponent.
+    pub fn from_toml(text: &str) -> Result<Game, Error> {
+        let state: GameState = toml::from_str(text).map_err(|err| Error::InvalidSave(err.to_string()))?;
+        Game::from_state(state)
+    }
+
+    /// Saves the game to a file, as TOML if the path ends in `.toml` and as JSON otherwise.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let text = match is_toml(path.as_ref()) {
+            true => self.to_toml()?,
+            false => self.to_json()?,
+        };
+
+        fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// Resumes a game saved by `Game::save()`, against a random opponent.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Game, Error> {
+        let text = fs::read_to_string(path.as_ref())?;
+
+        match is_toml(path.as_ref()) {
+            true => Game::from_toml(&text),
+            false => Game::from_json(&text),
+        }
+    }
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "toml")
+}
+
+/// Serializes a number as a string, for numbers TOML and JSON readers may not hold.
+pub(crate) mod text {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    pub fn serialize<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+    }
+}
+
+/// Serializes a map as a list of `(key, value)` pairs, for maps whose keys are not strings.
+pub(crate) mod entries {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+    use std::hash::Hash;
+
+    pub fn serialize<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<(K, V)>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
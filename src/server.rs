@@ -0,0 +1,765 @@
+use crate::lobby::{Admission, Pairing};
+use crate::protocol::negotiate;
+use crate::{
+    CommitRound, Error, Game, GameSettings, Lobby, Message, Player, PlayerId, Replay, Reveal, RuleSet, Scores, Winner,
+};
+use std::cmp::Ordering;
+use std::io::{self, BufReader, Read};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How long a write to a client may block before the client is treated as gone. Writes only block once a client has
+/// stopped reading and the socket's buffer has filled up.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// # MatchRecord Struct
+///
+/// How a match played on a `Server` went.
+///
+/// - `replay`: Every round of the match. It can be saved and verified like any other `Replay`.
+/// - `winner`: How the match ended, from the point of view of the replay's user.
+/// - `forfeited_by`: The player who quit, broke their commitment, or lost their connection and did not come back in
+///   time, before the match was decided, if any. A player who quit or broke their commitment lost the match;
+///   otherwise the server's `ForfeitRule` decided it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchRecord {
+    pub replay: Replay,
+    pub winner: Winner,
+    pub forfeited_by: Option<PlayerId>,
+}
+
+/// # ForfeitRule Enum
+///
+/// Decides a match on a `Server` when a player loses their connection and does not resume the match within the
+/// grace period. A player who sends `QUIT` always loses the match.
+///
+/// - `ForfeitRule::OpponentWins`: The player who stayed wins the match. This is the `Default`.
+/// - `ForfeitRule::LeaderWins`: The player with more wins when the connection dropped wins the match; it is a draw
+///   if they are level.
+/// - `ForfeitRule::Draw`: The match ends in a draw.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ForfeitRule {
+    #[default]
+    OpponentWins,
+    LeaderWins,
+    Draw,
+}
+
+impl ForfeitRule {
+    /// Returns the winner, from the user's point of view, of a match the player on `side` left.
+    fn winner(&self, side: usize, scores: &Scores) -> Winner {
+        match self {
+            ForfeitRule::OpponentWins => from_side(side, Winner::Enemy),
+            ForfeitRule::LeaderWins => leader(scores),
+            ForfeitRule::Draw => Winner::Tie,
+        }
+    }
+}
+
+/// A player connected to the server, after the handshake.
+pub(crate) struct Connection {
+    pub(crate) player: Player,
+    pub(crate) version: u32,
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    pub(crate) fn send(&mut self, message: &Message) -> Result<(), Error> {
+        message.write_to(&mut self.writer)
+    }
+
+    /// Sends a message only if it fits into the socket's buffer right away, and fails otherwise. A client whose
+    /// buffer is full has not read what it was sent before, and is not waited for.
+    pub(crate) fn try_send(&mut self, message: &Message) -> Result<(), Error> {
+        self.writer.set_nonblocking(true)?;
+        let sent = self.send(message);
+        self.writer.set_nonblocking(false)?;
+        sent
+    }
+
+    pub(crate) fn receive(&mut self) -> Result<Message, Error> {
+        Message::read_from(&mut self.reader)
+    }
+
+    /// Returns another handle to the connection's socket.
+    pub(crate) fn stream(&self) -> Option<TcpStream> {
+        self.writer.try_clone().ok()
+    }
+
+    /// Returns `true` if the client has closed the connection, without waiting for anything it sends.
+    pub(crate) fn is_closed(&self) -> bool {
+        if !self.reader.buffer().is_empty() || self.writer.set_nonblocking(true).is_err() {
+            return false;
+        }
+
+        let closed = matches!(self.writer.peek(&mut [0]), Ok(0));
+        let _ = self.writer.set_nonblocking(false);
+        closed
+    }
+
+    /// Closes the connection once the client has read everything sent to it. Closing a socket with unread input
+    /// resets the connection, which can throw away the last messages before the client gets to read them.
+    pub(crate) fn close(self) {
+        let _ = self.writer.shutdown(Shutdown::Write);
+        let _ = self.writer.set_read_timeout(Some(Duration::from_secs(1)));
+        let _ = io::copy(&mut self.reader.take(64 * 1024), &mut io::sink());
+    }
+}
+
+/// # Server Struct
+///
+/// A TCP server for remote matches. It accepts connections and greets each client on a thread of its own with the
+/// handshake described under `Message`, hands it to its `Lobby` to be paired and plays every pair's match on its own
+/// thread. Every round is played as a `CommitRound`: the server collects both commitments before asking for any
+/// reveal, so neither player sees the other's move in advance. Players who send their move outright are committed to
+/// it by the server.
+///
+/// ## Fields
+///
+/// - `lobby`: The queues, rooms and live matches. `Server::bind()` opens a single queue named `default`.
+/// - `handshake_timeout`: How long a new connection has to say `HELLO`, and then to tell the lobby what it wants.
+/// - `reconnect_grace`: How long a player who lost their connection has to resume the match.
+/// - `forfeit_rule`: How a match is decided when a player does not come back in time.
+/// - `move_timeout`: How long a player has to send their move or commitment once a round starts, and their reveal once
+///   both players have committed. A player who runs out of time is treated as if their connection dropped.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{ConstantStrategy, Client, GameSettings, MoveType, Server, Winner};
+/// use std::thread;
+///
+/// let mut server = Server::bind("127.0.0.1:0", GameSettings::from_first_to(2)).unwrap();
+/// let address = server.local_addr().unwrap();
+/// let matches = thread::spawn(move || server.serve(1));
+///
+/// let rocky = thread::spawn(move || {
+///     let mut client = Client::connect(address, "Rocky").unwrap();
+///     client.join_queue("default").unwrap();
+///     client.play(&mut ConstantStrategy::new(MoveType::Rock))
+/// });
+/// let mut client = Client::connect(address, "Papyrus").unwrap();
+/// client.join_queue("default").unwrap();
+///
+/// assert_eq!(client.play(&mut ConstantStrategy::new(MoveType::Paper)).unwrap(), Winner::User);
+/// assert_eq!(rocky.join().unwrap().unwrap(), Winner::Enemy);
+///
+/// let record = &matches.join().unwrap().unwrap()[0];
+/// assert_eq!(record.replay.scores.user_wins + record.replay.scores.enemy_wins, 2);
+/// ```
+pub struct Server {
+    pub lobby: Lobby,
+    pub handshake_timeout: Duration,
+    pub reconnect_grace: Duration,
+    pub forfeit_rule: ForfeitRule,
+    pub move_timeout: Duration,
+    listener: TcpListener,
+}
+
+impl Server {
+    /// Listens on `address` with a single queue, named `default`, for matches with `settings` and the classic rules.
+    /// Bind to port `0` to let the system pick a free port.
+    pub fn bind<A: ToSocketAddrs>(address: A, settings: GameSettings) -> Result<Server, Error> {
+        Ok(Server {
+            lobby: Lobby::new().with_queue("default", settings, RuleSet::classic()),
+            handshake_timeout: Duration::from_secs(10),
+            reconnect_grace: Duration::from_secs(30),
+            forfeit_rule: ForfeitRule::default(),
+            move_timeout: Duration::from_secs(60),
+            listener: TcpListener::bind(address)?,
+        })
+    }
+
+    /// Replaces the rules of the `default` queue.
+    pub fn with_rules(mut self, rules: RuleSet) -> Server {
+        let settings = self.lobby.status().queues.into_iter().find(|queue| queue.name == "default");
+        let settings = settings.map(|queue| queue.settings).unwrap_or_default();
+
+        self.lobby = self.lobby.with_queue("default", settings, rules);
+        self
+    }
+
+    /// Replaces the lobby, e.g. to offer several queues.
+    pub fn with_lobby(mut self, lobby: Lobby) -> Server {
+        self.lobby = lobby;
+        self
+    }
+
+    /// Gives players who lose their connection `grace` to resume their match, and decides the match with `rule` when
+    /// they do not. A grace of zero forfeits the match as soon as the connection drops.
+    pub fn with_reconnect(mut self, grace: Duration, rule: ForfeitRule) -> Server {
+        self.reconnect_grace = grace;
+        self.forfeit_rule = rule;
+        self
+    }
+
+    /// Gives players `timeout` to send their move or commitment, and again their reveal, in every round.
+    pub fn with_move_timeout(mut self, timeout: Duration) -> Server {
+        self.move_timeout = timeout;
+        self
+    }
+
+    /// Returns the address the server listens on.
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Accepts and plays matches until accepting a connection fails.
+    pub fn run(&mut self) -> Result<(), Error> {
+        // Nobody waits for the matches to end, so they are let go as soon as they start.
+        let (started, _) = mpsc::channel();
+
+        loop {
+            let (stream, _) = self.listener.accept()?;
+            self.admit(stream, &started);
+        }
+    }
+
+    /// Accepts connections until `matches` matches have been paired, then keeps accepting, e.g. for spectators and
+    /// players resuming their match, until every match has ended. Returns how the matches went, including any paired
+    /// after the first `matches`. A match whose thread panicked is left out, so it cannot take the others with it.
+    pub fn serve(&mut self, matches: usize) -> Result<Vec<MatchRecord>, Error> {
+        let (started, new_matches) = mpsc::channel();
+        let mut handles: Vec<JoinHandle<MatchRecord>> = Vec::new();
+
+        self.listener.set_nonblocking(true)?;
+
+        let accepting = loop {
+            handles.extend(new_matches.try_iter());
+
+            if handles.len() >= matches && handles.iter().all(JoinHandle::is_finished) {
+                break Ok(());
+            }
+
+            match self.listener.accept() {
+                Ok((stream, _)) => self.admit(stream, &started),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => thread::sleep(Duration::from_millis(10)),
+                Err(err) => break Err(err),
+            }
+        };
+
+        self.listener.set_nonblocking(false)?;
+        accepting?;
+
+        Ok(handles.into_iter().filter_map(|handle| handle.join().ok()).collect())
+    }
+
+    /// Greets a new connection on a thread of its own, so a client that takes its time holds up nobody else. The
+    /// thread of the connection that completes a pair starts the match and sends its handle to `started`.
+    fn admit(&self, stream: TcpStream, started: &Sender<JoinHandle<MatchRecord>>) {
+        let host = Host {
+            lobby: self.lobby.clone(),
+            handshake_timeout: self.handshake_timeout,
+            reconnect_grace: self.reconnect_grace,
+            forfeit_rule: self.forfeit_rule,
+            move_timeout: self.move_timeout,
+        };
+        let started = started.clone();
+
+        thread::spawn(move || {
+            // A client that fails the handshake is dropped; the server keeps accepting.
+            if let Ok(Some(pairing)) = host.admit(stream) {
+                let _ = started.send(host.spawn(pairing));
+            }
+        });
+    }
+}
+
+/// What the thread of a new connection needs from its `Server`.
+struct Host {
+    lobby: Lobby,
+    handshake_timeout: Duration,
+    reconnect_grace: Duration,
+    forfeit_rule: ForfeitRule,
+    move_timeout: Duration,
+}
+
+impl Host {
+    fn spawn(self, pairing: Pairing) -> JoinHandle<MatchRecord> {
+        thread::spawn(move || play_match(&self, pairing))
+    }
+
+    /// Greets a new connection and passes its requests to the lobby until the lobby keeps it.
+    fn admit(&self, stream: TcpStream) -> Result<Option<Pairing>, Error> {
+        let mut connection = self.handshake(stream)?;
+        let version = connection.version;
+
+        loop {
+            let request = match version {
+                1 => Message::Queue(self.lobby.default_queue().unwrap_or_default()),
+                _ => match connection.receive() {
+                    Ok(Message::Quit) => return Ok(None),
+                    Ok(request) => request,
+                    Err(Error::Protocol(reason)) => {
+                        connection.send(&Message::Error(reason))?;
+                        continue;
+                    }
+                    Err(err) => return Err(err),
+                },
+            };
+
+            connection.writer.set_read_timeout(None)?;
+
+            match self.lobby.admit(connection, request) {
+                Admission::Waiting => return Ok(None),
+                Admission::Paired(pairing) => return Ok(Some(*pairing)),
+                // A version 1 client cannot pick again.
+                Admission::Answered(_) if version == 1 => return Ok(None),
+                Admission::Answered(answered) => {
+                    connection = answered;
+                    connection.writer.set_read_timeout(Some(self.handshake_timeout))?;
+                }
+            }
+        }
+    }
+
+    fn handshake(&self, stream: TcpStream) -> Result<Connection, Error> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(self.handshake_timeout))?;
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        stream.set_nodelay(true)?;
+
+        let mut connection = Connection {
+            player: Player::remote(PlayerId(0), ""),
+            version: 0,
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        };
+
+        let hello = match connection.receive() {
+            Ok(Message::Hello { versions, name }) => Ok((versions, name)),
+            Ok(other) => Err(format!("Expected HELLO, got '{other}'")),
+            Err(Error::Protocol(reason)) => Err(reason),
+            Err(err) => return Err(err),
+        };
+
+        let (versions, name) = match hello {
+            Ok(hello) => hello,
+            Err(reason) => {
+                let _ = connection.send(&Message::Error(reason));
+                return Err(Error::Protocol(String::from("The client did not say HELLO")));
+            }
+        };
+
+        let Some(version) = negotiate(&versions) else {
+            let _ = connection.send(&Message::Error(String::from("No common protocol version")));
+            return Err(Error::Protocol(String::from("No common protocol version")));
+        };
+
+        let id = self.lobby.new_player_id();
+        connection.version = version;
+        connection.player.id = id;
+        connection.player.name = match name.trim() {
+            "" => format!("Player {}", id.0 + 1),
+            name => name.to_string(),
+        };
+        connection.send(&Message::Welcome {
+            version,
+            player: connection.player.id,
+        })?;
+
+        Ok(connection)
+    }
+}
+
+/// The reason a match ended before it was decided, with the side that left.
+enum Forfeit {
+    Quit(usize),
+    Absent(usize),
+}
+
+/// The two seats of a match. A player whose connection drops keeps their seat for the grace period, and the seat
+/// remembers the commitments and reveals received for the open round.
+struct Seats {
+    connections: [Connection; 2],
+    reconnects: Receiver<(usize, Connection)>,
+    grace: Duration,
+    move_timeout: Duration,
+    round: CommitRound,
+    /// The reveal of each player's move, once known: sent by the player, or made by the server for a player who sent
+    /// `MOVE`. The server reveals those as soon as both players have committed.
+    reveals: [Option<Reveal>; 2],
+    /// Whether the server committed on each player's behalf, because they sent `MOVE`.
+    proxied: [bool; 2],
+    /// Whether each player has been asked for their reveal.
+    prompted: [bool; 2],
+    /// Whether a round has been announced and waits for moves.
+    open: bool,
+    /// When each player's time for their move, or their reveal, runs out.
+    deadlines: [Instant; 2],
+}
+
+impl Seats {
+    /// Sends a message to the player on `side`. If that fails, waits for them to come back, in which case they catch
+    /// up on the whole match instead.
+    fn send(&mut self, side: usize, message: &Message, game: &Game) -> Result<(), Forfeit> {
+        match self.connections[side].send(message) {
+            Ok(()) => Ok(()),
+            Err(_) => self.recover(side, game),
+        }
+    }
+
+    /// Sends the opening messages of the match to the player on `side`.
+    fn introduce(&mut self, side: usize, game: &Game) -> Result<(), Error> {
+        let opponent = self.connections[1 - side].player.clone();
+        let connection = &mut self.connections[side];
+
+        connection.send(&Message::Match {
+            opponent: opponent.id,
+            name: opponent.name,
+        })?;
+        connection.send(&Message::Settings(game.settings().clone()))?;
+        connection.send(&Message::Rules(game.rules().gestures.clone()))
+    }
+
+    /// Announces the next round to both players and starts their time for it.
+    fn open_round(&mut self, game: &Game) -> Result<(), Forfeit> {
+        self.round = CommitRound::new();
+        self.reveals = [None; 2];
+        self.proxied = [false; 2];
+        self.prompted = [false; 2];
+        self.open = true;
+        self.deadlines = [Instant::now() + self.move_timeout; 2];
+
+        for side in 0..2 {
+            self.send(side, &Message::Round(game.history().len() + 1), game)?;
+        }
+
+        Ok(())
+    }
+
+    /// Waits for the player on `side` to commit to their move, or to send it outright.
+    fn receive_commitment(&mut self, side: usize, game: &Game) -> Result<(), Forfeit> {
+        let rules = game.rules();
+
+        while self.round.commitment(SIDES[side]).is_none() {
+            let received = receive(&mut self.connections[side], self.deadlines[side], |message| match message {
+                Message::Commit(commitment) => Ok((commitment, None)),
+                Message::Move(move_type) if rules.contains(&move_type) => {
+                    let reveal = Reveal::random(move_type);
+                    Ok((reveal.commitment(), Some(reveal)))
+                }
+                Message::Move(move_type) => Err(format!("{} is not part of the rules", rules.name(&move_type))),
+                other => Err(format!("Expected MOVE or COMMIT, got '{other}'")),
+            });
+
+            match received {
+                Ok(Some((commitment, reveal))) => {
+                    // The round has no timeout of its own, and the player has not committed yet.
+                    let _ = self.round.commit(SIDES[side], commitment);
+                    self.proxied[side] = reveal.is_some();
+                    self.reveals[side] = reveal;
+                }
+                Ok(None) => return Err(Forfeit::Quit(side)),
+                Err(_) => self.cut_off(side, game)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reveals the moves the server committed to, and asks the players who committed on their own to reveal theirs,
+    /// passing on their opponent's commitment.
+    fn ask_for_reveals(&mut self, game: &Game) -> Result<(), Forfeit> {
+        for (side, reveal) in self.reveals.into_iter().enumerate() {
+            match reveal {
+                // The server made this reveal itself, so it matches.
+                Some(reveal) => {
+                    let _ = self.round.reveal(SIDES[side], &reveal);
+                }
+                None => {
+                    self.prompted[side] = true;
+                    self.deadlines[side] = Instant::now() + self.move_timeout;
+                    self.send(side, &self.reveal_prompt(side), game)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the opponent's commitment, which tells the player on `side` to reveal.
+    fn reveal_prompt(&self, side: usize) -> Message {
+        Message::Commit(self.round.commitment(SIDES[1 - side]).expect("both players have committed"))
+    }
+
+    /// Waits for the player on `side` to reveal their move, unless it is known already. A reveal that does not match
+    /// the commitment, or reveals a move outside the rules, forfeits the match.
+    fn receive_reveal(&mut self, side: usize, game: &Game) -> Result<(), Forfeit> {
+        while self.reveals[side].is_none() {
+            let received = receive(&mut self.connections[side], self.deadlines[side], |message| match message {
+                Message::Reveal(reveal) => Ok(reveal),
+                other => Err(format!("Expected REVEAL, got '{other}'")),
+            });
+
+            match received {
+                Ok(Some(reveal)) => {
+                    let refused = match self.round.reveal(SIDES[side], &reveal) {
+                        Ok(()) if game.rules().contains(&reveal.move_type) => None,
+                        Ok(()) => Some(format!("{} is not part of the rules", game.rules().name(&reveal.move_type))),
+                        Err(_) => Some(String::from("The reveal does not match the commitment")),
+                    };
+
+                    if let Some(reason) = refused {
+                        let _ = self.connections[side].send(&Message::Error(reason));
+                        return Err(Forfeit::Quit(side));
+                    }
+
+                    self.reveals[side] = Some(reveal);
+                }
+                Ok(None) => return Err(Forfeit::Quit(side)),
+                Err(_) => self.cut_off(side, game)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Cuts off a player who lost their connection or ran out of time, so they can only go on by resuming the match.
+    fn cut_off(&mut self, side: usize, game: &Game) -> Result<(), Forfeit> {
+        let _ = self.connections[side].writer.shutdown(Shutdown::Both);
+        self.recover(side, game)
+    }
+
+    /// Waits out the grace period for the player on `side` to resume the match. Players who come back meanwhile
+    /// take their seat again, whichever side they are on.
+    fn recover(&mut self, side: usize, game: &Game) -> Result<(), Forfeit> {
+        // Clients before version 3 cannot resume.
+        if self.connections[side].version < 3 {
+            return Err(Forfeit::Absent(side));
+        }
+
+        let deadline = Instant::now() + self.grace;
+        let _ = self.connections[1 - side].send(&Message::Waiting);
+
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            let (returning, mut connection) = self.reconnects.recv_timeout(timeout).map_err(|_| Forfeit::Absent(side))?;
+
+            // The handshake gave the new connection an id of its own; the seat keeps the player it had.
+            connection.player = self.connections[returning].player.clone();
+            self.connections[returning] = connection;
+
+            if self.catch_up(returning, game).is_ok() && returning == side {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Tells a returning player where the match stands, and what the open round still needs from them.
+    fn catch_up(&mut self, side: usize, game: &Game) -> Result<(), Error> {
+        let round = game.history().len() + 1;
+        let commitment = self.round.commitment(SIDES[side]).filter(|_| self.open);
+        let resumed = Message::Resumed {
+            player: self.connections[side].player.id,
+            round,
+            scores: from_side_scores(side, game.scores()),
+            // Only a commitment the player made themselves is theirs to reveal.
+            commitment: commitment.filter(|_| !self.proxied[side]),
+        };
+
+        self.connections[side].send(&resumed)?;
+        self.introduce(side, game)?;
+
+        let pending = match (commitment, self.reveals[side]) {
+            _ if !self.open => None,
+            (None, _) => Some(Message::Round(round)),
+            (Some(_), None) if self.prompted[side] => Some(self.reveal_prompt(side)),
+            _ => None,
+        };
+
+        match pending {
+            Some(message) => {
+                self.deadlines[side] = Instant::now() + self.move_timeout;
+                self.connections[side].send(&message)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Plays a match between two connected players. The first one plays the user's side.
+/// Spectators follow it through the lobby.
+fn play_match(host: &Host, pairing: Pairing) -> MatchRecord {
+    let Pairing {
+        id,
+        settings,
+        rules,
+        connections,
+        tokens,
+        reconnects,
+    } = pairing;
+    let [user, enemy] = [connections[0].player.clone(), connections[1].player.clone()];
+    let mut game = Game::new(settings)
+        .with_rules(rules)
+        .with_players(user, enemy)
+        .expect("every connection gets an id of its own");
+    let mut seats = Seats {
+        connections,
+        reconnects,
+        grace: host.reconnect_grace,
+        move_timeout: host.move_timeout,
+        round: CommitRound::new(),
+        reveals: [None; 2],
+        proxied: [false; 2],
+        prompted: [false; 2],
+        open: false,
+        deadlines: [Instant::now(); 2],
+    };
+
+    let (lobby, rule) = (&host.lobby, host.forfeit_rule);
+    let (winner, forfeited_by) = match run_match(lobby, id, &mut seats, &mut game, tokens) {
+        Ok(()) => (game.winner().unwrap_or_else(|| leader(game.scores())), None),
+        Err(Forfeit::Quit(side)) => (from_side(side, Winner::Enemy), Some(side)),
+        Err(Forfeit::Absent(side)) => (rule.winner(side, game.scores()), Some(side)),
+    };
+
+    // Once the lobby has let go of the match, nobody else can resume it; anyone who just did is turned away.
+    lobby.finish(id, winner, forfeited_by.is_some());
+
+    for (_, mut late) in seats.reconnects.try_iter() {
+        let _ = late.send(&Message::Error(String::from("The match is over")));
+    }
+
+    for (side, connection) in seats.connections.iter_mut().enumerate() {
+        if forfeited_by != Some(side) {
+            let _ = connection.send(&Message::Over {
+                winner: from_side(side, winner),
+                forfeit: forfeited_by.is_some(),
+            });
+        }
+    }
+
+    let record = MatchRecord {
+        replay: Replay::from_game(&game),
+        winner,
+        forfeited_by: forfeited_by.map(|side| seats.connections[side].player.id),
+    };
+
+    seats.connections.into_iter().for_each(Connection::close);
+    record
+}
+
+fn run_match(lobby: &Lobby, id: usize, seats: &mut Seats, game: &mut Game, tokens: [String; 2]) -> Result<(), Forfeit> {
+    for (side, token) in tokens.into_iter().enumerate() {
+        if seats.introduce(side, game).is_err() {
+            seats.recover(side, game)?;
+        }
+
+        if seats.connections[side].version >= 3 {
+            seats.send(side, &Message::Session(token), game)?;
+        }
+    }
+
+    while !game.is_over() {
+        let round = game.history().len() + 1;
+        seats.open_round(game)?;
+
+        for side in 0..2 {
+            seats.receive_commitment(side, game)?;
+        }
+
+        seats.ask_for_reveals(game)?;
+
+        for side in 0..2 {
+            seats.receive_reveal(side, game)?;
+        }
+
+        let moves = seats.round.moves().expect("both players have revealed");
+        let outcome = seats.round.play(game).expect("both moves are revealed, both are part of the rules and the match is on");
+        seats.open = false;
+
+        for side in 0..2 {
+            // Players who committed on their own can check their opponent's move against its commitment.
+            if let (false, Some(reveal)) = (seats.proxied[side], seats.reveals[1 - side]) {
+                seats.send(side, &Message::Reveal(reveal), game)?;
+            }
+
+            let result = Message::Result {
+                round,
+                moves: match side {
+                    0 => moves,
+                    _ => moves.swapped(),
+                },
+                winner: from_side(side, outcome.winner),
+                scores: from_side_scores(side, &outcome.scores),
+            };
+
+            seats.send(side, &result, game)?;
+
+            if side == 0 {
+                lobby.record_round(id, result);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads messages until `accept` takes one, answering the others with `ERROR` and the reason `accept` gives, or
+/// `None` once the player quits. Fails once the connection is lost or `deadline` has passed.
+fn receive<T>(
+    connection: &mut Connection,
+    deadline: Instant,
+    mut accept: impl FnMut(Message) -> Result<T, String>,
+) -> Result<Option<T>, Error> {
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+
+        if remaining.is_zero() {
+            return Err(io::Error::from(io::ErrorKind::TimedOut).into());
+        }
+
+        connection.writer.set_read_timeout(Some(remaining))?;
+
+        let reply = match connection.receive() {
+            Ok(Message::Quit) => return Ok(None),
+            Ok(message) => match accept(message) {
+                Ok(accepted) => return Ok(Some(accepted)),
+                Err(reason) => reason,
+            },
+            Err(Error::Protocol(message)) => message,
+            Err(err) => return Err(err),
+        };
+
+        connection.send(&Message::Error(reply))?;
+    }
+}
+
+/// The sides of a `CommitRound`, by seat.
+const SIDES: [Winner; 2] = [Winner::User, Winner::Enemy];
+
+/// Returns the side with more wins, or `Winner::Tie` if they are level.
+fn leader(scores: &Scores) -> Winner {
+    match scores.user_wins.cmp(&scores.enemy_wins) {
+        Ordering::Greater => Winner::User,
+        Ordering::Less => Winner::Enemy,
+        Ordering::Equal => Winner::Tie,
+    }
+}
+
+/// Turns a `Winner` of the server's game into the view of the player on `side`, who sees themselves as the user.
+fn from_side(side: usize, winner: Winner) -> Winner {
+    match (side, winner) {
+        (1, Winner::User) => Winner::Enemy,
+        (1, Winner::Enemy) => Winner::User,
+        _ => winner,
+    }
+}
+
+/// Turns `Scores` of the server's game into the view of the player on `side`.
+fn from_side_scores(side: usize, scores: &Scores) -> Scores {
+    match side {
+        0 => scores.clone(),
+        _ => Scores {
+            user_wins: scores.enemy_wins,
+            enemy_wins: scores.user_wins,
+            ties: scores.ties,
+        },
+    }
+}
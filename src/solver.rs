@@ -0,0 +1,330 @@
+use crate::{Error, MixedStrategy, MoveType, RuleSet};
+
+/// # PayoffMatrix Struct
+///
+/// A zero-sum game given by how many points the user scores for every pair of moves. The enemy always scores the
+/// negative of the user's payoff. Use it when rounds are worth more than the win/lose/tie of
+/// `PlayerMoves::check_who_wins_round()`, e.g. when a win with Rock is worth two points.
+///
+/// ## Fields
+///
+/// - `moves`: The moves available to both players.
+/// - `payoffs`: `payoffs[i][j]` is the user's payoff when the user plays `moves[i]` and the enemy plays `moves[j]`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{MoveType, PayoffMatrix, RuleSet, Strategy};
+///
+/// let mut payoffs = PayoffMatrix::from_rules(&RuleSet::classic());
+/// payoffs.set_win_value(&MoveType::Rock, 2.0);
+///
+/// let equilibrium = payoffs.solve();
+/// assert!(equilibrium.value.abs() < 1e-9);
+///
+/// // Sample the enemy's moves from the equilibrium.
+/// let mut enemy = equilibrium.enemy_strategy.clone();
+/// let enemy_move = enemy.next_move(&[]);
+/// assert!(RuleSet::classic().contains(&enemy_move));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PayoffMatrix {
+    pub moves: Vec<MoveType>,
+    pub payoffs: Vec<Vec<f64>>,
+}
+
+/// # Equilibrium Struct
+///
+/// The result of `PayoffMatrix::solve()`.
+///
+/// - `user_strategy`: The user's optimal mixed strategy, guaranteeing at least `value` on average.
+/// - `enemy_strategy`: The enemy's optimal mixed strategy, holding the user to at most `value` on average.
+/// - `value`: The expected payoff to the user per round when both sides play optimally.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Equilibrium {
+    pub user_strategy: MixedStrategy,
+    pub enemy_strategy: MixedStrategy,
+    pub value: f64,
+}
+
+const EPSILON: f64 = 1e-12;
+
+impl PayoffMatrix {
+    /// Creates a payoff matrix, checking that `payoffs` is a square matrix matching `moves`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, PayoffMatrix};
+    ///
+    /// let moves = vec![MoveType::Rock, MoveType::Paper];
+    /// assert!(PayoffMatrix::new(moves.clone(), vec![vec![0.0, -1.0], vec![1.0, 0.0]]).is_ok());
+    /// assert!(PayoffMatrix::new(moves, vec![vec![0.0]]).is_err());
+    /// ```
+    pub fn new(moves: Vec<MoveType>, payoffs: Vec<Vec<f64>>) -> Result<PayoffMatrix, Error> {
+        if moves.is_empty() {
+            return Err(Error::InvalidRules("A payoff matrix needs at least one move".to_string()));
+        }
+
+        if payoffs.len() != moves.len() || payoffs.iter().any(|row| row.len() != moves.len()) {
+            return Err(Error::InvalidRules("Payoffs must have one row and one column per move".to_string()));
+        }
+
+        Ok(PayoffMatrix { moves, payoffs })
+    }
+
+    /// Builds the payoff matrix of a `RuleSet`: `1.0` for a win, `-1.0` for a loss and `0.0` for a tie.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, PayoffMatrix, RuleSet};
+    ///
+    /// let payoffs = PayoffMatrix::from_rules(&RuleSet::classic());
+    /// assert_eq!(payoffs.payoff(&MoveType::Paper, &MoveType::Rock), 1.0);
+    /// assert_eq!(payoffs.payoff(&MoveType::Rock, &MoveType::Paper), -1.0);
+    /// ```
+    pub fn from_rules(rules: &RuleSet) -> PayoffMatrix {
+        let payoffs = rules
+            .gestures
+            .iter()
+            .map(|user_move| {
+                rules
+                    .gestures
+                    .iter()
+                    .map(|enemy_move| {
+                        if rules.beats(user_move, enemy_move) {
+                            1.0
+                        } else if rules.beats(enemy_move, user_move) {
+                            -1.0
+                        } else {
+                            0.0
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        PayoffMatrix {
+            moves: rules.gestures.clone(),
+            payoffs,
+        }
+    }
+
+    /// Returns the user's payoff when the user plays `user_move` and the enemy plays `enemy_move`, or `0.0` if
+    /// either move is not part of the matrix.
+    pub fn payoff(&self, user_move: &MoveType, enemy_move: &MoveType) -> f64 {
+        match (self.position(user_move), self.position(enemy_move)) {
+            (Some(i), Some(j)) => self.payoffs[i][j],
+            _ => 0.0,
+        }
+    }
+
+    /// Sets the payoff for a single pair of moves.
+    pub fn set_payoff(&mut self, user_move: &MoveType, enemy_move: &MoveType, payoff: f64) {
+        if let (Some(i), Some(j)) = (self.position(user_move), self.position(enemy_move)) {
+            self.payoffs[i][j] = payoff;
+        }
+    }
+
+    /// Makes every win with `winning_move` worth `value` points, for whichever player plays it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, PayoffMatrix, RuleSet};
+    ///
+    /// let mut payoffs = PayoffMatrix::from_rules(&RuleSet::classic());
+    /// payoffs.set_win_value(&MoveType::Rock, 2.0);
+    ///
+    /// assert_eq!(payoffs.payoff(&MoveType::Rock, &MoveType::Scissors), 2.0);
+    /// assert_eq!(payoffs.payoff(&MoveType::Scissors, &MoveType::Rock), -2.0);
+    /// assert_eq!(payoffs.payoff(&MoveType::Paper, &MoveType::Rock), 1.0);
+    /// ```
+    pub fn set_win_value(&mut self, winning_move: &MoveType, value: f64) {
+        let Some(i) = self.position(winning_move) else {
+            return;
+        };
+
+        for j in 0..self.moves.len() {
+            if self.payoffs[i][j] > 0.0 {
+                self.payoffs[i][j] = value;
+            }
+
+            if self.payoffs[j][i] < 0.0 {
+                self.payoffs[j][i] = -value;
+            }
+        }
+    }
+
+    /// Returns the user's expected payoff when both players follow the given mixed strategies.
+    pub fn expected_payoff(&self, user_strategy: &MixedStrategy, enemy_strategy: &MixedStrategy) -> f64 {
+        let mut total = 0.0;
+
+        for (i, user_move) in self.moves.iter().enumerate() {
+            for (j, enemy_move) in self.moves.iter().enumerate() {
+                total += user_strategy.probability(user_move) * enemy_strategy.probability(enemy_move) * self.payoffs[i][j];
+            }
+        }
+
+        total
+    }
+
+    /// Computes the mixed-strategy Nash equilibrium and the value of the game.
+    ///
+    /// The game is solved exactly as a linear program with the simplex method.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{MoveType, PayoffMatrix, RuleSet};
+    ///
+    /// let equilibrium = PayoffMatrix::from_rules(&RuleSet::classic()).solve();
+    ///
+    /// assert!(equilibrium.value.abs() < 1e-9);
+    /// assert!((equilibrium.enemy_strategy.probability(&MoveType::Rock) - 1.0 / 3.0).abs() < 1e-9);
+    /// ```
+    pub fn solve(&self) -> Equilibrium {
+        let n = self.moves.len();
+
+        // Shifting every payoff by the same amount keeps the optimal strategies and makes the value positive,
+        // which the linear program below relies on.
+        let min = self.payoffs.iter().flatten().fold(f64::INFINITY, |min, payoff| min.min(*payoff));
+        let shift = 1.0 - min;
+
+        // The enemy maximizes sum(y) subject to A y <= 1 and y >= 0, where A is the shifted payoff matrix. The
+        // tableau has one row per user move and columns for y, the slack variables and the right-hand side.
+        let width = 2 * n + 1;
+        let mut tableau = vec![vec![0.0; width]; n + 1];
+
+        for (i, row) in self.payoffs.iter().enumerate() {
+            for (j, payoff) in row.iter().enumerate() {
+                tableau[i][j] = payoff + shift;
+            }
+
+            tableau[i][n + i] = 1.0;
+            tableau[i][2 * n] = 1.0;
+        }
+
+        for value in tableau[n].iter_mut().take(n) {
+            *value = -1.0;
+        }
+
+        let mut basis: Vec<usize> = (n..2 * n).collect();
+
+        // Bland's rule: enter the lowest-index improving column and leave on the lowest-index tie, which rules
+        // out cycling on degenerate games.
+        while let Some(column) = (0..2 * n).find(|j| tableau[n][*j] < -EPSILON) {
+            let mut pivot: Option<(usize, f64)> = None;
+
+            for (row, values) in tableau.iter().enumerate().take(n) {
+                if values[column] > EPSILON {
+                    let ratio = values[2 * n] / values[column];
+
+                    let better = match pivot {
+                        None => true,
+                        Some((best_row, best_ratio)) => {
+                            ratio < best_ratio - EPSILON
+                                || ((ratio - best_ratio).abs() <= EPSILON && basis[row] < basis[best_row])
+                        }
+                    };
+
+                    if better {
+                        pivot = Some((row, ratio));
+                    }
+                }
+            }
+
+            // The feasible region is bounded because every entry of A is positive, so a pivot row always exists.
+            let Some((row, _)) = pivot else {
+                break;
+            };
+
+            let factor = tableau[row][column];
+            for value in tableau[row].iter_mut() {
+                *value /= factor;
+            }
+
+            let pivot_row = tableau[row].clone();
+            for (other, values) in tableau.iter_mut().enumerate() {
+                if other != row && values[column].abs() > EPSILON {
+                    let factor = values[column];
+
+                    for (value, pivot_value) in values.iter_mut().zip(&pivot_row) {
+                        *value -= factor * pivot_value;
+                    }
+                }
+            }
+
+            basis[row] = column;
+        }
+
+        let total = tableau[n][2 * n];
+        let mut enemy_weights = vec![0.0; n];
+
+        for (row, variable) in basis.iter().enumerate() {
+            if *variable < n {
+                enemy_weights[*variable] = tableau[row][2 * n] / total;
+            }
+        }
+
+        // The user's strategy is the dual solution, read from the objective row under the slack columns.
+        let user_weights: Vec<f64> = (0..n).map(|i| tableau[n][n + i] / total).collect();
+
+        Equilibrium {
+            user_strategy: MixedStrategy::new(self.moves.iter().copied().zip(user_weights).collect()),
+            enemy_strategy: MixedStrategy::new(self.moves.iter().copied().zip(enemy_weights).collect()),
+            value: 1.0 / total - shift,
+        }
+    }
+
+    /// Returns how much a best-responding user gains against `enemy_strategy` compared to the value of the game.
+    ///
+    /// An equilibrium strategy has an exploitability of `0.0`; higher values mean the strategy can be beaten.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{MixedStrategy, MoveType, PayoffMatrix, RuleSet};
+    ///
+    /// let payoffs = PayoffMatrix::from_rules(&RuleSet::classic());
+    /// let always_rock = MixedStrategy::new(vec![(MoveType::Rock, 1.0)]);
+    ///
+    /// assert!((payoffs.enemy_exploitability(&always_rock) - 1.0).abs() < 1e-9);
+    /// assert!(payoffs.enemy_exploitability(&MixedStrategy::uniform(&RuleSet::classic())) < 1e-9);
+    /// ```
+    pub fn enemy_exploitability(&self, enemy_strategy: &MixedStrategy) -> f64 {
+        let best_response = (0..self.moves.len())
+            .map(|i| {
+                self.moves
+                    .iter()
+                    .enumerate()
+                    .map(|(j, enemy_move)| self.payoffs[i][j] * enemy_strategy.probability(enemy_move))
+                    .sum::<f64>()
+            })
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        (best_response - self.solve().value).max(0.0)
+    }
+
+    /// Returns how much a best-responding enemy gains against `user_strategy` compared to the value of the game.
+    pub fn user_exploitability(&self, user_strategy: &MixedStrategy) -> f64 {
+        let best_response = (0..self.moves.len())
+            .map(|j| {
+                self.moves
+                    .iter()
+                    .enumerate()
+                    .map(|(i, user_move)| self.payoffs[i][j] * user_strategy.probability(user_move))
+                    .sum::<f64>()
+            })
+            .fold(f64::INFINITY, f64::min);
+
+        (self.solve().value - best_response).max(0.0)
+    }
+
+    fn position(&self, move_type: &MoveType) -> Option<usize> {
+        self.moves.iter().position(|m| m == move_type)
+    }
+}
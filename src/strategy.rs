@@ -0,0 +1,315 @@
+use crate::{MoveType, PlayerMoves, RuleSet};
+use rand::{Rng, RngCore};
+use std::collections::HashMap;
+
+mod ensemble;
+mod markov;
+mod mixed;
+
+pub use ensemble::{EnsembleStrategy, Predictor};
+pub use markov::{MarkovContext, MarkovStrategy};
+pub use mixed::MixedStrategy;
+
+/// # Strategy Trait
+///
+/// Chooses the enemy's next move. Implement it to plug a custom opponent into `PlayerMoves::build_from_input_against()`.
+///
+/// `history` holds every previous round of the match in order, where `user_move` is the move of the player the
+/// strategy is up against and `enemy_move` is the strategy's own move.
+///
+/// ## Provided Strategies
+///
+/// - **`RandomStrategy`**: Picks a gesture uniformly at random, like `MoveType::random_move()`.
+/// - **`ConstantStrategy`**: Always plays the same move.
+/// - **`CycleStrategy`**: Plays a fixed sequence of moves over and over.
+/// - **`CopyLastStrategy`**: Plays the user's previous move.
+/// - **`BeatLastStrategy`**: Plays a move that beats the user's previous move.
+/// - **`MarkovStrategy`**: Learns the user's patterns with an order-k Markov chain and plays the counter.
+/// - **`EnsembleStrategy`**: A "hard mode" opponent that picks the best of several predictors and second-guessing layers.
+/// - **`MixedStrategy`**: Samples every move from a fixed probability distribution, such as a Nash equilibrium.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rand::RngCore;
+/// use rock_paper_scissors::{MoveType, PlayerMoves, Strategy};
+///
+/// // Plays Paper on even rounds and Scissors on odd ones.
+/// struct Alternating;
+///
+/// impl Strategy for Alternating {
+///     fn next_move_with(&mut self, history: &[PlayerMoves], _rng: &mut dyn RngCore) -> MoveType {
+///         if history.len() % 2 == 0 {
+///             MoveType::Paper
+///         } else {
+///             MoveType::Scissors
+///         }
+///     }
+/// }
+///
+/// let mut strategy = Alternating;
+/// assert_eq!(strategy.next_move(&[]), MoveType::Paper);
+/// ```
+pub trait Strategy {
+    /// Returns the move to play in the round following `history`, drawing any randomness from `rng`.
+    ///
+    /// Implementations must not use any other source of randomness, so that a seeded `rng` makes them reproducible.
+    fn next_move_with(&mut self, history: &[PlayerMoves], rng: &mut dyn RngCore) -> MoveType;
+
+    /// Returns the move to play in the round following `history`, using the thread-local random number generator.
+    fn next_move(&mut self, history: &[PlayerMoves]) -> MoveType {
+        self.next_move_with(history, &mut rand::rng())
+    }
+}
+
+impl<S: Strategy + ?Sized> Strategy for Box<S> {
+    fn next_move_with(&mut self, history: &[PlayerMoves], rng: &mut dyn RngCore) -> MoveType {
+        (**self).next_move_with(history, rng)
+    }
+}
+
+/// # RandomStrategy Struct
+///
+/// Picks one of the gestures of its `RuleSet` uniformly at random, ignoring the history.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{MoveType, RandomStrategy, RuleSet, Strategy};
+///
+/// let mut strategy = RandomStrategy::with_rules(RuleSet::lizard_spock());
+/// assert!(RuleSet::lizard_spock().contains(&strategy.next_move(&[])));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RandomStrategy {
+    pub rules: RuleSet,
+}
+
+impl RandomStrategy {
+    /// Creates a `RandomStrategy` for the classic rules.
+    pub fn new() -> RandomStrategy {
+        RandomStrategy::with_rules(RuleSet::classic())
+    }
+
+    /// Creates a `RandomStrategy` picking from the gestures of `rules`.
+    pub fn with_rules(rules: RuleSet) -> RandomStrategy {
+        RandomStrategy { rules }
+    }
+}
+
+impl Default for RandomStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Strategy for RandomStrategy {
+    fn next_move_with(&mut self, _history: &[PlayerMoves], rng: &mut dyn RngCore) -> MoveType {
+        self.rules.random_move_with(rng)
+    }
+}
+
+/// # ConstantStrategy Struct
+///
+/// Always plays `move_type`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{ConstantStrategy, MoveType, Strategy};
+///
+/// let mut strategy = ConstantStrategy::new(MoveType::Rock);
+/// assert_eq!(strategy.next_move(&[]), MoveType::Rock);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConstantStrategy {
+    pub move_type: MoveType,
+}
+
+impl ConstantStrategy {
+    /// Creates a `ConstantStrategy` that always plays `move_type`.
+    pub fn new(move_type: MoveType) -> ConstantStrategy {
+        ConstantStrategy { move_type }
+    }
+}
+
+impl Strategy for ConstantStrategy {
+    fn next_move_with(&mut self, _history: &[PlayerMoves], _rng: &mut dyn RngCore) -> MoveType {
+        self.move_type
+    }
+}
+
+/// # CycleStrategy Struct
+///
+/// Plays `moves` in order, starting over after the last one. The position in the cycle is the number of rounds
+/// played so far, so the strategy can pick up a match in progress.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{CycleStrategy, MoveType, PlayerMoves, Strategy};
+///
+/// let mut strategy = CycleStrategy::new(vec![MoveType::Rock, MoveType::Paper]);
+/// let round = PlayerMoves { user_move: MoveType::Rock, enemy_move: MoveType::Rock };
+///
+/// assert_eq!(strategy.next_move(&[]), MoveType::Rock);
+/// assert_eq!(strategy.next_move(&[round]), MoveType::Paper);
+/// assert_eq!(strategy.next_move(&[round, round]), MoveType::Rock);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CycleStrategy {
+    pub moves: Vec<MoveType>,
+}
+
+impl CycleStrategy {
+    /// Creates a `CycleStrategy` repeating `moves`.
+    pub fn new(moves: Vec<MoveType>) -> CycleStrategy {
+        CycleStrategy { moves }
+    }
+}
+
+impl Strategy for CycleStrategy {
+    fn next_move_with(&mut self, history: &[PlayerMoves], _rng: &mut dyn RngCore) -> MoveType {
+        match self.moves.len() {
+            0 => MoveType::None,
+            len => self.moves[history.len() % len],
+        }
+    }
+}
+
+/// # CopyLastStrategy Struct
+///
+/// Plays whatever the user played in the previous round. The first move is picked at random from `rules`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{CopyLastStrategy, MoveType, PlayerMoves, Strategy};
+///
+/// let mut strategy = CopyLastStrategy::new();
+/// let round = PlayerMoves { user_move: MoveType::Scissors, enemy_move: MoveType::Rock };
+///
+/// assert_eq!(strategy.next_move(&[round]), MoveType::Scissors);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CopyLastStrategy {
+    pub rules: RuleSet,
+}
+
+impl CopyLastStrategy {
+    /// Creates a `CopyLastStrategy` for the classic rules.
+    pub fn new() -> CopyLastStrategy {
+        CopyLastStrategy::with_rules(RuleSet::classic())
+    }
+
+    /// Creates a `CopyLastStrategy` whose opening move is picked from `rules`.
+    pub fn with_rules(rules: RuleSet) -> CopyLastStrategy {
+        CopyLastStrategy { rules }
+    }
+}
+
+impl Default for CopyLastStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Strategy for CopyLastStrategy {
+    fn next_move_with(&mut self, history: &[PlayerMoves], rng: &mut dyn RngCore) -> MoveType {
+        match history.last() {
+            Some(last) => last.user_move,
+            None => self.rules.random_move_with(rng),
+        }
+    }
+}
+
+/// # BeatLastStrategy Struct
+///
+/// Plays a move that beats the user's previous move, picking at random when several gestures do (as in
+/// Rock-Paper-Scissors-Lizard-Spock). The first move is picked at random from `rules`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{BeatLastStrategy, MoveType, PlayerMoves, Strategy};
+///
+/// let mut strategy = BeatLastStrategy::new();
+/// let round = PlayerMoves { user_move: MoveType::Rock, enemy_move: MoveType::Scissors };
+///
+/// assert_eq!(strategy.next_move(&[round]), MoveType::Paper);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BeatLastStrategy {
+    pub rules: RuleSet,
+}
+
+impl BeatLastStrategy {
+    /// Creates a `BeatLastStrategy` for the classic rules.
+    pub fn new() -> BeatLastStrategy {
+        BeatLastStrategy::with_rules(RuleSet::classic())
+    }
+
+    /// Creates a `BeatLastStrategy` that counters moves according to `rules`.
+    pub fn with_rules(rules: RuleSet) -> BeatLastStrategy {
+        BeatLastStrategy { rules }
+    }
+}
+
+impl Default for BeatLastStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Strategy for BeatLastStrategy {
+    fn next_move_with(&mut self, history: &[PlayerMoves], rng: &mut dyn RngCore) -> MoveType {
+        let counters = match history.last() {
+            Some(last) => self.rules.counters(&last.user_move),
+            None => Vec::new(),
+        };
+
+        match counters.len() {
+            0 => self.rules.random_move_with(rng),
+            len => counters[rng.random_range(0..len)],
+        }
+    }
+}
+
+/// Returns the gestures with the best expected result against a user who plays according to `weights`.
+///
+/// Every gesture scores `+weight` for each move it beats and `-weight` for each move it loses to.
+pub(crate) fn best_responses(rules: &RuleSet, weights: &HashMap<MoveType, f64>) -> Vec<MoveType> {
+    let expected: Vec<(MoveType, f64)> = rules
+        .gestures
+        .iter()
+        .map(|gesture| {
+            let score = weights
+                .iter()
+                .map(|(user_move, weight)| {
+                    if rules.beats(gesture, user_move) {
+                        *weight
+                    } else if rules.beats(user_move, gesture) {
+                        -*weight
+                    } else {
+                        0.0
+                    }
+                })
+                .sum::<f64>();
+
+            (*gesture, score)
+        })
+        .collect();
+
+    let best = expected.iter().map(|(_, score)| *score).fold(f64::NEG_INFINITY, f64::max);
+
+    expected
+        .into_iter()
+        .filter(|(_, score)| best - score < 1e-9)
+        .map(|(gesture, _)| gesture)
+        .collect()
+}
@@ -0,0 +1,275 @@
+use super::{MarkovStrategy, Strategy};
+use crate::{MoveType, PlayerMoves, RuleSet};
+use rand::RngCore;
+use std::collections::HashMap;
+
+/// # Predictor Enum
+///
+/// The ways an `EnsembleStrategy` guesses the user's next move.
+///
+/// - `Predictor::Frequency`: The move the user has played most often.
+/// - `Predictor::HistoryMatch`: Finds the longest earlier stretch of rounds matching the most recent ones and
+///   predicts whatever the user played right after it.
+/// - `Predictor::Markov`: The most likely move according to an order-1 `MarkovStrategy`.
+/// - `Predictor::Mirror`: Assumes the user models the enemy: predicts the counter to the enemy's most frequent move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Predictor {
+    Frequency,
+    HistoryMatch,
+    Markov,
+    Mirror,
+}
+
+impl Predictor {
+    /// Every predictor, in the order an `EnsembleStrategy` evaluates them.
+    pub const ALL: [Predictor; 4] = [
+        Predictor::Frequency,
+        Predictor::HistoryMatch,
+        Predictor::Markov,
+        Predictor::Mirror,
+    ];
+}
+
+/// # EnsembleStrategy Struct
+///
+/// A "hard mode" opponent in the style of Iocaine Powder: it runs several predictors side by side and plays
+/// whichever has done best recently.
+///
+/// Every `Predictor` guesses the user's next move. Each guess is turned into three candidate moves, one per layer
+/// of second-guessing: the move that beats the guess, the move that beats *that* (in case the user expects to be
+/// countered), and the move that beats the second one. After each round every candidate is scored `+1` if it would
+/// have won, `-1` if it would have lost and `0` on a tie, with older scores multiplied by `decay`. The strategy plays
+/// the best-scoring candidate, or a random move while no candidate has a positive score, so it cannot be
+/// exploited worse than random play.
+///
+/// ## Fields
+///
+/// - `rules`: The rules used to counter predictions.
+/// - `decay`: Factor in `0.0..=1.0` applied to the candidates' scores every round. Lower values switch faster.
+/// - `max_match_length`: The longest stretch of rounds `Predictor::HistoryMatch` tries to match.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{EnsembleStrategy, MoveType, PlayerMoves, Strategy, Winner};
+///
+/// let mut strategy = EnsembleStrategy::new();
+/// let mut history = Vec::new();
+///
+/// // A user who always throws what would have beaten the enemy's previous move.
+/// let mut user_move = MoveType::Rock;
+/// for _ in 0..50 {
+///     let enemy_move = strategy.next_move(&history);
+///     history.push(PlayerMoves { user_move, enemy_move });
+///     user_move = match enemy_move {
+///         MoveType::Rock => MoveType::Paper,
+///         MoveType::Paper => MoveType::Scissors,
+///         _ => MoveType::Rock,
+///     };
+/// }
+///
+/// let enemy_wins = history[25..].iter().filter(|round| round.check_who_wins_round() == Winner::Enemy).count();
+/// assert!(enemy_wins > 20);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnsembleStrategy {
+    pub rules: RuleSet,
+    pub decay: f64,
+    pub max_match_length: usize,
+    markov: MarkovStrategy,
+    scores: Vec<f64>,
+    learned_rounds: usize,
+}
+
+const LAYERS: usize = 3;
+
+impl EnsembleStrategy {
+    /// Creates an `EnsembleStrategy` for the classic rules with a `decay` of `0.9`.
+    pub fn new() -> EnsembleStrategy {
+        EnsembleStrategy::with_rules(RuleSet::classic())
+    }
+
+    /// Creates an `EnsembleStrategy` that counters moves according to `rules`.
+    pub fn with_rules(rules: RuleSet) -> EnsembleStrategy {
+        EnsembleStrategy {
+            markov: MarkovStrategy::with_rules(rules.clone(), 1),
+            rules,
+            decay: 0.9,
+            max_match_length: 20,
+            scores: vec![0.0; Predictor::ALL.len() * LAYERS],
+            learned_rounds: 0,
+        }
+    }
+
+    /// Returns the predictor and second-guessing layer (`0` to `2`) the strategy currently trusts most, along with
+    /// its score, or `None` while it would play randomly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{EnsembleStrategy, MoveType, PlayerMoves};
+    ///
+    /// let mut strategy = EnsembleStrategy::new();
+    /// assert_eq!(strategy.favorite(&[]), None);
+    ///
+    /// let history = vec![PlayerMoves { user_move: MoveType::Rock, enemy_move: MoveType::Rock }; 10];
+    /// assert!(strategy.favorite(&history).is_some());
+    /// ```
+    pub fn favorite(&mut self, history: &[PlayerMoves]) -> Option<(Predictor, usize, f64)> {
+        self.learn(history);
+
+        let index = self.best_candidate()?;
+
+        Some((Predictor::ALL[index / LAYERS], index % LAYERS, self.scores[index]))
+    }
+
+    /// Forgets everything learned so far.
+    pub fn reset(&mut self) {
+        self.markov.reset();
+        self.scores = vec![0.0; Predictor::ALL.len() * LAYERS];
+        self.learned_rounds = 0;
+    }
+
+    fn best_candidate(&self) -> Option<usize> {
+        let mut best = None;
+
+        for (index, score) in self.scores.iter().enumerate() {
+            match best {
+                Some(best_index) if self.scores[best_index] >= *score => (),
+                _ if *score > 0.0 => best = Some(index),
+                _ => (),
+            }
+        }
+
+        best
+    }
+
+    fn learn(&mut self, history: &[PlayerMoves]) {
+        if history.len() < self.learned_rounds {
+            self.reset();
+        }
+
+        for round in self.learned_rounds..history.len() {
+            let candidates = self.candidates(&history[..round]);
+            let user_move = history[round].user_move;
+
+            for (score, candidate) in self.scores.iter_mut().zip(candidates) {
+                *score *= self.decay;
+
+                if let Some(candidate) = candidate {
+                    if self.rules.beats(&candidate, &user_move) {
+                        *score += 1.0;
+                    } else if self.rules.beats(&user_move, &candidate) {
+                        *score -= 1.0;
+                    }
+                }
+            }
+        }
+
+        self.learned_rounds = history.len();
+    }
+
+    fn candidates(&mut self, history: &[PlayerMoves]) -> Vec<Option<MoveType>> {
+        let mut candidates = Vec::with_capacity(Predictor::ALL.len() * LAYERS);
+
+        for predictor in Predictor::ALL {
+            let mut candidate = self.predict(predictor, history);
+
+            for _ in 0..LAYERS {
+                candidate = candidate.map(|prediction| self.counter(&prediction));
+                candidates.push(candidate);
+            }
+        }
+
+        candidates
+    }
+
+    fn predict(&mut self, predictor: Predictor, history: &[PlayerMoves]) -> Option<MoveType> {
+        match predictor {
+            Predictor::Frequency => self.most_frequent(history.iter().map(|round| round.user_move)),
+            Predictor::HistoryMatch => self.match_history(history),
+            Predictor::Markov => {
+                let prediction = self.markov.predict(history)?;
+
+                self.rules
+                    .gestures
+                    .iter()
+                    .copied()
+                    .filter(|gesture| prediction.contains_key(gesture))
+                    .fold(None, |best: Option<MoveType>, gesture| match best {
+                        Some(best) if prediction[&best] >= prediction[&gesture] => Some(best),
+                        _ => Some(gesture),
+                    })
+            }
+            Predictor::Mirror => {
+                let enemy_favorite = self.most_frequent(history.iter().map(|round| round.enemy_move))?;
+
+                Some(self.counter(&enemy_favorite))
+            }
+        }
+    }
+
+    fn most_frequent(&self, moves: impl Iterator<Item = MoveType>) -> Option<MoveType> {
+        let mut counts: HashMap<MoveType, usize> = HashMap::new();
+
+        for move_type in moves {
+            *counts.entry(move_type).or_insert(0) += 1;
+        }
+
+        self.rules
+            .gestures
+            .iter()
+            .copied()
+            .filter(|gesture| counts.contains_key(gesture))
+            .fold(None, |best: Option<MoveType>, gesture| match best {
+                Some(best) if counts[&best] >= counts[&gesture] => Some(best),
+                _ => Some(gesture),
+            })
+    }
+
+    fn match_history(&self, history: &[PlayerMoves]) -> Option<MoveType> {
+        let len = history.len();
+
+        for match_length in (1..=self.max_match_length.min(len.saturating_sub(1))).rev() {
+            let suffix = &history[len - match_length..];
+
+            // Search the most recent earlier occurrence first.
+            for start in (0..len - match_length).rev() {
+                if &history[start..start + match_length] == suffix {
+                    return Some(history[start + match_length].user_move);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn counter(&self, move_type: &MoveType) -> MoveType {
+        match self.rules.counters(move_type).first() {
+            Some(counter) => *counter,
+            None => *move_type,
+        }
+    }
+}
+
+impl Default for EnsembleStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Strategy for EnsembleStrategy {
+    fn next_move_with(&mut self, history: &[PlayerMoves], rng: &mut dyn RngCore) -> MoveType {
+        self.learn(history);
+
+        let best = self.best_candidate();
+        let candidates = self.candidates(history);
+
+        match best.and_then(|index| candidates[index]) {
+            Some(candidate) => candidate,
+            None => self.rules.random_move_with(rng),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use super::{best_responses, Strategy};
+use crate::{MoveType, PlayerMoves, RuleSet};
+use rand::{Rng, RngCore};
+use std::collections::HashMap;
+
+/// # MarkovContext Enum
+///
+/// Selects what a `MarkovStrategy` conditions its predictions on.
+///
+/// - `MarkovContext::UserMoves`: The user's last `order` moves.
+/// - `MarkovContext::JointMoves`: The last `order` rounds, including the enemy's own moves. This catches users who
+///   react to what the enemy played, at the cost of needing more data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MarkovContext {
+    UserMoves,
+    JointMoves,
+}
+
+/// # MarkovStrategy Struct
+///
+/// An adaptive opponent that predicts the user's next move with an order-k Markov chain and plays the move with the
+/// best expected result against that prediction.
+///
+/// After every round the strategy looks at the `order` rounds that preceded the user's move and counts which move
+/// followed that context. Older observations fade: each time a context is seen again, its counts are multiplied by
+/// `decay` before the new observation is added, so a `decay` of `1.0` never forgets and smaller values adapt faster.
+/// As long as the current context has a total weight below `min_observations`, the strategy falls back to a
+/// random move.
+///
+/// ## Fields
+///
+/// - `rules`: The rules used to counter the prediction.
+/// - `order`: How many previous rounds form the context (`k`). An order of `0` only tracks overall move frequencies.
+/// - `context`: Whether the context holds only the user's moves or both players' moves.
+/// - `decay`: Factor in `0.0..=1.0` applied to a context's counts before each new observation.
+/// - `min_observations`: Weight a context needs before its prediction is trusted.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{MarkovStrategy, MoveType, PlayerMoves, Strategy};
+///
+/// let mut strategy = MarkovStrategy::new(1);
+/// let mut history = Vec::new();
+///
+/// // The user always follows Rock with Paper and Paper with Rock.
+/// for round in 0..20 {
+///     let user_move = if round % 2 == 0 { MoveType::Rock } else { MoveType::Paper };
+///     let enemy_move = strategy.next_move(&history);
+///     history.push(PlayerMoves { user_move, enemy_move });
+/// }
+///
+/// // The last user move was Paper, so the strategy expects Rock and plays Paper.
+/// assert_eq!(strategy.next_move(&history), MoveType::Paper);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarkovStrategy {
+    pub rules: RuleSet,
+    pub order: usize,
+    pub context: MarkovContext,
+    pub decay: f64,
+    pub min_observations: f64,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    transitions: HashMap<Vec<MoveType>, HashMap<MoveType, f64>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    learned_rounds: usize,
+}
+
+impl MarkovStrategy {
+    /// Creates a `MarkovStrategy` of the given order for the classic rules.
+    ///
+    /// It conditions on the user's moves, uses a `decay` of `0.9` and trusts a context after a single observation.
+    pub fn new(order: usize) -> MarkovStrategy {
+        MarkovStrategy::with_rules(RuleSet::classic(), order)
+    }
+
+    /// Creates a `MarkovStrategy` of the given order that counters moves according to `rules`.
+    pub fn with_rules(rules: RuleSet, order: usize) -> MarkovStrategy {
+        MarkovStrategy {
+            rules,
+            order,
+            context: MarkovContext::UserMoves,
+            decay: 0.9,
+            min_observations: 1.0,
+            transitions: HashMap::new(),
+            learned_rounds: 0,
+        }
+    }
+
+    /// Returns the predicted probability of each user move for the round following `history`.
+    ///
+    /// Returns `None` when the current context has too little data, in which case `next_move()` plays randomly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{MarkovStrategy, MoveType, PlayerMoves};
+    ///
+    /// let mut strategy = MarkovStrategy::new(0);
+    /// let history = vec![PlayerMoves { user_move: MoveType::Rock, enemy_move: MoveType::Paper }; 3];
+    ///
+    /// assert_eq!(strategy.predict(&[]), None);
+    ///
+    /// let prediction = strategy.predict(&history).unwrap();
+    /// assert_eq!(prediction[&MoveType::Rock], 1.0);
+    /// ```
+    pub fn predict(&mut self, history: &[PlayerMoves]) -> Option<HashMap<MoveType, f64>> {
+        self.learn(history);
+
+        if history.len() < self.order {
+            return None;
+        }
+
+        let key = self.context_key(&history[history.len() - self.order..]);
+        let counts = self.transitions.get(&key)?;
+        let total: f64 = counts.values().sum();
+
+        if total <= 0.0 || total < self.min_observations {
+            return None;
+        }
+
+        Some(counts.iter().map(|(user_move, count)| (*user_move, count / total)).collect())
+    }
+
+    /// Forgets everything learned so far.
+    pub fn reset(&mut self) {
+        self.transitions.clear();
+        self.learned_rounds = 0;
+    }
+
+    fn learn(&mut self, history: &[PlayerMoves]) {
+        // A shorter history than what was already learned means a new match has started.
+        if history.len() < self.learned_rounds {
+            self.reset();
+        }
+
+        for round in self.learned_rounds.max(self.order)..history.len() {
+            let key = self.context_key(&history[round - self.order..round]);
+            let counts = self.transitions.entry(key).or_default();
+
+            for count in counts.values_mut() {
+                *count *= self.decay;
+            }
+
+            *counts.entry(history[round].user_move).or_insert(0.0) += 1.0;
+        }
+
+        self.learned_rounds = history.len();
+    }
+
+    fn context_key(&self, rounds: &[PlayerMoves]) -> Vec<MoveType> {
+        match self.context {
+            MarkovContext::UserMoves => rounds.iter().map(|round| round.user_move).collect(),
+            MarkovContext::JointMoves => rounds
+                .iter()
+                .flat_map(|round| [round.user_move, round.enemy_move])
+                .collect(),
+        }
+    }
+}
+
+impl Strategy for MarkovStrategy {
+    fn next_move_with(&mut self, history: &[PlayerMoves], rng: &mut dyn RngCore) -> MoveType {
+        let best = match self.predict(history) {
+            Some(prediction) => best_responses(&self.rules, &prediction),
+            None => Vec::new(),
+        };
+
+        match best.len() {
+            0 => self.rules.random_move_with(rng),
+            len => best[rng.random_range(0..len)],
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use super::Strategy;
+use crate::{Error, MoveType, PlayerMoves, RuleSet};
+use rand::{Rng, RngCore};
+
+/// # MixedStrategy Struct
+///
+/// A probability distribution over moves. Playing it as a `Strategy` samples a move from the distribution every
+/// round, ignoring the history, which is how equilibrium strategies from `PayoffMatrix::solve()` are used as opponents.
+///
+/// ## Fields
+///
+/// - `weights`: Every move with its weight. Weights do not need to add up to `1.0`; `probability()` normalizes them.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{MixedStrategy, MoveType, Strategy};
+///
+/// let mut strategy = MixedStrategy::new(vec![(MoveType::Rock, 3.0), (MoveType::Paper, 1.0)]);
+///
+/// assert_eq!(strategy.probability(&MoveType::Rock), 0.75);
+/// assert_eq!(strategy.probability(&MoveType::Scissors), 0.0);
+/// assert!(matches!(strategy.next_move(&[]), MoveType::Rock | MoveType::Paper));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MixedStrategy {
+    pub weights: Vec<(MoveType, f64)>,
+}
+
+impl MixedStrategy {
+    /// Creates a `MixedStrategy` from `(move, weight)` pairs.
+    pub fn new(weights: Vec<(MoveType, f64)>) -> MixedStrategy {
+        MixedStrategy { weights }
+    }
+
+    /// Creates a `MixedStrategy` that plays every gesture of `rules` with the same probability.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{MixedStrategy, MoveType, RuleSet};
+    ///
+    /// let strategy = MixedStrategy::uniform(&RuleSet::lizard_spock());
+    /// assert_eq!(strategy.probability(&MoveType::Spock), 0.2);
+    /// ```
+    pub fn uniform(rules: &RuleSet) -> MixedStrategy {
+        MixedStrategy::new(rules.gestures.iter().map(|gesture| (*gesture, 1.0)).collect())
+    }
+
+    /// Returns the probability of playing `move_type`.
+    pub fn probability(&self, move_type: &MoveType) -> f64 {
+        let total = self.total_weight();
+
+        if total <= 0.0 {
+            return 0.0;
+        }
+
+        self.weights
+            .iter()
+            .filter(|(m, _)| m == move_type)
+            .map(|(_, weight)| weight.max(0.0))
+            .sum::<f64>()
+            / total
+    }
+
+    /// Draws a move from the distribution, or `MoveType::None` if it has no positive weight.
+    pub fn sample(&self) -> MoveType {
+        self.sample_with(&mut rand::rng())
+    }
+
+    /// Draws a move from the distribution using the given random number generator.
+    pub fn sample_with<R: Rng + ?Sized>(&self, rng: &mut R) -> MoveType {
+        let total = self.total_weight();
+
+        if total <= 0.0 {
+            return MoveType::None;
+        }
+
+        let mut remaining = rng.random_range(0.0..total);
+
+        for (move_type, weight) in &self.weights {
+            let weight = weight.max(0.0);
+
+            if remaining < weight {
+                return *move_type;
+            }
+
+            remaining -= weight;
+        }
+
+        // Rounding can leave a sliver of `remaining`; it belongs to the last move with a positive weight.
+        self.weights
+            .iter()
+            .rev()
+            .find(|(_, weight)| *weight > 0.0)
+            .map_or(MoveType::None, |(move_type, _)| *move_type)
+    }
+
+    /// Exports the distribution as text, one `<move name> <probability>` line per move. Moves are named as `rules`
+    /// names them, so gestures with custom names survive the round trip.
+    ///
+    /// Load it back with `MixedStrategy::import()`, using the same rules.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{MixedStrategy, MoveType, RuleSet};
+    ///
+    /// let rules = RuleSet::classic();
+    /// let strategy = MixedStrategy::new(vec![(MoveType::Rock, 1.0), (MoveType::Paper, 3.0)]);
+    /// assert_eq!(strategy.export(&rules), "Rock 0.25\nPaper 0.75\n");
+    ///
+    /// let loaded = MixedStrategy::import(&strategy.export(&rules), &rules).unwrap();
+    /// assert_eq!(loaded.probability(&MoveType::Paper), 0.75);
+    /// ```
+    pub fn export(&self, rules: &RuleSet) -> String {
+        self.weights
+            .iter()
+            .map(|(move_type, _)| format!("{} {}\n", rules.name(move_type), self.probability(move_type)))
+            .collect()
+    }
+
+    /// Loads a distribution written by `MixedStrategy::export()`, resolving move names with `RuleSet::parse_move()`.
+    ///
+    /// Blank lines are skipped. Returns an error for unknown moves, malformed lines and negative weights.
+    pub fn import(text: &str, rules: &RuleSet) -> Result<MixedStrategy, Error> {
+        let mut weights = Vec::new();
+
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let Some((name, weight)) = line.rsplit_once(char::is_whitespace) else {
+                return Err(Error::InvalidStrategy("Expected a move name followed by its probability".to_string()));
+            };
+
+            let move_type = match rules.parse_move(name) {
+                Some(move_type) => move_type,
+                None => return Err(Error::InvalidStrategy(format!("Unknown move '{}' in strategy", name))),
+            };
+
+            match weight.parse::<f64>() {
+                Ok(weight) if weight >= 0.0 => weights.push((move_type, weight)),
+                _ => return Err(Error::InvalidStrategy("Probabilities must be non-negative numbers".to_string())),
+            }
+        }
+
+        Ok(MixedStrategy::new(weights))
+    }
+
+    fn total_weight(&self) -> f64 {
+        self.weights.iter().map(|(_, weight)| weight.max(0.0)).sum()
+    }
+}
+
+impl Strategy for MixedStrategy {
+    fn next_move_with(&mut self, _history: &[PlayerMoves], rng: &mut dyn RngCore) -> MoveType {
+        self.sample_with(rng)
+    }
+}
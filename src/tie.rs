@@ -0,0 +1,79 @@
+use crate::Winner;
+use rand::{Rng, RngCore};
+
+/// # TiePolicy Enum
+///
+/// Decides what a tied round is worth. `Scores` always counts ties; the policy decides how they count toward the
+/// `MatchFormat`.
+///
+/// - `TiePolicy::Replay`: A tie is replayed. It gives no points and does not count toward round limits such as
+///   `MatchFormat::FixedRounds`.
+/// - `TiePolicy::AwardNobody`: A tie is a played round that nobody wins. This is the default.
+/// - `TiePolicy::HalfPoint`: A tie is a played round worth half a point to each player, as in chess.
+/// - `TiePolicy::CapThenTiebreak { max_ties, tiebreaker }`: Up to `max_ties` ties in a row count like
+///   `TiePolicy::AwardNobody`. A further tie in a row is decided by the `tiebreaker` and counts as a win.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{GameSettings, MatchFormat, Scores, TiePolicy, Winner};
+///
+/// let settings = GameSettings::from_first_to(2).with_tie_policy(TiePolicy::HalfPoint);
+/// let scores = Scores { user_wins: 1, enemy_wins: 0, ties: 2 };
+///
+/// // One win and two half points make two points.
+/// assert_eq!(scores.winner(&settings), Some(Winner::User));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TiePolicy {
+    Replay,
+    #[default]
+    AwardNobody,
+    HalfPoint,
+    CapThenTiebreak { max_ties: u8, tiebreaker: Tiebreaker },
+}
+
+/// # Tiebreaker Enum
+///
+/// Decides a tied round under `TiePolicy::CapThenTiebreak`.
+///
+/// - `Tiebreaker::CoinFlip`: Either player wins with equal probability, drawn from the game's random number generator.
+/// - `Tiebreaker::FavorUser`: The user wins.
+/// - `Tiebreaker::FavorEnemy`: The enemy wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tiebreaker {
+    CoinFlip,
+    FavorUser,
+    FavorEnemy,
+}
+
+impl TiePolicy {
+    /// Returns how many points a single tie is worth to each player.
+    pub fn tie_value(&self) -> f64 {
+        match self {
+            TiePolicy::HalfPoint => 0.5,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns `true` if ties count as played rounds toward round limits.
+    pub fn counts_ties(&self) -> bool {
+        !matches!(self, TiePolicy::Replay)
+    }
+}
+
+impl Tiebreaker {
+    /// Decides a tied round, drawing from `rng` for a coin flip.
+    pub fn decide(&self, rng: &mut dyn RngCore) -> Winner {
+        match self {
+            Tiebreaker::CoinFlip => match rng.random_bool(0.5) {
+                true => Winner::User,
+                false => Winner::Enemy,
+            },
+            Tiebreaker::FavorUser => Winner::User,
+            Tiebreaker::FavorEnemy => Winner::Enemy,
+        }
+    }
+}
@@ -0,0 +1,524 @@
+use crate::{
+    seeded_rng, Error, Game, GameRng, GameSettings, Player, PlayerId, PlayerMoves, RuleSet, Scores, Strategy, Tiebreaker,
+    Winner,
+};
+use rand::Rng;
+use std::cmp::Ordering;
+
+/// # TournamentFormat Enum
+///
+/// How a `Tournament` pairs its entrants.
+///
+/// - `TournamentFormat::RoundRobin`: Everyone plays everyone once. With an odd number of entrants one of them sits out
+///   each round.
+/// - `TournamentFormat::SingleElimination`: Losing a match knocks a player out. Players are paired by seed, the best
+///   remaining seed against the worst, and the best seed gets the bye when the number of players is odd.
+/// - `TournamentFormat::DoubleElimination`: A player is knocked out by their second loss. Each round pairs players with
+///   the same number of losses, so undefeated players meet in the winners' bracket and players with one loss in the
+///   losers' bracket, until the last two players meet in the final. A final won by the player who came through the
+///   losers' bracket is played again.
+/// - `TournamentFormat::Swiss { rounds }`: Plays `rounds` rounds, each pairing players with the same or similar points
+///   who have not met yet. About `log2` of the number of entrants rounds is enough to find a clear winner.
+///
+/// Elimination matches must have a winner; a drawn match is decided by a coin flip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TournamentFormat {
+    RoundRobin,
+    SingleElimination,
+    DoubleElimination,
+    Swiss { rounds: usize },
+}
+
+/// # MatchResult Struct
+///
+/// The result of one match of a `Tournament`.
+///
+/// - `round`: The 1-based tournament round the match was played in.
+/// - `players`: The two players, the first of which played the user's side.
+/// - `winner`: The player who won, or `None` for a draw.
+/// - `scores`: The final scores, from the first player's point of view.
+/// - `tiebreak`: `true` if the match was drawn and the winner was decided by a coin flip.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchResult {
+    pub round: usize,
+    pub players: [PlayerId; 2],
+    pub winner: Option<PlayerId>,
+    pub scores: Scores,
+    pub tiebreak: bool,
+}
+
+/// # Standing Struct
+///
+/// A player's final placing in a `Tournament`.
+///
+/// ## Fields
+///
+/// - `rank`: The 1-based final position.
+/// - `player`: The player.
+/// - `points`: One point per won match and half a point per draw. A bye in a Swiss tournament is worth a win.
+/// - `wins`, `draws`, `losses`, `byes`: How the player's matches went.
+/// - `head_to_head`: Points scored against the players who finished with the same points.
+/// - `buchholz`: The sum of the points of every opponent the player met, a measure of how strong the opposition was.
+/// - `eliminated_in`: The round a player was knocked out in, for elimination formats. `None` for the champion and in
+///   formats without elimination.
+///
+/// Players are ranked by elimination round (later is better), then points, then `head_to_head`, then `buchholz`, and
+/// finally by the order they entered the tournament in.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Standing {
+    pub rank: usize,
+    pub player: Player,
+    pub points: f64,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub byes: usize,
+    pub head_to_head: f64,
+    pub buchholz: f64,
+    pub eliminated_in: Option<usize>,
+}
+
+/// # TournamentReport Struct
+///
+/// Everything that happened in a `Tournament`.
+///
+/// - `matches`: Every match, in the order played.
+/// - `byes`: Every bye as a `(round, player)` pair.
+/// - `standings`: The final standings, best first.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TournamentReport {
+    pub matches: Vec<MatchResult>,
+    pub byes: Vec<(usize, PlayerId)>,
+    pub standings: Vec<Standing>,
+}
+
+struct Entrant {
+    player: Player,
+    strategy: Box<dyn Strategy>,
+}
+
+/// # Tournament Struct
+///
+/// Runs a bot tournament: pairs the entrants according to a `TournamentFormat`, plays every match as a `Game` between
+/// the entrants' strategies, and ranks the players.
+///
+/// Every entrant's strategy plays every one of its matches, so strategies that learn should forget what they learned
+/// when a new match starts (the strategies of this crate do, as the history becomes shorter again).
+///
+/// Every random decision, of the strategies and of the tournament, is drawn from a `GameRng` seeded with `seed()`, so a
+/// tournament created with the same seed and entrants plays out the same way.
+///
+/// ## Fields
+///
+/// - `format`: How entrants are paired.
+/// - `settings`: The settings of every match.
+/// - `rules`: The rules of every match.
+/// - `max_rounds`: The most rounds a match may last. A match that hits the cap goes to the player with more round
+///   wins, or is drawn if they are level. This keeps two bots that always tie from playing forever.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{
+///     BeatLastStrategy, ConstantStrategy, GameSettings, MoveType, RandomStrategy, Tournament, TournamentFormat,
+/// };
+///
+/// let mut tournament = Tournament::with_seed(TournamentFormat::RoundRobin, GameSettings::from_first_to(3), 42);
+/// tournament.add_bot("Rocky", ConstantStrategy::new(MoveType::Rock));
+/// tournament.add_bot("Counter", BeatLastStrategy::new());
+/// tournament.add_bot("Chaos", RandomStrategy::new());
+///
+/// let report = tournament.run().unwrap();
+///
+/// assert_eq!(report.matches.len(), 3);
+/// assert_eq!(report.byes.len(), 3);
+/// assert_eq!(report.standings[0].rank, 1);
+/// ```
+pub struct Tournament {
+    pub format: TournamentFormat,
+    pub settings: GameSettings,
+    pub rules: RuleSet,
+    pub max_rounds: usize,
+    entrants: Vec<Entrant>,
+    rng: GameRng,
+    seed: u64,
+}
+
+impl Tournament {
+    /// Creates an empty tournament with the classic rules, using a random seed.
+    pub fn new(format: TournamentFormat, settings: GameSettings) -> Tournament {
+        Tournament::with_seed(format, settings, rand::random())
+    }
+
+    /// Creates an empty tournament with the classic rules, drawing every random decision from a `GameRng` seeded with
+    /// `seed`.
+    pub fn with_seed(format: TournamentFormat, settings: GameSettings, seed: u64) -> Tournament {
+        Tournament {
+            format,
+            settings,
+            rules: RuleSet::classic(),
+            max_rounds: 1000,
+            entrants: Vec::new(),
+            rng: seeded_rng(seed),
+            seed,
+        }
+    }
+
+    /// Replaces the rules of every match.
+    pub fn with_rules(mut self, rules: RuleSet) -> Tournament {
+        self.rules = rules;
+        self
+    }
+
+    /// Enters a player whose moves are chosen by `strategy`. Entrants are seeded in the order they are added.
+    pub fn add_entrant<S: Strategy + 'static>(&mut self, player: Player, strategy: S) {
+        self.entrants.push(Entrant {
+            player,
+            strategy: Box::new(strategy),
+        });
+    }
+
+    /// Enters a bot with the given name and returns its id. Bots are numbered in the order they are added.
+    pub fn add_bot<S: Strategy + 'static>(&mut self, name: &str, strategy: S) -> PlayerId {
+        let id = PlayerId(self.entrants.len());
+        self.add_entrant(Player::bot(id, name), strategy);
+        id
+    }
+
+    /// Returns every entered player, in seed order.
+    pub fn players(&self) -> Vec<&Player> {
+        self.entrants.iter().map(|entrant| &entrant.player).collect()
+    }
+
+    /// Returns the seed of the tournament's random number generator.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Plays the whole tournament and returns its matches and standings.
+    ///
+    /// Returns an error if there are fewer than two entrants, if two entrants share an id, or if a strategy plays a
+    /// move that is not part of the rules.
+    pub fn run(&mut self) -> Result<TournamentReport, Error> {
+        if self.entrants.len() < 2 {
+            return Err(Error::InvalidSetting(String::from("A tournament needs at least 2 entrants")));
+        }
+
+        for (index, entrant) in self.entrants.iter().enumerate() {
+            if self.entrants[..index].iter().any(|other| other.player.id == entrant.player.id) {
+                return Err(Error::InvalidSetting(format!("Player id {} is used twice", entrant.player.id)));
+            }
+        }
+
+        let mut log = Log::default();
+
+        match self.format {
+            TournamentFormat::RoundRobin => self.run_round_robin(&mut log)?,
+            TournamentFormat::SingleElimination => self.run_elimination(1, &mut log)?,
+            TournamentFormat::DoubleElimination => self.run_elimination(2, &mut log)?,
+            TournamentFormat::Swiss { rounds } => self.run_swiss(rounds, &mut log)?,
+        }
+
+        Ok(self.report(log))
+    }
+
+    /// Pairs everyone with everyone using the circle method.
+    fn run_round_robin(&mut self, log: &mut Log) -> Result<(), Error> {
+        let mut seats: Vec<Option<usize>> = (0..self.entrants.len()).map(Some).collect();
+
+        if seats.len() % 2 == 1 {
+            seats.push(None);
+        }
+
+        for round in 1..seats.len() {
+            for i in 0..seats.len() / 2 {
+                match (seats[i], seats[seats.len() - 1 - i]) {
+                    (Some(a), Some(b)) => self.play_match(round, a, b, false, log)?,
+                    (Some(player), None) | (None, Some(player)) => log.byes.push((round, player)),
+                    (None, None) => (),
+                }
+            }
+
+            seats[1..].rotate_right(1);
+        }
+
+        Ok(())
+    }
+
+    /// Plays rounds until a single player has fewer than `max_losses` losses.
+    fn run_elimination(&mut self, max_losses: usize, log: &mut Log) -> Result<(), Error> {
+        let mut round = 0;
+
+        loop {
+            let alive: Vec<usize> = (0..self.entrants.len()).filter(|p| log.losses(*p) < max_losses).collect();
+
+            if alive.len() <= 1 {
+                return Ok(());
+            }
+
+            round += 1;
+
+            let brackets: Vec<Vec<usize>> = (0..max_losses)
+                .map(|losses| alive.iter().copied().filter(|p| log.losses(*p) == losses).collect())
+                .collect();
+
+            // Once no bracket has two players left, the bracket winners meet in the final.
+            let brackets = match brackets.iter().all(|bracket| bracket.len() <= 1) {
+                true => vec![alive],
+                false => brackets,
+            };
+
+            for bracket in brackets {
+                if bracket.len() % 2 == 1 {
+                    log.byes.push((round, bracket[0]));
+                }
+
+                let paired = &bracket[bracket.len() % 2..];
+
+                for i in 0..paired.len() / 2 {
+                    let (a, b) = (paired[i], paired[paired.len() - 1 - i]);
+                    self.play_match(round, a, b, true, log)?;
+
+                    let loser = match log.matches.last().and_then(|result| result.winner) {
+                        Some(winner) if winner == a => b,
+                        _ => a,
+                    };
+
+                    if log.losses(loser) == max_losses {
+                        log.eliminated[loser] = Some(round);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pairs players with similar points who have not met yet.
+    fn run_swiss(&mut self, rounds: usize, log: &mut Log) -> Result<(), Error> {
+        for round in 1..=rounds {
+            let mut order: Vec<usize> = (0..self.entrants.len()).collect();
+            order.sort_by(|a, b| log.points(*b, true).total_cmp(&log.points(*a, true)));
+
+            if order.len() % 2 == 1 {
+                // The lowest ranked player who has not had a bye yet sits out.
+                let bye = order
+                    .iter()
+                    .rposition(|p| !log.byes.iter().any(|(_, other)| other == p))
+                    .unwrap_or(order.len() - 1);
+                log.byes.push((round, order.remove(bye)));
+            }
+
+            while !order.is_empty() {
+                let a = order.remove(0);
+                let opponent = order.iter().position(|b| !log.have_met(a, *b)).unwrap_or(0);
+                let b = order.remove(opponent);
+
+                self.play_match(round, a, b, false, log)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn play_match(&mut self, round: usize, a: usize, b: usize, decisive: bool, log: &mut Log) -> Result<(), Error> {
+        let match_seed = self.rng.random();
+        let mut rng = seeded_rng(match_seed);
+        let mut game = Game::with_seed(self.settings.clone(), match_seed)
+            .with_rules(self.rules.clone())
+            .with_players(self.entrants[a].player.clone(), self.entrants[b].player.clone())?;
+
+        let Ok([first, second]) = self.entrants.get_disjoint_mut([a, b]) else {
+            return Err(Error::InvalidSetting(String::from("A player cannot play against themselves")));
+        };
+
+        while !game.is_over() && game.history().len() < self.max_rounds {
+            let history = game.moves();
+            let swapped: Vec<PlayerMoves> = history.iter().map(PlayerMoves::swapped).collect();
+            let moves = PlayerMoves {
+                user_move: first.strategy.next_move_with(&swapped, &mut rng),
+                enemy_move: second.strategy.next_move_with(&history, &mut rng),
+            };
+
+            game.play_moves(moves)?;
+        }
+
+        let scores = game.scores().clone();
+        let mut winner = match game.winner() {
+            Some(Winner::User) => Some(a),
+            Some(Winner::Enemy) => Some(b),
+            _ => match scores.user_wins.cmp(&scores.enemy_wins) {
+                Ordering::Greater => Some(a),
+                Ordering::Less => Some(b),
+                Ordering::Equal => None,
+            },
+        };
+
+        let tiebreak = decisive && winner.is_none();
+
+        if tiebreak {
+            winner = match Tiebreaker::CoinFlip.decide(&mut self.rng) {
+                Winner::User => Some(a),
+                _ => Some(b),
+            };
+        }
+
+        log.matches.push(Played {
+            round,
+            players: [a, b],
+            winner,
+            scores,
+            tiebreak,
+        });
+        log.eliminated.resize(self.entrants.len(), None);
+
+        Ok(())
+    }
+
+    fn report(&self, log: Log) -> TournamentReport {
+        let swiss = matches!(self.format, TournamentFormat::Swiss { .. });
+        let eliminating = matches!(
+            self.format,
+            TournamentFormat::SingleElimination | TournamentFormat::DoubleElimination
+        );
+        let points: Vec<f64> = (0..self.entrants.len()).map(|p| log.points(p, swiss)).collect();
+
+        let mut standings: Vec<(usize, Standing)> = (0..self.entrants.len())
+            .map(|p| {
+                let played: Vec<&Played> = log.matches.iter().filter(|m| m.players.contains(&p)).collect();
+                let opponents = played.iter().map(|m| m.opponent_of(p));
+
+                (p, Standing {
+                    rank: 0,
+                    player: self.entrants[p].player.clone(),
+                    points: points[p],
+                    wins: played.iter().filter(|m| m.winner == Some(p)).count(),
+                    draws: played.iter().filter(|m| m.winner.is_none()).count(),
+                    losses: played.iter().filter(|m| m.winner.is_some_and(|w| w != p)).count(),
+                    byes: log.byes.iter().filter(|(_, player)| *player == p).count(),
+                    head_to_head: 0.0,
+                    buchholz: opponents.map(|opponent| points[opponent]).sum(),
+                    eliminated_in: match eliminating {
+                        true => log.eliminated.get(p).copied().flatten(),
+                        false => None,
+                    },
+                })
+            })
+            .collect();
+
+        for i in 0..standings.len() {
+            let (p, standing) = &standings[i];
+            let tied: Vec<usize> = standings
+                .iter()
+                .filter(|(q, other)| q != p && other.points == standing.points && other.eliminated_in == standing.eliminated_in)
+                .map(|(q, _)| *q)
+                .collect();
+
+            standings[i].1.head_to_head = log
+                .matches
+                .iter()
+                .filter(|m| m.players.contains(p) && tied.contains(&m.opponent_of(*p)))
+                .map(|m| m.points_for(*p))
+                .sum();
+        }
+
+        standings.sort_by(|(p, a), (q, b)| {
+            let stage = |standing: &Standing| standing.eliminated_in.unwrap_or(usize::MAX);
+
+            stage(b)
+                .cmp(&stage(a))
+                .then(b.points.total_cmp(&a.points))
+                .then(b.head_to_head.total_cmp(&a.head_to_head))
+                .then(b.buchholz.total_cmp(&a.buchholz))
+                .then(p.cmp(q))
+        });
+
+        let id = |p: usize| self.entrants[p].player.id;
+
+        TournamentReport {
+            matches: log
+                .matches
+                .iter()
+                .map(|m| MatchResult {
+                    round: m.round,
+                    players: [id(m.players[0]), id(m.players[1])],
+                    winner: m.winner.map(id),
+                    scores: m.scores.clone(),
+                    tiebreak: m.tiebreak,
+                })
+                .collect(),
+            byes: log.byes.iter().map(|(round, p)| (*round, id(*p))).collect(),
+            standings: standings
+                .into_iter()
+                .enumerate()
+                .map(|(index, (_, standing))| Standing {
+                    rank: index + 1,
+                    ..standing
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A match as recorded while the tournament runs, with players as entrant indices.
+struct Played {
+    round: usize,
+    players: [usize; 2],
+    winner: Option<usize>,
+    scores: Scores,
+    tiebreak: bool,
+}
+
+impl Played {
+    fn opponent_of(&self, player: usize) -> usize {
+        match self.players[0] == player {
+            true => self.players[1],
+            false => self.players[0],
+        }
+    }
+
+    fn points_for(&self, player: usize) -> f64 {
+        match self.winner {
+            Some(winner) if winner == player => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Log {
+    matches: Vec<Played>,
+    byes: Vec<(usize, usize)>,
+    eliminated: Vec<Option<usize>>,
+}
+
+impl Log {
+    fn points(&self, player: usize, byes_count: bool) -> f64 {
+        let matches: f64 = self
+            .matches
+            .iter()
+            .filter(|m| m.players.contains(&player))
+            .map(|m| m.points_for(player))
+            .sum();
+        let byes = self.byes.iter().filter(|(_, p)| *p == player).count();
+
+        match byes_count {
+            true => matches + byes as f64,
+            false => matches,
+        }
+    }
+
+    fn losses(&self, player: usize) -> usize {
+        self.matches
+            .iter()
+            .filter(|m| m.players.contains(&player) && m.winner.is_some_and(|winner| winner != player))
+            .count()
+    }
+
+    fn have_met(&self, a: usize, b: usize) -> bool {
+        self.matches.iter().any(|m| m.players.contains(&a) && m.players.contains(&b))
+    }
+}
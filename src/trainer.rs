@@ -0,0 +1,201 @@
+use crate::{MixedStrategy, PayoffMatrix, RuleSet};
+
+/// # RegretTrainer Struct
+///
+/// Trains opponent strategies offline through self-play with regret matching.
+///
+/// Both sides start from the uniform strategy. Every iteration each side computes how much better every move would
+/// have done against the other side's current strategy (its regret), then plays the next iteration in proportion to
+/// its accumulated positive regret (regret matching+). The *average* strategy over all iterations converges toward a
+/// Nash equilibrium of the game, for any `RuleSet` or custom `PayoffMatrix`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use rock_paper_scissors::{MoveType, PayoffMatrix, RegretTrainer, RuleSet, Strategy};
+///
+/// let mut payoffs = PayoffMatrix::from_rules(&RuleSet::classic());
+/// payoffs.set_win_value(&MoveType::Rock, 2.0);
+///
+/// let mut trainer = RegretTrainer::new(payoffs);
+/// let reports = trainer.train(2000);
+/// assert!(reports.last().unwrap().exploitability < 0.05);
+///
+/// // Use the trained distribution as the enemy.
+/// let mut enemy = trainer.average_enemy_strategy();
+/// assert!(RuleSet::classic().contains(&enemy.next_move(&[])));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegretTrainer {
+    pub payoffs: PayoffMatrix,
+    user_regrets: Vec<f64>,
+    enemy_regrets: Vec<f64>,
+    user_strategy_sum: Vec<f64>,
+    enemy_strategy_sum: Vec<f64>,
+    iterations: usize,
+}
+
+/// # TrainingReport Struct
+///
+/// Progress of a `RegretTrainer` after one iteration.
+///
+/// - `iteration`: The number of iterations run so far.
+/// - `exploitability`: How much a best response gains against the average strategies, summed over both sides.
+///   It is `0.0` exactly at an equilibrium.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrainingReport {
+    pub iteration: usize,
+    pub exploitability: f64,
+}
+
+impl RegretTrainer {
+    /// Creates a trainer for the given payoffs.
+    pub fn new(payoffs: PayoffMatrix) -> RegretTrainer {
+        let n = payoffs.moves.len();
+
+        RegretTrainer {
+            payoffs,
+            user_regrets: vec![0.0; n],
+            enemy_regrets: vec![0.0; n],
+            user_strategy_sum: vec![0.0; n],
+            enemy_strategy_sum: vec![0.0; n],
+            iterations: 0,
+        }
+    }
+
+    /// Creates a trainer for the win/lose/tie payoffs of `rules`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rock_paper_scissors::{RegretTrainer, RuleSet};
+    ///
+    /// let mut trainer = RegretTrainer::from_rules(&RuleSet::lizard_spock());
+    /// trainer.train(10);
+    /// assert_eq!(trainer.iterations(), 10);
+    /// ```
+    pub fn from_rules(rules: &RuleSet) -> RegretTrainer {
+        RegretTrainer::new(PayoffMatrix::from_rules(rules))
+    }
+
+    /// Returns the number of iterations run so far.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Runs `iterations` rounds of self-play and returns a report for each of them.
+    pub fn train(&mut self, iterations: usize) -> Vec<TrainingReport> {
+        (0..iterations).map(|_| self.iterate()).collect()
+    }
+
+    /// Runs a single round of self-play.
+    pub fn iterate(&mut self) -> TrainingReport {
+        self.iterations += 1;
+        let weight = self.iterations as f64;
+
+        // The user updates first and the enemy then responds to the user's new strategy. Alternating the updates
+        // and weighting later iterations more heavily in the average makes the average converge much faster.
+        let enemy_strategy = RegretTrainer::current_strategy(&self.enemy_regrets);
+        let user_strategy = RegretTrainer::current_strategy(&self.user_regrets);
+        let user_values = self.user_values(&enemy_strategy);
+        RegretTrainer::accumulate(&mut self.user_regrets, &user_values, &user_strategy);
+
+        for (sum, probability) in self.user_strategy_sum.iter_mut().zip(&user_strategy) {
+            *sum += weight * probability;
+        }
+
+        let user_strategy = RegretTrainer::current_strategy(&self.user_regrets);
+        let enemy_values = self.enemy_values(&user_strategy);
+        RegretTrainer::accumulate(&mut self.enemy_regrets, &enemy_values, &enemy_strategy);
+
+        for (sum, probability) in self.enemy_strategy_sum.iter_mut().zip(&enemy_strategy) {
+            *sum += weight * probability;
+        }
+
+        TrainingReport {
+            iteration: self.iterations,
+            exploitability: self.exploitability(),
+        }
+    }
+
+    /// Returns the user's average strategy over all iterations.
+    pub fn average_user_strategy(&self) -> MixedStrategy {
+        self.average(&self.user_strategy_sum)
+    }
+
+    /// Returns the enemy's average strategy over all iterations, ready to be played as a `Strategy`.
+    pub fn average_enemy_strategy(&self) -> MixedStrategy {
+        self.average(&self.enemy_strategy_sum)
+    }
+
+    /// Returns the exploitability of the current average strategies, as reported by `iterate()`.
+    pub fn exploitability(&self) -> f64 {
+        let user = RegretTrainer::normalize(&self.user_strategy_sum);
+        let enemy = RegretTrainer::normalize(&self.enemy_strategy_sum);
+
+        let best_user = self.user_values(&enemy).into_iter().fold(f64::NEG_INFINITY, f64::max);
+        let best_enemy = self.enemy_values(&user).into_iter().fold(f64::NEG_INFINITY, f64::max);
+
+        // In a zero-sum game the two best responses add up to zero exactly at an equilibrium.
+        (best_user + best_enemy).max(0.0)
+    }
+
+    fn user_values(&self, enemy_strategy: &[f64]) -> Vec<f64> {
+        self.payoffs
+            .payoffs
+            .iter()
+            .map(|row| row.iter().zip(enemy_strategy).map(|(payoff, q)| payoff * q).sum())
+            .collect()
+    }
+
+    fn enemy_values(&self, user_strategy: &[f64]) -> Vec<f64> {
+        (0..self.payoffs.moves.len())
+            .map(|j| {
+                -self
+                    .payoffs
+                    .payoffs
+                    .iter()
+                    .zip(user_strategy)
+                    .map(|(row, p)| row[j] * p)
+                    .sum::<f64>()
+            })
+            .collect()
+    }
+
+    fn accumulate(regrets: &mut [f64], values: &[f64], strategy: &[f64]) {
+        let expected: f64 = values.iter().zip(strategy).map(|(value, p)| value * p).sum();
+
+        // Regret matching+: negative regrets are dropped, so a move that becomes good again is picked up right away.
+        for (regret, value) in regrets.iter_mut().zip(values) {
+            *regret = (*regret + value - expected).max(0.0);
+        }
+    }
+
+    fn current_strategy(regrets: &[f64]) -> Vec<f64> {
+        let positive: Vec<f64> = regrets.iter().map(|regret| regret.max(0.0)).collect();
+
+        RegretTrainer::normalize(&positive)
+    }
+
+    fn normalize(weights: &[f64]) -> Vec<f64> {
+        let total: f64 = weights.iter().sum();
+
+        match total > 0.0 {
+            true => weights.iter().map(|weight| weight / total).collect(),
+            false => vec![1.0 / weights.len() as f64; weights.len()],
+        }
+    }
+
+    fn average(&self, strategy_sum: &[f64]) -> MixedStrategy {
+        MixedStrategy::new(
+            self.payoffs
+                .moves
+                .iter()
+                .copied()
+                .zip(RegretTrainer::normalize(strategy_sum))
+                .collect(),
+        )
+    }
+}
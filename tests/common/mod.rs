@@ -0,0 +1,53 @@
+// Each test file uses a different part of these helpers.
+#![allow(dead_code)]
+
+use rock_paper_scissors::*;
+use std::net::SocketAddr;
+use std::thread::{self, JoinHandle};
+
+pub type Serving = JoinHandle<Result<Vec<MatchRecord>, Error>>;
+
+/// Binds a server to a free port of this machine, with a single queue named `default`.
+pub fn local_server(settings: GameSettings) -> Server {
+    Server::bind("127.0.0.1:0", settings).unwrap()
+}
+
+/// Serves `matches` matches on a thread of their own.
+pub fn start_server(mut server: Server, matches: usize) -> (SocketAddr, Serving) {
+    let address = server.local_addr().unwrap();
+
+    (address, thread::spawn(move || server.serve(matches)))
+}
+
+/// Connects a client and waits until the server has put it in `queue`, so clients are paired in a known order.
+pub fn connect(address: SocketAddr, name: &str, queue: &str) -> Client {
+    let mut client = Client::connect(address, name).unwrap();
+    client.join_queue(queue).unwrap();
+    assert_eq!(client.receive().unwrap(), Message::Waiting);
+    client
+}
+
+/// Pairs two players in `queue` and reads up to the first round. The first one plays the user's side.
+pub fn start_match(address: SocketAddr, queue: &str) -> (Client, Client) {
+    let mut user = connect(address, "User", queue);
+    let mut enemy = Client::connect(address, "Enemy").unwrap();
+    enemy.join_queue(queue).unwrap();
+
+    skip_to_round(&mut user, 1);
+    skip_to_round(&mut enemy, 1);
+    (user, enemy)
+}
+
+/// Reads messages up to the start of `round`.
+pub fn skip_to_round(client: &mut Client, round: usize) {
+    while client.receive().unwrap() != Message::Round(round) {}
+}
+
+/// Sends both moves of the open round and reads both results.
+pub fn play_round(user: &mut Client, enemy: &mut Client, user_move: MoveType, enemy_move: MoveType) {
+    user.send(&Message::Move(user_move)).unwrap();
+    enemy.send(&Message::Move(enemy_move)).unwrap();
+
+    assert!(matches!(user.receive().unwrap(), Message::Result { .. }));
+    assert!(matches!(enemy.receive().unwrap(), Message::Result { .. }));
+}
@@ -0,0 +1,126 @@
+use rock_paper_scissors::*;
+use std::time::{Duration, Instant};
+
+fn committed_round(user: &Reveal, enemy: &Reveal) -> CommitRound {
+    let mut round = CommitRound::new();
+    round.commit(Winner::User, user.commitment()).unwrap();
+    round.commit(Winner::Enemy, enemy.commitment()).unwrap();
+    round
+}
+
+#[test]
+fn test_commitments_hide_and_bind_the_move() {
+    let mut rng = seeded_rng(1);
+    let reveal = Reveal::new(MoveType::Rock, &mut rng);
+
+    assert!(reveal.matches(&reveal.commitment()));
+    assert_ne!(Reveal::new(MoveType::Rock, &mut rng).commitment(), reveal.commitment());
+
+    let changed = Reveal {
+        move_type: MoveType::Paper,
+        ..reveal
+    };
+    assert!(!changed.matches(&reveal.commitment()));
+}
+
+#[test]
+fn test_wire_format() {
+    let reveal = Reveal {
+        move_type: MoveType::Spock,
+        nonce: [0xab; 32],
+    };
+
+    assert_eq!(reveal.to_string(), format!("3 {}", "ab".repeat(32)));
+    assert_eq!(reveal.to_string().parse::<Reveal>().unwrap(), reveal);
+
+    let commitment = reveal.commitment();
+    assert_eq!(commitment.to_string().len(), 64);
+    assert_eq!(format!(" {commitment}\n").parse::<Commitment>().unwrap(), commitment);
+
+    assert!(matches!("12".parse::<Commitment>(), Err(Error::Protocol(_))));
+    assert!(matches!("rock abc".parse::<Reveal>(), Err(Error::Protocol(_))));
+}
+
+#[test]
+fn test_full_round() {
+    let user = Reveal::random(MoveType::Scissors);
+    let enemy = Reveal::random(MoveType::Rock);
+    let mut round = CommitRound::new();
+    let mut game = Game::new(GameSettings::from_first_to(2));
+
+    assert_eq!(round.phase(), CommitPhase::Committing);
+    round.commit(Winner::Enemy, enemy.commitment()).unwrap();
+    assert_eq!(round.commitment(Winner::Enemy), Some(enemy.commitment()));
+    assert!(matches!(round.reveal(Winner::Enemy, &enemy), Err(Error::Protocol(_))));
+    assert!(matches!(round.play(&mut game), Err(Error::Protocol(_))));
+
+    round.commit(Winner::User, user.commitment()).unwrap();
+    assert_eq!(round.phase(), CommitPhase::Revealing);
+
+    round.reveal(Winner::Enemy, &enemy).unwrap();
+    round.reveal(Winner::User, &user).unwrap();
+    assert_eq!(round.phase(), CommitPhase::Revealed);
+    assert_eq!(round.moves(), Some(PlayerMoves {
+        user_move: MoveType::Scissors,
+        enemy_move: MoveType::Rock,
+    }));
+
+    let outcome = round.play(&mut game).unwrap();
+    assert_eq!(outcome.winner, Winner::Enemy);
+    assert_eq!(game.scores().enemy_wins, 1);
+}
+
+#[test]
+fn test_mismatched_reveal_is_refused() {
+    let user = Reveal::random(MoveType::Rock);
+    let enemy = Reveal::random(MoveType::Rock);
+    let mut round = committed_round(&user, &enemy);
+
+    let cheat = Reveal {
+        move_type: MoveType::Paper,
+        ..enemy
+    };
+
+    assert_eq!(round.reveal(Winner::Enemy, &cheat), Err(Error::CommitmentMismatch(Winner::Enemy)));
+    assert_eq!(
+        Error::CommitmentMismatch(Winner::Enemy).to_string(),
+        "rock-paper-scissors: err: Enemy's reveal does not match their commitment"
+    );
+    assert_eq!(round.moves(), None);
+
+    round.reveal(Winner::Enemy, &enemy).unwrap();
+    assert!(matches!(round.reveal(Winner::Enemy, &enemy), Err(Error::Protocol(_))));
+}
+
+#[test]
+fn test_out_of_turn_messages() {
+    let user = Reveal::random(MoveType::Rock);
+    let mut round = CommitRound::new();
+
+    round.commit(Winner::User, user.commitment()).unwrap();
+    assert!(matches!(round.commit(Winner::User, user.commitment()), Err(Error::Protocol(_))));
+    assert!(matches!(round.commit(Winner::Tie, user.commitment()), Err(Error::Protocol(_))));
+}
+
+#[test]
+fn test_timeouts() {
+    let user = Reveal::random(MoveType::Rock);
+    let enemy = Reveal::random(MoveType::Paper);
+    let mut round = CommitRound::with_timeout(Duration::from_millis(50));
+    let later = Instant::now() + Duration::from_secs(1);
+
+    round.commit(Winner::User, user.commitment()).unwrap();
+    assert_eq!(round.check_timeout(Instant::now()), Ok(()));
+    assert_eq!(round.check_timeout(later), Err(Error::Timeout(Winner::Enemy)));
+
+    round.commit(Winner::Enemy, enemy.commitment()).unwrap();
+    round.reveal(Winner::Enemy, &enemy).unwrap();
+    assert_eq!(round.check_timeout(later), Err(Error::Timeout(Winner::User)));
+
+    std::thread::sleep(Duration::from_millis(60));
+    assert_eq!(round.reveal(Winner::User, &user), Err(Error::Timeout(Winner::User)));
+
+    let mut untimed = committed_round(&user, &enemy);
+    assert_eq!(untimed.check_timeout(later), Ok(()));
+    untimed.reveal(Winner::User, &user).unwrap();
+}
@@ -0,0 +1,56 @@
+use rock_paper_scissors::*;
+
+fn enemy_wins_against<F: FnMut(&[PlayerMoves]) -> MoveType>(mut user: F, rounds: usize) -> usize {
+    let mut strategy = EnsembleStrategy::new();
+    let mut history = Vec::new();
+
+    for _ in 0..rounds {
+        let user_move = user(&history);
+        let enemy_move = strategy.next_move(&history);
+        history.push(PlayerMoves {
+            user_move,
+            enemy_move,
+        });
+    }
+
+    history[rounds / 2..]
+        .iter()
+        .filter(|round| round.check_who_wins_round() == Winner::Enemy)
+        .count()
+}
+
+#[test]
+fn test_ensemble_beats_constant_user() {
+    let wins = enemy_wins_against(|_| MoveType::Scissors, 40);
+
+    assert!(wins >= 18);
+}
+
+#[test]
+fn test_ensemble_beats_cycling_user() {
+    let cycle = [MoveType::Rock, MoveType::Rock, MoveType::Paper, MoveType::Scissors];
+    let wins = enemy_wins_against(|history| cycle[history.len() % cycle.len()], 80);
+
+    assert!(wins >= 35);
+}
+
+#[test]
+fn test_ensemble_beats_user_countering_enemy() {
+    let wins = enemy_wins_against(
+        |history| match history.last() {
+            Some(last) => RuleSet::classic().counters(&last.enemy_move)[0],
+            None => MoveType::Rock,
+        },
+        80,
+    );
+
+    assert!(wins >= 35);
+}
+
+#[test]
+fn test_ensemble_plays_randomly_without_data() {
+    let mut strategy = EnsembleStrategy::with_rules(RuleSet::lizard_spock());
+
+    assert_eq!(strategy.favorite(&[]), None);
+    assert!(RuleSet::lizard_spock().contains(&strategy.next_move(&[])));
+}
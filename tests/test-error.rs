@@ -0,0 +1,56 @@
+use rock_paper_scissors::*;
+use std::error::Error as _;
+use std::io;
+
+#[test]
+fn test_error_display() {
+    assert_eq!(Error::MatchAlreadyOver.to_string(), "rock-paper-scissors: err: The match is already over");
+    assert_eq!(
+        Error::InvalidSetting(String::from("Invalid input. Please enter a number.")).to_string(),
+        "rock-paper-scissors: err: Invalid input. Please enter a number."
+    );
+    assert_eq!(
+        Error::from(io::Error::other("disconnected")).to_string(),
+        "rock-paper-scissors: err: Failed to read input: disconnected"
+    );
+}
+
+#[test]
+fn test_error_source_and_kind() {
+    assert!(Error::from(io::Error::other("disconnected")).source().is_some());
+    assert!(Error::Eof.source().is_none());
+
+    assert!(Error::InvalidMove(String::new()).is_invalid());
+    assert!(Error::InvalidSetting(String::new()).is_invalid());
+    assert!(!Error::InvalidRules(String::new()).is_invalid());
+    assert!(!Error::MatchAlreadyOver.is_invalid());
+    assert!(!Error::Eof.is_invalid());
+}
+
+#[test]
+fn test_parse_errors_convert_to_invalid_move() {
+    let error = Error::from(MoveParser::new().parse("rokc").unwrap_err());
+
+    assert!(matches!(error, Error::InvalidMove(_)));
+    assert_eq!(error.to_string(), "rock-paper-scissors: err: 'rokc' is not a move, did you mean 'rock'?");
+}
+
+#[test]
+fn test_game_errors() {
+    let mut game = Game::new(GameSettings::from_first_to(1)).with_opponent(ConstantStrategy::new(MoveType::Rock));
+
+    let error = game.play_round(MoveType::Lizard).unwrap_err();
+    assert_eq!(error.to_string(), "rock-paper-scissors: err: Lizard is not part of the rules");
+
+    game.play_round(MoveType::Paper).unwrap();
+    assert!(matches!(game.play_round(MoveType::Paper), Err(Error::MatchAlreadyOver)));
+}
+
+#[test]
+fn test_constructor_errors() {
+    assert!(matches!(RuleSet::balanced(4), Err(Error::InvalidRules(_))));
+    assert!(matches!(PayoffMatrix::new(Vec::new(), Vec::new()), Err(Error::InvalidRules(_))));
+
+    let error = MixedStrategy::import("Stone 1.0", &RuleSet::classic()).unwrap_err();
+    assert_eq!(error.to_string(), "rock-paper-scissors: err: Unknown move 'Stone' in strategy");
+}
@@ -0,0 +1,90 @@
+use rock_paper_scissors::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn test_events_of_a_won_round() {
+    let mut game = Game::new(GameSettings::from_first_to(2)).with_opponent(ConstantStrategy::new(MoveType::Scissors));
+    let events = game.subscribe();
+
+    game.play_round(MoveType::Rock).unwrap();
+
+    let moves = PlayerMoves {
+        user_move: MoveType::Rock,
+        enemy_move: MoveType::Scissors,
+    };
+
+    assert_eq!(events.try_iter().collect::<Vec<_>>(), vec![
+        GameEvent::RoundStarted { round: 1, scores: Scores::new() },
+        GameEvent::MovesRevealed { round: 1, moves },
+        GameEvent::RoundWon {
+            round: 1,
+            moves,
+            winner: Winner::User,
+            winner_id: Some(PlayerId(0)),
+        },
+        GameEvent::ScoreChanged {
+            round: 1,
+            previous: Scores::new(),
+            scores: Scores { user_wins: 1, enemy_wins: 0, ties: 0 },
+        },
+    ]);
+
+    game.play_round(MoveType::Rock).unwrap();
+
+    assert_eq!(
+        events.try_iter().last(),
+        Some(GameEvent::MatchWon {
+            round: 2,
+            winner: Winner::User,
+            winner_id: Some(PlayerId(0)),
+            scores: Scores { user_wins: 2, enemy_wins: 0, ties: 0 },
+        })
+    );
+}
+
+#[test]
+fn test_events_of_a_tied_round() {
+    let mut game = Game::new(GameSettings::from_first_to(1)).with_opponent(ConstantStrategy::new(MoveType::Paper));
+    let events = game.subscribe();
+
+    game.play_round(MoveType::Paper).unwrap();
+
+    let events: Vec<GameEvent> = events.try_iter().collect();
+    assert_eq!(events.len(), 4);
+    assert!(matches!(events[2], GameEvent::RoundTied { round: 1, .. }));
+    assert!(matches!(events[3], GameEvent::ScoreChanged { scores: Scores { ties: 1, .. }, .. }));
+}
+
+#[test]
+fn test_listeners_are_called_in_order() {
+    let mut game = Game::new(GameSettings::from_first_to(1)).with_opponent(ConstantStrategy::new(MoveType::Rock));
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let first = Rc::clone(&log);
+    game.add_listener(move |event| {
+        if let GameEvent::MatchWon { winner, .. } = event {
+            first.borrow_mut().push(format!("first: {}", winner.convert_to_string()));
+        }
+    });
+
+    let second = Rc::clone(&log);
+    game.add_listener(move |event| {
+        if let GameEvent::MatchWon { .. } = event {
+            second.borrow_mut().push(String::from("second"));
+        }
+    });
+
+    game.play_round(MoveType::Paper).unwrap();
+    assert!(game.play_round(MoveType::Paper).is_err());
+
+    assert_eq!(*log.borrow(), vec![String::from("first: User"), String::from("second")]);
+}
+
+#[test]
+fn test_dropped_receiver_does_not_stop_the_game() {
+    let mut game = Game::new(GameSettings::from_first_to(1)).with_opponent(ConstantStrategy::new(MoveType::Rock));
+    drop(game.subscribe());
+
+    assert!(game.play_round(MoveType::Paper).is_ok());
+}
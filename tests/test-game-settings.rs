@@ -5,7 +5,8 @@ fn test_game_settings_new() {
     let game_settings = GameSettings::new();
 
     assert_eq!(game_settings, GameSettings {
-        first_to: 1
+        format: MatchFormat::FirstTo(1),
+        tie_policy: TiePolicy::AwardNobody,
     });
 }
 
@@ -14,6 +15,7 @@ fn test_game_settings_from_first_to() {
     let game_settings = GameSettings::from_first_to(3);
 
     assert_eq!(game_settings, GameSettings {
-        first_to: 3
+        format: MatchFormat::FirstTo(3),
+        tie_policy: TiePolicy::AwardNobody,
     });
-}
\ No newline at end of file
+}
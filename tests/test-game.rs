@@ -0,0 +1,144 @@
+use rock_paper_scissors::*;
+
+#[test]
+fn test_game_new() {
+    let game = Game::with_seed(GameSettings::from_first_to(3), 5);
+
+    assert_eq!(game.scores(), &Scores::new());
+    assert!(game.history().is_empty());
+    assert_eq!(game.settings(), &GameSettings::from_first_to(3));
+    assert_eq!(game.rules(), &RuleSet::classic());
+    assert_eq!(game.seed(), 5);
+    assert!(!game.is_over());
+}
+
+#[test]
+fn test_play_round_updates_scores_and_history() {
+    let mut game = Game::new(GameSettings::from_first_to(3))
+        .with_opponent(CycleStrategy::new(vec![MoveType::Scissors, MoveType::Rock, MoveType::Paper]));
+
+    let outcome = game.play_round(MoveType::Rock).unwrap();
+    assert_eq!(outcome.round, 1);
+    assert_eq!(outcome.winner, Winner::User);
+    assert_eq!(outcome.scores, Scores { user_wins: 1, enemy_wins: 0, ties: 0 });
+
+    let outcome = game.play_round(MoveType::Rock).unwrap();
+    assert_eq!(outcome.round, 2);
+    assert_eq!(outcome.winner, Winner::Tie);
+
+    let outcome = game.play_round(MoveType::Rock).unwrap();
+    assert_eq!(outcome.winner, Winner::Enemy);
+    assert_eq!(outcome.scores, Scores { user_wins: 1, enemy_wins: 1, ties: 1 });
+
+    assert_eq!(game.history().len(), 3);
+    assert_eq!(game.history()[1], Round {
+        moves: PlayerMoves {
+            user_move: MoveType::Rock,
+            enemy_move: MoveType::Rock,
+        },
+        winner: Winner::Tie,
+        winner_id: None,
+    });
+}
+
+#[test]
+fn test_game_reports_match_over() {
+    let mut game = Game::new(GameSettings::from_first_to(2)).with_opponent(ConstantStrategy::new(MoveType::Paper));
+
+    assert_eq!(game.play_round(MoveType::Rock).unwrap().match_winner, None);
+    assert_eq!(game.play_round(MoveType::Rock).unwrap().match_winner, Some(Winner::Enemy));
+    assert_eq!(game.winner(), Some(Winner::Enemy));
+    assert!(game.play_round(MoveType::Rock).is_err());
+    assert_eq!(game.history().len(), 2);
+}
+
+#[test]
+fn test_game_rejects_moves_outside_the_rules() {
+    let mut game = Game::new(GameSettings::from_first_to(1));
+
+    assert!(game.play_round(MoveType::Spock).is_err());
+    assert!(game.play_round(MoveType::None).is_err());
+    assert!(game.history().is_empty());
+
+    let mut game = Game::new(GameSettings::from_first_to(1)).with_rules(RuleSet::lizard_spock());
+    assert!(game.play_round(MoveType::Spock).is_ok());
+}
+
+#[test]
+fn test_with_rules_keeps_a_custom_opponent() {
+    let mut game = Game::new(GameSettings::from_first_to(1))
+        .with_opponent(ConstantStrategy::new(MoveType::Spock))
+        .with_rules(RuleSet::lizard_spock());
+
+    assert_eq!(game.play_round(MoveType::Rock).unwrap().moves.enemy_move, MoveType::Spock);
+}
+
+#[test]
+fn test_seeded_games_are_reproducible() {
+    let user_moves = [MoveType::Rock, MoveType::Scissors, MoveType::Scissors, MoveType::Paper];
+
+    let play = || {
+        let mut game = Game::with_seed(GameSettings::from_first_to(5), 1234).with_opponent(MarkovStrategy::new(1));
+        let mut outcomes = Vec::new();
+
+        for user_move in user_moves.iter().cycle() {
+            if game.is_over() {
+                break;
+            }
+
+            outcomes.push(game.play_round(*user_move).unwrap());
+        }
+
+        outcomes
+    };
+
+    assert_eq!(play(), play());
+}
+
+#[test]
+fn test_play_moves_bypasses_the_opponent() {
+    let mut game = Game::new(GameSettings::from_first_to(2)).with_opponent(ConstantStrategy::new(MoveType::Paper));
+    let moves = PlayerMoves {
+        user_move: MoveType::Paper,
+        enemy_move: MoveType::Rock,
+    };
+
+    assert_eq!(game.play_moves(moves).unwrap().winner, Winner::User);
+    assert_eq!(game.moves(), vec![moves]);
+    assert!(game
+        .play_moves(PlayerMoves {
+            user_move: MoveType::Rock,
+            enemy_move: MoveType::Spock,
+        })
+        .is_err());
+    assert_eq!(game.history().len(), 1);
+}
+
+#[test]
+fn test_game_state_resumes_the_match() {
+    let mut game = Game::with_seed(GameSettings::from_first_to(4), 77)
+        .with_players(Player::human(PlayerId(2), "Ada"), Player::bot(PlayerId(9), "Marvin"))
+        .unwrap();
+
+    game.play_round(MoveType::Rock).unwrap();
+    game.play_round(MoveType::Scissors).unwrap();
+
+    let state = game.state();
+    assert_eq!(state.seed, 77);
+    assert_eq!(state.history.len(), 2);
+
+    let mut resumed = Game::from_state(state.clone()).unwrap();
+    assert_eq!(resumed.players(), game.players());
+
+    while !game.is_over() {
+        assert_eq!(resumed.play_round(MoveType::Paper), game.play_round(MoveType::Paper));
+    }
+
+    let mut invalid = state.clone();
+    invalid.history[0].moves.user_move = MoveType::Spock;
+    assert!(matches!(Game::from_state(invalid), Err(Error::InvalidSave(_))));
+
+    let mut invalid = state;
+    invalid.scores.user_wins = 9;
+    assert!(matches!(Game::from_state(invalid), Err(Error::InvalidScores(_))));
+}
@@ -0,0 +1,109 @@
+use rock_paper_scissors::*;
+
+#[test]
+fn test_losing_gestures() {
+    let rules = RuleSet::classic();
+
+    assert_eq!(rules.losing_gestures(&[MoveType::Paper, MoveType::Rock]), vec![MoveType::Rock]);
+    assert_eq!(
+        rules.losing_gestures(&[MoveType::Scissors, MoveType::Scissors, MoveType::Paper]),
+        vec![MoveType::Paper]
+    );
+    assert!(rules.losing_gestures(&[MoveType::Rock; 5]).is_empty());
+    assert!(rules.losing_gestures(&[MoveType::Scissors, MoveType::Rock, MoveType::Paper]).is_empty());
+    assert!(rules.losing_gestures(&[]).is_empty());
+}
+
+#[test]
+fn test_losing_gestures_lizard_spock() {
+    let rules = RuleSet::lizard_spock();
+
+    // Spock beats Scissors and Rock, and nothing thrown beats Spock.
+    assert_eq!(
+        rules.losing_gestures(&[MoveType::Spock, MoveType::Scissors, MoveType::Rock]),
+        vec![MoveType::Rock, MoveType::Scissors]
+    );
+
+    // Every thrown gesture is beaten by another one.
+    assert!(rules
+        .losing_gestures(&[MoveType::Rock, MoveType::Paper, MoveType::Scissors, MoveType::Lizard])
+        .is_empty());
+}
+
+#[test]
+fn test_group_game_new() {
+    let game = GroupGame::new(5).unwrap();
+
+    assert_eq!(game.players().len(), 5);
+    assert_eq!(game.players()[4], Player::human(PlayerId(4), "Player 5"));
+    assert_eq!(game.remaining(), &[PlayerId(0), PlayerId(1), PlayerId(2), PlayerId(3), PlayerId(4)]);
+    assert!(game.history().is_empty());
+    assert!(!game.is_over());
+
+    assert!(matches!(GroupGame::new(1), Err(Error::InvalidSetting(_))));
+}
+
+#[test]
+fn test_group_game_eliminates_until_one_remains() {
+    let mut game = GroupGame::new(3).unwrap();
+
+    let outcome = game.play_round(&[MoveType::Rock, MoveType::Rock, MoveType::Rock]).unwrap();
+    assert_eq!(outcome.round.round, 1);
+    assert!(outcome.round.eliminated.is_empty());
+    assert_eq!(outcome.match_winner, None);
+
+    let outcome = game.play_round(&[MoveType::Rock, MoveType::Scissors, MoveType::Rock]).unwrap();
+    assert_eq!(outcome.round, GroupRound {
+        round: 2,
+        moves: vec![
+            (PlayerId(0), MoveType::Rock),
+            (PlayerId(1), MoveType::Scissors),
+            (PlayerId(2), MoveType::Rock),
+        ],
+        eliminated: vec![PlayerId(1)],
+    });
+    assert_eq!(outcome.remaining, vec![PlayerId(0), PlayerId(2)]);
+
+    let outcome = game.play_round(&[MoveType::Paper, MoveType::Scissors]).unwrap();
+    assert_eq!(outcome.round.eliminated, vec![PlayerId(0)]);
+    assert_eq!(outcome.match_winner, Some(PlayerId(2)));
+    assert_eq!(game.winner(), Some(PlayerId(2)));
+    assert_eq!(game.history().len(), 3);
+}
+
+#[test]
+fn test_group_game_errors() {
+    let mut game = GroupGame::new(3).unwrap();
+
+    assert!(matches!(game.play_round(&[MoveType::Rock, MoveType::Rock]), Err(Error::InvalidMove(_))));
+    assert!(matches!(
+        game.play_round(&[MoveType::Rock, MoveType::Rock, MoveType::Spock]),
+        Err(Error::InvalidMove(_))
+    ));
+    assert!(game.history().is_empty());
+
+    game.play_round(&[MoveType::Paper, MoveType::Rock, MoveType::Rock]).unwrap();
+    assert!(matches!(game.play_round(&[MoveType::Rock]), Err(Error::MatchAlreadyOver)));
+
+    let mut game = GroupGame::new(3).unwrap().with_rules(RuleSet::lizard_spock());
+    assert!(game.play_round(&[MoveType::Rock, MoveType::Rock, MoveType::Spock]).is_ok());
+}
+
+#[test]
+fn test_group_game_with_players() {
+    let players = vec![
+        Player::human(PlayerId(10), "Ada"),
+        Player::bot(PlayerId(20), "Marvin"),
+        Player::remote(PlayerId(30), "Grace"),
+    ];
+    let mut game = GroupGame::with_players(players).unwrap();
+
+    let outcome = game.play_round(&[MoveType::Rock, MoveType::Scissors, MoveType::Scissors]).unwrap();
+    assert_eq!(outcome.round.eliminated, vec![PlayerId(20), PlayerId(30)]);
+    assert_eq!(game.winning_player().unwrap().name, "Ada");
+    assert_eq!(game.player(PlayerId(30)).unwrap().kind, PlayerKind::Remote);
+    assert_eq!(game.player(PlayerId(40)), None);
+
+    let duplicates = vec![Player::human(PlayerId(1), "Ada"), Player::human(PlayerId(1), "Grace")];
+    assert!(matches!(GroupGame::with_players(duplicates), Err(Error::InvalidSetting(_))));
+}
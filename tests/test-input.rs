@@ -0,0 +1,94 @@
+use rock_paper_scissors::*;
+use std::io::{self, BufRead, Read};
+
+struct FailingInput;
+
+impl Read for FailingInput {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("disconnected"))
+    }
+}
+
+impl BufRead for FailingInput {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Err(io::Error::other("disconnected"))
+    }
+
+    fn consume(&mut self, _amt: usize) {}
+}
+
+#[test]
+fn test_move_type_read_from() {
+    let mut input = "1\n 2 \n3\n".as_bytes();
+    let mut output = Vec::new();
+
+    assert_eq!(MoveType::read_from(&mut input, &mut output).unwrap(), MoveType::Rock);
+    assert_eq!(MoveType::read_from(&mut input, &mut output).unwrap(), MoveType::Paper);
+    assert_eq!(MoveType::read_from(&mut input, &mut output).unwrap(), MoveType::Scissors);
+    assert!(matches!(MoveType::read_from(&mut input, &mut output), Err(Error::Eof)));
+    assert_eq!(String::from_utf8(output).unwrap().lines().count(), 4);
+}
+
+#[test]
+fn test_move_type_read_from_errors() {
+    let mut output = Vec::new();
+
+    let error = MoveType::read_from(&mut "4\n".as_bytes(), &mut output).unwrap_err();
+    assert!(error.is_invalid());
+    assert_eq!(error.to_string(), "rock-paper-scissors: err: '4' is not a move");
+
+    let error = MoveType::read_from(&mut FailingInput, &mut output).unwrap_err();
+    assert!(matches!(error, Error::Io(_)));
+    assert!(!error.is_invalid());
+}
+
+#[test]
+fn test_player_moves_read_from_against_asks_again() {
+    let mut input = "x\n0\n2\n".as_bytes();
+    let mut output = Vec::new();
+    let mut strategy = ConstantStrategy::new(MoveType::Rock);
+
+    let player_moves = PlayerMoves::read_from_against(&mut input, &mut output, &mut strategy, &[]).unwrap();
+    assert_eq!(player_moves, PlayerMoves {
+        user_move: MoveType::Paper,
+        enemy_move: MoveType::Rock,
+    });
+
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(output.matches("is not a move").count(), 2);
+}
+
+#[test]
+fn test_player_moves_read_from_against_stops_at_eof() {
+    let mut input = "banana\n".as_bytes();
+    let mut output = Vec::new();
+    let mut strategy = RandomStrategy::new();
+
+    let error = PlayerMoves::read_from_against(&mut input, &mut output, &mut strategy, &[]).unwrap_err();
+    assert!(matches!(error, Error::Eof));
+}
+
+#[test]
+fn test_game_settings_read_from() {
+    assert_eq!(GameSettings::read_from(&mut "7\n".as_bytes()).unwrap(), GameSettings::from_first_to(7));
+    assert!(GameSettings::read_from(&mut "-1\n".as_bytes()).unwrap_err().is_invalid());
+    assert!(matches!(GameSettings::read_from(&mut "".as_bytes()), Err(Error::Eof)));
+    assert!(matches!(GameSettings::read_from(&mut FailingInput), Err(Error::Io(_))));
+}
+
+#[test]
+fn test_player_moves_read_from_against_with_seeded_rng() {
+    let input = "rock\n".repeat(20);
+    let mut input = input.as_bytes();
+    let mut output = Vec::new();
+    let mut strategy = RandomStrategy::new();
+
+    let mut play = |seed| {
+        let mut rng = seeded_rng(seed);
+        (0..10)
+            .map(|_| PlayerMoves::read_from_against_with(&mut input, &mut output, &mut strategy, &[], &mut rng).unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(play(5), play(5));
+}
@@ -0,0 +1,333 @@
+mod common;
+
+use common::{connect, local_server, play_round, start_match, start_server};
+use rock_paper_scissors::*;
+use std::net::TcpStream;
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn lobby() -> Lobby {
+    Lobby::new()
+        .with_queue("first-to-2", GameSettings::from_first_to(2), RuleSet::classic())
+        .with_queue("best-of-5", GameSettings::from_format(MatchFormat::BestOf(5)), RuleSet::classic())
+        .with_queue("rpsls", GameSettings::from_first_to(1), RuleSet::lizard_spock())
+}
+
+#[test]
+fn test_queues_pair_players_of_the_same_format() {
+    let lobby = lobby();
+    let (address, server) = start_server(local_server(GameSettings::new()).with_lobby(lobby.clone()), 1);
+
+    let _classic = connect(address, "Classic", "best-of-5");
+
+    let mut spock = connect(address, "Spock", "rpsls");
+
+    let waiting: Vec<_> = lobby.status().queues.into_iter().map(|queue| queue.waiting.map(|player| player.name)).collect();
+    assert_eq!(waiting, vec![None, Some(String::from("Classic")), Some(String::from("Spock"))]);
+
+    let mut kirk = Client::connect(address, "Kirk").unwrap();
+    kirk.join_queue("rpsls").unwrap();
+
+    for client in [&mut spock, &mut kirk] {
+        assert!(matches!(client.receive().unwrap(), Message::Match { .. }));
+        assert_eq!(client.receive().unwrap(), Message::Settings(GameSettings::from_first_to(1)));
+        assert_eq!(client.receive().unwrap(), Message::Rules(RuleSet::lizard_spock().gestures));
+    }
+
+    let kirk = thread::spawn(move || kirk.play(&mut ConstantStrategy::new(MoveType::Rock)));
+    assert_eq!(spock.play(&mut ConstantStrategy::new(MoveType::Spock)).unwrap(), Winner::User);
+    assert_eq!(kirk.join().unwrap().unwrap(), Winner::Enemy);
+
+    drop(spock);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].replay.rules, RuleSet::lizard_spock());
+    assert_eq!(lobby.status().queues[1].waiting.as_ref().unwrap().name, "Classic");
+}
+
+#[test]
+fn test_unknown_requests_can_be_retried() {
+    let (address, server) = start_server(local_server(GameSettings::new()).with_lobby(lobby()), 1);
+
+    let mut client = Client::connect(address, "Lost").unwrap();
+    client.join_queue("best-of-7").unwrap();
+    assert_eq!(client.receive().unwrap(), Message::Error(String::from("There is no queue named 'best-of-7'")));
+
+    client.join_room("ZZZZZZ").unwrap();
+    assert_eq!(client.receive().unwrap(), Message::Error(String::from("There is no room with the code 'ZZZZZZ'")));
+
+    assert_eq!(client.watch(9), Err(Error::Protocol(String::from("There is no live match 9"))));
+    assert_eq!(client.create_room("best-of-7"), Err(Error::Protocol(String::from("There is no queue named 'best-of-7'"))));
+
+    client.send(&Message::Round(1)).unwrap();
+    assert!(matches!(client.receive().unwrap(), Message::Error(_)));
+
+    client.join_queue("first-to-2").unwrap();
+    assert_eq!(client.receive().unwrap(), Message::Waiting);
+
+    let mut other = Client::connect(address, "Found").unwrap();
+    other.join_queue("first-to-2").unwrap();
+    assert!(matches!(other.receive().unwrap(), Message::Match { .. }));
+
+    other.send(&Message::Quit).unwrap();
+    assert_eq!(client.play(&mut ConstantStrategy::new(MoveType::Rock)).unwrap(), Winner::User);
+
+    drop([client, other]);
+    server.join().unwrap().unwrap();
+}
+
+#[test]
+fn test_private_rooms() {
+    let lobby = lobby();
+    let (address, server) = start_server(local_server(GameSettings::new()).with_lobby(lobby.clone()), 1);
+
+    let mut host = Client::connect(address, "Host").unwrap();
+    let code = host.create_room("best-of-5").unwrap();
+    assert_eq!(code.len(), 6);
+    assert_eq!(host.receive().unwrap(), Message::Waiting);
+
+    let rooms = lobby.status().rooms;
+    assert_eq!(rooms.len(), 1);
+    assert_eq!(rooms[0].code, code);
+    assert_eq!(rooms[0].queue, "best-of-5");
+    assert_eq!(rooms[0].host.name, "Host");
+
+    // Joining the queue the room was made from does not reach the host.
+    let stranger = connect(address, "Stranger", "best-of-5");
+
+    let mut guest = Client::connect(address, "Guest").unwrap();
+    guest.join_room(&code.to_lowercase()).unwrap();
+
+    assert_eq!(host.receive().unwrap(), Message::Match {
+        opponent: guest.player,
+        name: String::from("Guest"),
+    });
+    assert_eq!(guest.receive().unwrap(), Message::Match {
+        opponent: host.player,
+        name: String::from("Host"),
+    });
+    assert_eq!(host.receive().unwrap(), Message::Settings(GameSettings::from_format(MatchFormat::BestOf(5))));
+    assert!(lobby.status().rooms.is_empty());
+
+    let guest = thread::spawn(move || guest.play(&mut ConstantStrategy::new(MoveType::Scissors)));
+    assert_eq!(host.play(&mut ConstantStrategy::new(MoveType::Rock)).unwrap(), Winner::User);
+    assert_eq!(guest.join().unwrap().unwrap(), Winner::Enemy);
+
+    drop([host, stranger]);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].replay.scores.user_wins, 3);
+}
+
+#[test]
+fn test_spectators_follow_live_matches() {
+    let lobby = lobby();
+    let (address, server) = start_server(local_server(GameSettings::new()).with_lobby(lobby.clone()), 1);
+
+    let (mut user, mut enemy) = start_match(address, "first-to-2");
+    play_round(&mut user, &mut enemy, MoveType::Paper, MoveType::Rock);
+
+    let mut spectator = Client::connect(address, "Spectator").unwrap();
+    assert_eq!(spectator.list_matches().unwrap(), vec![0]);
+    assert_eq!(spectator.watch(0).unwrap(), [user.player, enemy.player]);
+    assert_eq!(spectator.receive().unwrap(), Message::Settings(GameSettings::from_first_to(2)));
+    assert_eq!(spectator.receive().unwrap(), Message::Rules(RuleSet::classic().gestures));
+
+    // The rounds played before the spectator arrived come first.
+    assert_eq!(spectator.receive().unwrap(), Message::Result {
+        round: 1,
+        moves: PlayerMoves {
+            user_move: MoveType::Paper,
+            enemy_move: MoveType::Rock,
+        },
+        winner: Winner::User,
+        scores: Scores {
+            user_wins: 1,
+            enemy_wins: 0,
+            ties: 0,
+        },
+    });
+
+    let matches = lobby.status().matches;
+    assert_eq!(matches[0].rounds, 1);
+    assert_eq!(matches[0].spectators, 1);
+    assert_eq!(
+        lobby.admin("matches").unwrap(),
+        "0 (first-to-2): User (#0) 1-0 Enemy (#1) after 1 rounds, 1 watching"
+    );
+
+    assert_eq!(user.receive().unwrap(), Message::Round(2));
+    assert_eq!(enemy.receive().unwrap(), Message::Round(2));
+    play_round(&mut user, &mut enemy, MoveType::Rock, MoveType::Paper);
+
+    assert_eq!(spectator.receive().unwrap(), Message::Result {
+        round: 2,
+        moves: PlayerMoves {
+            user_move: MoveType::Rock,
+            enemy_move: MoveType::Paper,
+        },
+        winner: Winner::Enemy,
+        scores: Scores {
+            user_wins: 1,
+            enemy_wins: 1,
+            ties: 0,
+        },
+    });
+
+    // The enemy leaves, so the spectator sees the user win by forfeit.
+    assert_eq!(user.receive().unwrap(), Message::Round(3));
+    assert_eq!(enemy.receive().unwrap(), Message::Round(3));
+    user.send(&Message::Move(MoveType::Rock)).unwrap();
+    enemy.send(&Message::Quit).unwrap();
+
+    assert_eq!(spectator.receive().unwrap(), Message::Over {
+        winner: Winner::User,
+        forfeit: true,
+    });
+    assert!(matches!(spectator.receive(), Err(Error::Eof)));
+
+    assert_eq!(user.receive().unwrap(), Message::Over {
+        winner: Winner::User,
+        forfeit: true,
+    });
+
+    drop([user, enemy]);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].forfeited_by, Some(PlayerId(1)));
+    assert!(lobby.status().matches.is_empty());
+}
+
+#[test]
+fn test_servers_sharing_a_lobby_give_out_distinct_ids() {
+    let lobby = lobby();
+    let mut other = local_server(GameSettings::new()).with_lobby(lobby.clone());
+    let other_address = other.local_addr().unwrap();
+    // The match is paired, and played, by the server the second player connects to.
+    thread::spawn(move || other.run());
+    let (address, server) = start_server(local_server(GameSettings::new()).with_lobby(lobby), 1);
+
+    let mut first = connect(other_address, "First", "first-to-2");
+    let mut second = Client::connect(address, "Second").unwrap();
+    assert_ne!(first.player, second.player);
+    second.join_queue("first-to-2").unwrap();
+
+    let second = thread::spawn(move || second.play(&mut ConstantStrategy::new(MoveType::Rock)));
+    assert_eq!(first.play(&mut ConstantStrategy::new(MoveType::Paper)).unwrap(), Winner::User);
+    assert_eq!(second.join().unwrap().unwrap(), Winner::Enemy);
+
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].replay.players[0].name, "First");
+}
+
+#[test]
+fn test_admin_commands() {
+    let lobby = lobby();
+
+    assert_eq!(
+        lobby.admin("queues").unwrap(),
+        "first-to-2: first to 2, nobody waiting\n\
+         best-of-5: best of 5, nobody waiting\n\
+         rpsls: first to 1 with 5 gestures, nobody waiting"
+    );
+    assert_eq!(lobby.admin("rooms").unwrap(), "none");
+    assert_eq!(lobby.admin(" matches ").unwrap(), "none");
+    assert!(lobby.admin("status").unwrap().starts_with("queues: 3\n  first-to-2: first to 2, nobody waiting\n"));
+    assert!(lobby.admin("status").unwrap().ends_with("rooms: 0\nmatches: 0"));
+    assert!(matches!(lobby.admin("shutdown"), Err(Error::InvalidSetting(_))));
+
+    let (address, server) = start_server(local_server(GameSettings::new()).with_lobby(lobby.clone()), 1);
+    let mut host = Client::connect(address, "Ada").unwrap();
+    let code = host.create_room("rpsls").unwrap();
+    assert_eq!(host.receive().unwrap(), Message::Waiting);
+
+    assert_eq!(lobby.admin("rooms").unwrap(), format!("{code} (rpsls): Ada (#0) waiting"));
+
+    let mut guest = Client::connect(address, "Grace").unwrap();
+    guest.join_room(&code).unwrap();
+    host.send(&Message::Quit).unwrap();
+    assert_eq!(guest.play(&mut ConstantStrategy::new(MoveType::Rock)).unwrap(), Winner::User);
+
+    drop([host, guest]);
+    server.join().unwrap().unwrap();
+}
+
+#[test]
+fn test_lobby_needs_version_2() {
+    let (address, server) = start_server(local_server(GameSettings::new()).with_lobby(lobby()), 1);
+
+    // A version 1 client joins the first queue right away.
+    let mut old = Client::connect_with_versions(address, "Old", &[1]).unwrap();
+    assert_eq!(old.version, 1);
+    assert_eq!(old.receive().unwrap(), Message::Waiting);
+    assert_eq!(old.create_room("rpsls"), Err(Error::Protocol(String::from("The lobby needs protocol version 2"))));
+
+    let mut new = Client::connect(address, "New").unwrap();
+    new.join_queue("first-to-2").unwrap();
+
+    let new = thread::spawn(move || new.play(&mut ConstantStrategy::new(MoveType::Paper)));
+    assert_eq!(old.play(&mut ConstantStrategy::new(MoveType::Rock)).unwrap(), Winner::Enemy);
+    assert_eq!(new.join().unwrap().unwrap(), Winner::User);
+
+    drop(old);
+    server.join().unwrap().unwrap();
+}
+
+#[test]
+fn test_disconnected_players_give_up_their_place() {
+    let (address, server) = start_server(local_server(GameSettings::new()).with_lobby(lobby()), 1);
+
+    let gone = connect(address, "Gone", "first-to-2");
+    drop(gone);
+
+    let mut first = connect(address, "First", "first-to-2");
+
+    let mut second = Client::connect(address, "Second").unwrap();
+    second.join_queue("first-to-2").unwrap();
+
+    let second = thread::spawn(move || second.play(&mut ConstantStrategy::new(MoveType::Paper)));
+    assert_eq!(first.play(&mut ConstantStrategy::new(MoveType::Scissors)).unwrap(), Winner::User);
+    assert_eq!(second.join().unwrap().unwrap(), Winner::Enemy);
+
+    drop(first);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].replay.players[0].name, "First");
+    assert_eq!(records[0].forfeited_by, None);
+}
+
+#[test]
+fn test_clients_who_never_read_do_not_hold_up_the_lobby() {
+    let lobby = lobby();
+    let (address, server) = start_server(local_server(GameSettings::new()).with_lobby(lobby.clone()), 1);
+
+    // Sends requests with long, unknown queue names until the server stops reading them, because the answers it
+    // is sending back are never read either.
+    let mut flood = TcpStream::connect(address).unwrap();
+    flood.set_write_timeout(Some(Duration::from_millis(500))).unwrap();
+    let hello = Message::Hello {
+        versions: PROTOCOL_VERSIONS.to_vec(),
+        name: String::from("Flood"),
+    };
+    hello.write_to(&mut flood).unwrap();
+
+    let request = Message::Queue("x".repeat(1 << 16));
+    while request.write_to(&mut flood).is_ok() {}
+
+    // Nothing below has to wait for the server to give up on writing to the flood.
+    let started = Instant::now();
+    let (mut user, mut enemy) = start_match(address, "first-to-2");
+
+    let mut spectator = Client::connect(address, "Spectator").unwrap();
+    assert_eq!(spectator.watch(0).unwrap(), [user.player, enemy.player]);
+    assert_eq!(lobby.status().matches[0].spectators, 1);
+
+    play_round(&mut user, &mut enemy, MoveType::Paper, MoveType::Rock);
+
+    let enemy = thread::spawn(move || enemy.play(&mut ConstantStrategy::new(MoveType::Rock)));
+    assert_eq!(user.play(&mut ConstantStrategy::new(MoveType::Paper)).unwrap(), Winner::User);
+    assert_eq!(enemy.join().unwrap().unwrap(), Winner::Enemy);
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    drop([user, spectator]);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].replay.scores.user_wins, 2);
+    assert!(lobby.status().matches.is_empty());
+    drop(flood);
+}
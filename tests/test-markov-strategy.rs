@@ -0,0 +1,106 @@
+use rock_paper_scissors::*;
+
+fn play(strategy: &mut MarkovStrategy, user_moves: &[MoveType]) -> Vec<PlayerMoves> {
+    let mut history = Vec::new();
+
+    for user_move in user_moves {
+        let enemy_move = strategy.next_move(&history);
+        history.push(PlayerMoves {
+            user_move: *user_move,
+            enemy_move,
+        });
+    }
+
+    history
+}
+
+#[test]
+fn test_markov_falls_back_to_random_without_data() {
+    let mut strategy = MarkovStrategy::new(2);
+
+    assert_eq!(strategy.predict(&[]), None);
+    assert!(RuleSet::classic().contains(&strategy.next_move(&[])));
+}
+
+#[test]
+fn test_markov_exploits_cycles() {
+    let mut strategy = MarkovStrategy::new(1);
+    let cycle = [MoveType::Rock, MoveType::Paper, MoveType::Scissors];
+    let user_moves: Vec<MoveType> = cycle.iter().cycle().take(60).copied().collect();
+
+    let history = play(&mut strategy, &user_moves);
+
+    let enemy_wins = history[10..]
+        .iter()
+        .filter(|round| round.check_who_wins_round() == Winner::Enemy)
+        .count();
+
+    assert_eq!(enemy_wins, history.len() - 10);
+}
+
+#[test]
+fn test_markov_decay_adapts_to_new_patterns() {
+    let mut strategy = MarkovStrategy::new(0);
+    strategy.decay = 0.5;
+
+    let mut user_moves = vec![MoveType::Rock; 20];
+    user_moves.extend(vec![MoveType::Scissors; 3]);
+    let history = play(&mut strategy, &user_moves);
+
+    assert_eq!(strategy.next_move(&history), MoveType::Rock);
+}
+
+#[test]
+fn test_markov_min_observations() {
+    let mut strategy = MarkovStrategy::new(0);
+    strategy.min_observations = 5.0;
+    strategy.decay = 1.0;
+
+    let history = vec![
+        PlayerMoves {
+            user_move: MoveType::Paper,
+            enemy_move: MoveType::Rock,
+        };
+        4
+    ];
+
+    assert_eq!(strategy.predict(&history), None);
+    assert!(strategy.predict(&[history.clone(), history].concat()).is_some());
+}
+
+#[test]
+fn test_markov_joint_context() {
+    let mut strategy = MarkovStrategy::new(1);
+    strategy.context = MarkovContext::JointMoves;
+
+    // The user plays whatever the enemy played last round.
+    let mut history: Vec<PlayerMoves> = Vec::new();
+
+    for _ in 0..40 {
+        let user_move = match history.last() {
+            Some(last) => last.enemy_move,
+            None => MoveType::Rock,
+        };
+        let enemy_move = strategy.next_move(&history);
+        history.push(PlayerMoves {
+            user_move,
+            enemy_move,
+        });
+    }
+
+    let enemy_wins = history[20..]
+        .iter()
+        .filter(|round| round.check_who_wins_round() == Winner::Enemy)
+        .count();
+
+    assert_eq!(enemy_wins, 20);
+}
+
+#[test]
+fn test_markov_resets_for_a_new_match() {
+    let mut strategy = MarkovStrategy::new(0);
+    let history = play(&mut strategy, &[MoveType::Rock; 5]);
+
+    assert!(strategy.predict(&history).is_some());
+    assert_eq!(strategy.predict(&history[..1]).unwrap()[&MoveType::Rock], 1.0);
+}
@@ -0,0 +1,126 @@
+use rock_paper_scissors::*;
+
+fn scores(user_wins: u8, enemy_wins: u8, ties: u8) -> Scores {
+    Scores {
+        user_wins,
+        enemy_wins,
+        ties,
+    }
+}
+
+#[test]
+fn test_first_to() {
+    let format = MatchFormat::FirstTo(3);
+
+    assert_eq!(format.winner(&scores(2, 2, 7)), None);
+    assert_eq!(format.winner(&scores(3, 2, 0)), Some(Winner::User));
+    assert_eq!(format.winner(&scores(0, 3, 0)), Some(Winner::Enemy));
+
+    // Overshooting the target still reports the leader, but is rejected by `check()`.
+    assert_eq!(format.winner(&scores(4, 1, 0)), Some(Winner::User));
+    assert!(matches!(format.check(&scores(4, 1, 0)), Err(Error::InvalidScores(_))));
+    assert!(matches!(format.check(&scores(3, 3, 0)), Err(Error::InvalidScores(_))));
+    assert_eq!(format.check(&scores(3, 2, 9)), Ok(Some(Winner::User)));
+}
+
+#[test]
+fn test_best_of() {
+    let format = MatchFormat::BestOf(5);
+
+    assert_eq!(format.winner(&scores(2, 2, 3)), None);
+    assert_eq!(format.winner(&scores(3, 1, 0)), Some(Winner::User));
+    assert_eq!(format.winner(&scores(2, 3, 0)), Some(Winner::Enemy));
+    assert!(format.check(&scores(4, 0, 0)).is_err());
+    assert!(MatchFormat::BestOf(4).validate().is_err());
+}
+
+#[test]
+fn test_win_by() {
+    let format = MatchFormat::WinBy { target: 11, margin: 2 };
+
+    assert_eq!(format.winner(&scores(11, 10, 0)), None);
+    assert_eq!(format.winner(&scores(13, 11, 0)), Some(Winner::User));
+    assert_eq!(format.winner(&scores(9, 11, 0)), Some(Winner::Enemy));
+    assert_eq!(format.check(&scores(14, 12, 0)), Ok(Some(Winner::User)));
+
+    // 12-9 was already decided at 11-9.
+    assert!(format.check(&scores(12, 9, 0)).is_err());
+}
+
+#[test]
+fn test_fixed_rounds() {
+    let format = MatchFormat::FixedRounds(5);
+
+    assert_eq!(format.winner(&scores(3, 0, 1)), None);
+    assert_eq!(format.winner(&scores(3, 0, 2)), Some(Winner::User));
+    assert_eq!(format.winner(&scores(1, 2, 2)), Some(Winner::Enemy));
+    assert_eq!(format.winner(&scores(2, 2, 1)), Some(Winner::Tie));
+    assert!(format.check(&scores(2, 2, 2)).is_err());
+}
+
+#[test]
+fn test_sudden_death() {
+    let format = MatchFormat::SuddenDeath { first_to: 3, max_rounds: 4 };
+
+    assert_eq!(format.winner(&scores(3, 0, 0)), Some(Winner::User));
+    assert_eq!(format.winner(&scores(1, 2, 1)), Some(Winner::Enemy));
+    assert_eq!(format.winner(&scores(1, 1, 2)), None);
+    assert_eq!(format.winner(&scores(1, 1, 5)), None);
+    assert_eq!(format.winner(&scores(2, 1, 5)), Some(Winner::User));
+    assert_eq!(format.check(&scores(2, 1, 5)), Ok(Some(Winner::User)));
+
+    // Level after the cap can only be reached if the scores were level when the cap was hit.
+    assert_eq!(format.check(&scores(2, 2, 3)), Ok(None));
+    assert!(MatchFormat::SuddenDeath { first_to: 3, max_rounds: 2 }.check(&scores(2, 2, 3)).is_err());
+}
+
+#[test]
+fn test_validate() {
+    assert!(MatchFormat::FirstTo(1).validate().is_ok());
+    assert!(MatchFormat::FirstTo(0).validate().is_err());
+    assert!(MatchFormat::FixedRounds(0).validate().is_err());
+    assert!(MatchFormat::WinBy { target: 3, margin: 0 }.validate().is_err());
+    assert!(MatchFormat::SuddenDeath { first_to: 3, max_rounds: 0 }.validate().is_err());
+    assert!(matches!(MatchFormat::BestOf(2).check(&Scores::new()), Err(Error::InvalidSetting(_))));
+}
+
+#[test]
+fn test_fixed_rounds_game_can_be_drawn() {
+    let mut game = Game::new(GameSettings::from_format(MatchFormat::FixedRounds(3)))
+        .with_opponent(CycleStrategy::new(vec![MoveType::Scissors, MoveType::Paper, MoveType::Paper]));
+
+    game.play_round(MoveType::Rock).unwrap();
+    game.play_round(MoveType::Rock).unwrap();
+    let outcome = game.play_round(MoveType::Paper).unwrap();
+
+    assert_eq!(outcome.match_winner, Some(Winner::Tie));
+    assert_eq!(game.scores(), &scores(1, 1, 1));
+    assert!(game.is_over());
+}
+
+#[test]
+fn test_win_by_is_settled_before_the_scores_overflow() {
+    let mut game = Game::new(GameSettings::from_format(MatchFormat::WinBy { target: 3, margin: 2 }))
+        .with_opponent(CycleStrategy::new(vec![MoveType::Scissors, MoveType::Paper]));
+
+    // The players trade wins, so the match would never be decided by a margin of 2.
+    for _ in 0..508 {
+        assert_eq!(game.play_round(MoveType::Rock).unwrap().match_winner, None);
+    }
+
+    assert_eq!(game.play_round(MoveType::Rock).unwrap().match_winner, Some(Winner::User));
+    assert_eq!(game.scores(), &scores(255, 254, 0));
+    assert!(game.is_over());
+    assert!(matches!(game.play_round(MoveType::Rock), Err(Error::MatchAlreadyOver)));
+}
+
+#[test]
+fn test_level_scores_are_drawn_at_the_cap() {
+    let format = MatchFormat::FirstTo(3);
+
+    assert_eq!(format.winner(&scores(1, 1, 254)), None);
+    assert_eq!(format.winner(&scores(1, 1, 255)), Some(Winner::Tie));
+    assert_eq!(format.winner(&scores(2, 1, 255)), Some(Winner::User));
+    assert_eq!(format.check(&scores(1, 1, 255)), Ok(Some(Winner::Tie)));
+    assert!(matches!(format.check(&scores(3, 1, 255)), Err(Error::InvalidScores(_))));
+}
@@ -0,0 +1,86 @@
+use rock_paper_scissors::*;
+
+#[test]
+fn test_parse_names_and_positions() {
+    let parser = MoveParser::new();
+
+    assert_eq!(parser.parse("1"), Ok(MoveType::Rock));
+    assert_eq!(parser.parse(" rock "), Ok(MoveType::Rock));
+    assert_eq!(parser.parse("PAPER"), Ok(MoveType::Paper));
+    assert_eq!(parser.parse("Scissors"), Ok(MoveType::Scissors));
+    assert!(parser.parse("0").is_err());
+    assert!(parser.parse("4").is_err());
+    assert!(parser.parse("").is_err());
+}
+
+#[test]
+fn test_parse_abbreviations() {
+    let parser = MoveParser::new();
+
+    assert_eq!(parser.parse("R"), Ok(MoveType::Rock));
+    assert_eq!(parser.parse("pa"), Ok(MoveType::Paper));
+    assert_eq!(parser.parse("scissor"), Ok(MoveType::Scissors));
+
+    let parser = MoveParser::with_rules(RuleSet::lizard_spock());
+    assert_eq!(parser.parse("s"), Ok(MoveType::Scissors));
+    assert_eq!(parser.parse("sp"), Ok(MoveType::Spock));
+    assert_eq!(parser.parse("L"), Ok(MoveType::Lizard));
+}
+
+#[test]
+fn test_parse_emoji() {
+    let parser = MoveParser::with_rules(RuleSet::lizard_spock());
+
+    assert_eq!(parser.parse("🪨"), Ok(MoveType::Rock));
+    assert_eq!(parser.parse("📄"), Ok(MoveType::Paper));
+    assert_eq!(parser.parse("✂️"), Ok(MoveType::Scissors));
+    assert_eq!(parser.parse("✂"), Ok(MoveType::Scissors));
+    assert_eq!(parser.parse("🦎"), Ok(MoveType::Lizard));
+    assert_eq!(parser.parse("🖖"), Ok(MoveType::Spock));
+
+    assert!(MoveParser::new().parse("🖖").is_err());
+}
+
+#[test]
+fn test_custom_aliases() {
+    let mut parser = MoveParser::new();
+    parser.add_alias("Stone", MoveType::Rock);
+    parser.add_alias("s", MoveType::Rock);
+
+    assert_eq!(parser.parse("stone"), Ok(MoveType::Rock));
+    assert_eq!(parser.parse("S"), Ok(MoveType::Rock));
+}
+
+#[test]
+fn test_typo_suggestions() {
+    let parser = MoveParser::new();
+
+    assert_eq!(parser.parse("sissors"), Err(ParseMoveError {
+        input: String::from("sissors"),
+        suggestion: Some(String::from("scissors")),
+    }));
+    assert_eq!(parser.parse("rokc").unwrap_err().suggestion, Some(String::from("rock")));
+    assert_eq!(parser.parse("banana").unwrap_err().suggestion, None);
+
+    let error = parser.parse("paer").unwrap_err();
+    assert_eq!(error.to_string(), "rock-paper-scissors: err: 'paer' is not a move, did you mean 'paper'?");
+}
+
+#[test]
+fn test_from_str_and_try_from() {
+    assert_eq!("spock".parse::<MoveType>(), Ok(MoveType::Spock));
+    assert_eq!(MoveType::try_from("✌️"), Ok(MoveType::Scissors));
+    assert!("gesture".parse::<MoveType>().is_err());
+}
+
+#[test]
+fn test_read_from_lists_the_rules() {
+    let parser = MoveParser::with_rules(RuleSet::lizard_spock());
+    let mut output = Vec::new();
+
+    assert_eq!(parser.read_from(&mut "lizard\n".as_bytes(), &mut output).unwrap(), MoveType::Lizard);
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "Enter your move: (1 = Rock, 2 = Paper, 3 = Scissors, 4 = Lizard, 5 = Spock)\n"
+    );
+}
@@ -23,4 +23,8 @@ fn convert_to_string_works() {
     let move_type = MoveType::Paper;
 
     assert_eq!(move_type.convert_to_string(), "Paper");
+
+    assert_eq!(MoveType::Lizard.convert_to_string(), "Lizard");
+
+    assert_eq!(MoveType::Spock.convert_to_string(), "Spock");
 }
\ No newline at end of file
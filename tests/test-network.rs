@@ -0,0 +1,444 @@
+mod common;
+
+use common::{connect, local_server, start_match, start_server};
+use rock_paper_scissors::*;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_message_round_trip() {
+    let messages = [
+        Message::Hello {
+            versions: vec![1, 2],
+            name: String::from("Ada Lovelace"),
+        },
+        Message::Welcome {
+            version: 1,
+            player: PlayerId(4),
+        },
+        Message::Queue(String::from("best-of-5")),
+        Message::Create(String::from("rpsls")),
+        Message::Room(String::from("K7QX2M")),
+        Message::Join(String::from("K7QX2M")),
+        Message::List,
+        Message::Matches(vec![0, 3]),
+        Message::Matches(Vec::new()),
+        Message::Watch(3),
+        Message::Watching {
+            id: 3,
+            user: PlayerId(6),
+            enemy: PlayerId(7),
+        },
+        Message::Resume(String::from("9f86d081884c7d659a2feaa0c55ad015")),
+        Message::Resumed {
+            player: PlayerId(0),
+            round: 4,
+            scores: Scores {
+                user_wins: 2,
+                enemy_wins: 1,
+                ties: 0,
+            },
+            commitment: None,
+        },
+        Message::Resumed {
+            player: PlayerId(1),
+            round: 2,
+            scores: Scores::new(),
+            commitment: Some(Commitment([0x5d; 32])),
+        },
+        Message::Session(String::from("9f86d081884c7d659a2feaa0c55ad015")),
+        Message::Waiting,
+        Message::Match {
+            opponent: PlayerId(5),
+            name: String::from("Grace"),
+        },
+        Message::Settings(GameSettings::from_format(MatchFormat::BestOf(5)).with_tie_policy(TiePolicy::HalfPoint)),
+        Message::Rules(RuleSet::lizard_spock().gestures),
+        Message::Round(3),
+        Message::Move(MoveType::Lizard),
+        Message::Commit(Commitment([0x9f; 32])),
+        Message::Reveal(Reveal {
+            move_type: MoveType::Paper,
+            nonce: [0x7c; 32],
+        }),
+        Message::Result {
+            round: 3,
+            moves: PlayerMoves {
+                user_move: MoveType::Rock,
+                enemy_move: MoveType::Scissors,
+            },
+            winner: Winner::User,
+            scores: Scores {
+                user_wins: 2,
+                enemy_wins: 0,
+                ties: 1,
+            },
+        },
+        Message::Over {
+            winner: Winner::Tie,
+            forfeit: false,
+        },
+        Message::Over {
+            winner: Winner::User,
+            forfeit: true,
+        },
+        Message::Error(String::from("Spock is not part of the rules")),
+        Message::Quit,
+    ];
+
+    for message in messages {
+        assert_eq!(message.to_string().parse::<Message>().unwrap(), message);
+    }
+
+    assert_eq!(Message::Round(2).to_string(), "ROUND 2");
+    assert!(matches!("MOVE rock".parse::<Message>(), Err(Error::Protocol(_))));
+    assert!(matches!("HELLO".parse::<Message>(), Err(Error::Protocol(_))));
+    assert_eq!(Message::Queue(String::from("best of 5")).to_string(), "QUEUE best-of-5");
+    assert!(matches!("QUEUE".parse::<Message>(), Err(Error::Protocol(_))));
+}
+
+#[test]
+fn test_match_between_bots() {
+    let (address, server) = start_server(local_server(GameSettings::from_first_to(3)), 1);
+
+    let mut first = connect(address, "Rocky", "default");
+    let second = thread::spawn(move || {
+        let mut client = Client::connect(address, "Cycler").unwrap();
+        client.join_queue("default").unwrap();
+        let result = client.play(&mut CycleStrategy::new(vec![MoveType::Rock, MoveType::Paper, MoveType::Scissors]));
+        (client.player, result)
+    });
+
+    assert_eq!(first.version, 3);
+    assert_eq!(first.receive().unwrap(), Message::Match {
+        opponent: PlayerId(1),
+        name: String::from("Cycler"),
+    });
+    assert_eq!(first.receive().unwrap(), Message::Settings(GameSettings::from_first_to(3)));
+    assert_eq!(first.receive().unwrap(), Message::Rules(RuleSet::classic().gestures));
+
+    let first_result = first.play(&mut ConstantStrategy::new(MoveType::Rock)).unwrap();
+    let (second_id, second_result) = second.join().unwrap();
+
+    // Rock ties Rock, loses to Paper and beats Scissors, so the cycle reaches three wins first.
+    assert_eq!(first_result, Winner::Enemy);
+    assert_eq!(second_result.unwrap(), Winner::User);
+    assert_eq!(second_id, PlayerId(1));
+
+    drop(first);
+    let records = server.join().unwrap().unwrap();
+    let record = &records[0];
+
+    assert_eq!(record.forfeited_by, None);
+    assert_eq!(record.replay.players[0].name, "Rocky");
+    assert_eq!(record.replay.players[1].kind, PlayerKind::Remote);
+    assert_eq!(record.replay.scores.user_wins, 2);
+    assert_eq!(record.replay.scores.enemy_wins, 3);
+    assert_eq!(record.replay.scores.ties, 3);
+    assert!(record.replay.verify().is_ok());
+
+    #[cfg(feature = "serde")]
+    {
+        let json = serde_json::to_string(record).unwrap();
+        assert_eq!(&serde_json::from_str::<MatchRecord>(&json).unwrap(), record);
+    }
+}
+
+#[test]
+fn test_results_are_sent_from_each_players_view() {
+    let (address, server) = start_server(local_server(GameSettings::from_first_to(1)), 1);
+
+    let mut first = connect(address, "A", "default");
+    let mut second = Client::connect(address, "B").unwrap();
+    second.join_queue("default").unwrap();
+
+    for client in [&mut first, &mut second] {
+        for _ in 0..3 {
+            client.receive().unwrap();
+        }
+        assert!(matches!(client.receive().unwrap(), Message::Session(_)));
+        assert_eq!(client.receive().unwrap(), Message::Round(1));
+    }
+
+    first.send(&Message::Move(MoveType::Spock)).unwrap();
+    assert_eq!(first.receive().unwrap(), Message::Error(String::from("Spock is not part of the rules")));
+    first.send(&Message::Move(MoveType::Paper)).unwrap();
+    second.send(&Message::Move(MoveType::Scissors)).unwrap();
+
+    assert_eq!(first.receive().unwrap(), Message::Result {
+        round: 1,
+        moves: PlayerMoves {
+            user_move: MoveType::Paper,
+            enemy_move: MoveType::Scissors,
+        },
+        winner: Winner::Enemy,
+        scores: Scores {
+            user_wins: 0,
+            enemy_wins: 1,
+            ties: 0,
+        },
+    });
+    assert_eq!(second.receive().unwrap(), Message::Result {
+        round: 1,
+        moves: PlayerMoves {
+            user_move: MoveType::Scissors,
+            enemy_move: MoveType::Paper,
+        },
+        winner: Winner::User,
+        scores: Scores {
+            user_wins: 1,
+            enemy_wins: 0,
+            ties: 0,
+        },
+    });
+
+    assert_eq!(first.receive().unwrap(), Message::Over {
+        winner: Winner::Enemy,
+        forfeit: false,
+    });
+    assert_eq!(second.receive().unwrap(), Message::Over {
+        winner: Winner::User,
+        forfeit: false,
+    });
+
+    drop([first, second]);
+    server.join().unwrap().unwrap();
+}
+
+#[test]
+fn test_quitting_forfeits_the_match() {
+    let (address, server) = start_server(local_server(GameSettings::from_first_to(3)), 1);
+
+    let mut quitter = connect(address, "Quitter", "default");
+    let stayer = thread::spawn(move || {
+        let mut client = Client::connect(address, "Stayer").unwrap();
+        client.join_queue("default").unwrap();
+        client.play(&mut ConstantStrategy::new(MoveType::Rock))
+    });
+
+    for _ in 0..4 {
+        quitter.receive().unwrap();
+    }
+    quitter.send(&Message::Quit).unwrap();
+
+    assert_eq!(stayer.join().unwrap().unwrap(), Winner::User);
+
+    drop(quitter);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].forfeited_by, Some(PlayerId(0)));
+    assert!(records[0].replay.rounds.is_empty());
+}
+
+#[test]
+fn test_version_negotiation() {
+    let (address, server) = start_server(local_server(GameSettings::from_first_to(1)), 1);
+
+    let refused = Client::connect_with_versions(address, "Future", &[7, 8]);
+    assert_eq!(refused.err(), Some(Error::Protocol(String::from("No common protocol version"))));
+
+    // A connection that never says HELLO is dropped without stopping the server.
+    let mut stranger = TcpStream::connect(address).unwrap();
+    stranger.write_all(b"GET / HTTP/1.1\n").unwrap();
+    let mut reply = String::new();
+    BufReader::new(&stranger).read_line(&mut reply).unwrap();
+    assert!(reply.starts_with("ERROR"));
+
+    let mut first = connect(address, "One", "default");
+    let second = thread::spawn(move || {
+        let mut client = Client::connect_with_versions(address, "Two", &[1, 7]).unwrap();
+        assert_eq!(client.version, 1);
+        assert_eq!(client.player, PlayerId(1));
+        client.play(&mut ConstantStrategy::new(MoveType::Scissors))
+    });
+
+    assert_eq!(first.player, PlayerId(0));
+    assert_eq!(first.play(&mut ConstantStrategy::new(MoveType::Paper)).unwrap(), Winner::Enemy);
+    assert_eq!(second.join().unwrap().unwrap(), Winner::User);
+
+    drop(first);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].replay.players[0].name, "One");
+}
+
+#[test]
+fn test_match_ends_once_the_scores_are_full() {
+    let settings = GameSettings::from_format(MatchFormat::WinBy { target: 3, margin: 2 });
+    let (address, server) = start_server(local_server(settings), 1);
+
+    // The players trade wins, so the match could go on forever.
+    let mut rocky = connect(address, "Rocky", "default");
+    let trader = thread::spawn(move || {
+        let mut client = Client::connect(address, "Trader").unwrap();
+        client.join_queue("default").unwrap();
+        client.play(&mut CycleStrategy::new(vec![MoveType::Scissors, MoveType::Paper]))
+    });
+
+    assert_eq!(rocky.play(&mut ConstantStrategy::new(MoveType::Rock)).unwrap(), Winner::User);
+    assert_eq!(trader.join().unwrap().unwrap(), Winner::Enemy);
+
+    drop(rocky);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].winner, Winner::User);
+    assert_eq!(records[0].forfeited_by, None);
+    assert_eq!(records[0].replay.scores, Scores {
+        user_wins: 255,
+        enemy_wins: 254,
+        ties: 0,
+    });
+}
+
+#[test]
+fn test_idle_connections_do_not_hold_up_others() {
+    let mut server = local_server(GameSettings::from_first_to(1));
+    server.handshake_timeout = Duration::from_secs(5);
+    let (address, server) = start_server(server, 1);
+    let started = Instant::now();
+
+    // Neither of these ever says HELLO.
+    let idle = [TcpStream::connect(address).unwrap(), TcpStream::connect(address).unwrap()];
+
+    let mut first = connect(address, "First", "default");
+    let second = thread::spawn(move || {
+        let mut client = Client::connect(address, "Second").unwrap();
+        client.join_queue("default").unwrap();
+        client.play(&mut ConstantStrategy::new(MoveType::Rock))
+    });
+
+    assert_eq!(first.play(&mut ConstantStrategy::new(MoveType::Paper)).unwrap(), Winner::User);
+    assert_eq!(second.join().unwrap().unwrap(), Winner::Enemy);
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    drop((first, idle));
+    server.join().unwrap().unwrap();
+}
+
+#[test]
+fn test_players_who_never_move_forfeit() {
+    let server = local_server(GameSettings::from_first_to(3))
+        .with_move_timeout(Duration::from_millis(200))
+        .with_reconnect(Duration::ZERO, ForfeitRule::OpponentWins);
+    let (address, server) = start_server(server, 1);
+    let (mut user, mut enemy) = start_match(address, "default");
+
+    // The enemy stays connected, but never answers the round.
+    user.send(&Message::Move(MoveType::Rock)).unwrap();
+    assert_eq!(user.receive().unwrap(), Message::Waiting);
+    assert_eq!(user.receive().unwrap(), Message::Over {
+        winner: Winner::User,
+        forfeit: true,
+    });
+    assert!(enemy.receive().is_err());
+
+    drop([user, enemy]);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].forfeited_by, Some(PlayerId(1)));
+    assert!(records[0].replay.rounds.is_empty());
+}
+
+#[test]
+fn test_seeded_bots_play_the_same_moves() {
+    let play = || {
+        let (address, server) = start_server(local_server(GameSettings::from_format(MatchFormat::FixedRounds(8))), 1);
+        let mut rocky = connect(address, "Rocky", "default");
+        let random = thread::spawn(move || {
+            let mut client = Client::connect(address, "Random").unwrap();
+            client.join_queue("default").unwrap();
+            client.play_with(&mut RandomStrategy::new(), &mut seeded_rng(11))
+        });
+
+        rocky.play(&mut ConstantStrategy::new(MoveType::Rock)).unwrap();
+        random.join().unwrap().unwrap();
+
+        drop(rocky);
+        let records = server.join().unwrap().unwrap();
+        records[0].replay.rounds.clone()
+    };
+
+    assert_eq!(play(), play());
+}
+
+#[test]
+fn test_rounds_are_played_by_commit_reveal() {
+    let (address, server) = start_server(local_server(GameSettings::from_first_to(2)), 1);
+    let (mut user, mut enemy) = start_match(address, "default");
+    let (rock, scissors) = (Reveal::random(MoveType::Rock), Reveal::random(MoveType::Scissors));
+
+    // Nobody reveals before both players are bound to their moves.
+    user.send(&Message::Reveal(rock)).unwrap();
+    assert!(matches!(user.receive().unwrap(), Message::Error(_)));
+
+    user.send(&Message::Commit(rock.commitment())).unwrap();
+    enemy.send(&Message::Commit(scissors.commitment())).unwrap();
+    assert_eq!(user.receive().unwrap(), Message::Commit(scissors.commitment()));
+    assert_eq!(enemy.receive().unwrap(), Message::Commit(rock.commitment()));
+
+    user.send(&Message::Reveal(rock)).unwrap();
+    enemy.send(&Message::Reveal(scissors)).unwrap();
+    assert_eq!(user.receive().unwrap(), Message::Reveal(scissors));
+    assert_eq!(enemy.receive().unwrap(), Message::Reveal(rock));
+    assert!(matches!(user.receive().unwrap(), Message::Result { winner: Winner::User, .. }));
+    assert!(matches!(enemy.receive().unwrap(), Message::Result { winner: Winner::Enemy, .. }));
+
+    // A player who sends their move outright is committed to it by the server, which reveals it in turn.
+    assert_eq!(user.receive().unwrap(), Message::Round(2));
+    assert_eq!(enemy.receive().unwrap(), Message::Round(2));
+    let paper = Reveal::random(MoveType::Paper);
+    user.send(&Message::Move(MoveType::Rock)).unwrap();
+    enemy.send(&Message::Commit(paper.commitment())).unwrap();
+
+    let Message::Commit(commitment) = enemy.receive().unwrap() else {
+        panic!("Expected the user's commitment");
+    };
+    enemy.send(&Message::Reveal(paper)).unwrap();
+
+    let Message::Reveal(revealed) = enemy.receive().unwrap() else {
+        panic!("Expected the user's reveal");
+    };
+    assert_eq!(revealed.move_type, MoveType::Rock);
+    assert!(revealed.matches(&commitment));
+    assert!(matches!(enemy.receive().unwrap(), Message::Result { winner: Winner::User, .. }));
+    assert!(matches!(user.receive().unwrap(), Message::Result { winner: Winner::Enemy, .. }));
+
+    let enemy = thread::spawn(move || enemy.play(&mut ConstantStrategy::new(MoveType::Paper)));
+    assert_eq!(user.play(&mut ConstantStrategy::new(MoveType::Scissors)).unwrap(), Winner::User);
+    assert_eq!(enemy.join().unwrap().unwrap(), Winner::Enemy);
+
+    drop(user);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].replay.scores.user_wins, 2);
+    assert!(records[0].replay.verify().is_ok());
+}
+
+#[test]
+fn test_breaking_a_commitment_forfeits_the_match() {
+    let (address, server) = start_server(local_server(GameSettings::from_first_to(3)), 1);
+    let (mut user, mut enemy) = start_match(address, "default");
+    let rock = Reveal::random(MoveType::Rock);
+
+    user.send(&Message::Move(MoveType::Scissors)).unwrap();
+    enemy.send(&Message::Commit(rock.commitment())).unwrap();
+    assert!(matches!(enemy.receive().unwrap(), Message::Commit(_)));
+
+    // Having seen nothing of the user's move, the enemy still tries to change its own.
+    let paper = Reveal {
+        move_type: MoveType::Paper,
+        ..rock
+    };
+    enemy.send(&Message::Reveal(paper)).unwrap();
+    assert_eq!(
+        enemy.receive().unwrap(),
+        Message::Error(String::from("The reveal does not match the commitment"))
+    );
+    assert_eq!(user.receive().unwrap(), Message::Over {
+        winner: Winner::User,
+        forfeit: true,
+    });
+
+    drop([user, enemy]);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].winner, Winner::User);
+    assert_eq!(records[0].forfeited_by, Some(PlayerId(1)));
+    assert!(records[0].replay.rounds.is_empty());
+}
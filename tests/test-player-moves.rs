@@ -35,3 +35,17 @@ fn test_check_who_wins_round() {
     assert_eq!(player_moves.check_who_wins_round(), Winner::Enemy);
 }
 
+
+#[test]
+fn test_swapped_player_moves() {
+    let player_moves = PlayerMoves {
+        user_move: MoveType::Rock,
+        enemy_move: MoveType::Scissors,
+    };
+
+    assert_eq!(player_moves.swapped(), PlayerMoves {
+        user_move: MoveType::Scissors,
+        enemy_move: MoveType::Rock,
+    });
+    assert_eq!(player_moves.swapped().check_who_wins_round(), Winner::Enemy);
+}
@@ -0,0 +1,90 @@
+use rock_paper_scissors::*;
+
+#[test]
+fn test_player_constructors() {
+    assert_eq!(Player::human(PlayerId(1), "Ada"), Player {
+        id: PlayerId(1),
+        name: String::from("Ada"),
+        kind: PlayerKind::Human,
+    });
+    assert_eq!(Player::bot(PlayerId(2), "Marvin").kind, PlayerKind::Bot);
+    assert_eq!(Player::remote(PlayerId(3), "Grace").kind, PlayerKind::Remote);
+    assert_eq!(Player::new(PlayerId(4), "Linus", PlayerKind::Human), Player::human(PlayerId(4), "Linus"));
+}
+
+#[test]
+fn test_player_display() {
+    assert_eq!(Player::human(PlayerId(1), "Ada").to_string(), "Ada");
+    assert_eq!(PlayerId(12).to_string(), "#12");
+}
+
+#[test]
+fn test_game_default_players() {
+    let game = Game::new(GameSettings::from_first_to(1));
+
+    assert_eq!(game.players(), &[Player::human(PlayerId(0), "User"), Player::bot(PlayerId(1), "Enemy")]);
+    assert_eq!(game.player(Winner::User).unwrap().id, PlayerId(0));
+    assert_eq!(game.player(Winner::Tie), None);
+    assert_eq!(game.winning_player(), None);
+}
+
+#[test]
+fn test_game_results_by_player() {
+    let mut game = Game::new(GameSettings::from_first_to(2))
+        .with_players(Player::bot(PlayerId(5), "Alpha"), Player::bot(PlayerId(6), "Beta"))
+        .unwrap()
+        .with_opponent(ConstantStrategy::new(MoveType::Paper));
+
+    assert_eq!(game.side(PlayerId(5)), Some(Winner::User));
+    assert_eq!(game.side(PlayerId(6)), Some(Winner::Enemy));
+    assert_eq!(game.side(PlayerId(0)), None);
+
+    game.play_round(MoveType::Scissors).unwrap();
+    game.play_round(MoveType::Rock).unwrap();
+    assert_eq!(game.score(PlayerId(5)), Some(1));
+    assert_eq!(game.score(PlayerId(6)), Some(1));
+    assert_eq!(game.score(PlayerId(0)), None);
+
+    game.play_round(MoveType::Scissors).unwrap();
+    assert_eq!(game.winning_player(), Some(&Player::bot(PlayerId(5), "Alpha")));
+}
+
+#[test]
+fn test_game_reports_winners_by_id() {
+    let mut game = Game::new(GameSettings::from_first_to(2))
+        .with_players(Player::bot(PlayerId(5), "Alpha"), Player::bot(PlayerId(6), "Beta"))
+        .unwrap()
+        .with_opponent(ConstantStrategy::new(MoveType::Paper));
+    let events = game.subscribe();
+
+    let outcome = game.play_round(MoveType::Rock).unwrap();
+    assert_eq!((outcome.winner, outcome.winner_id), (Winner::Enemy, Some(PlayerId(6))));
+    assert_eq!(outcome.match_winner_id, None);
+
+    let outcome = game.play_round(MoveType::Paper).unwrap();
+    assert_eq!(outcome.winner_id, None);
+
+    let outcome = game.play_round(MoveType::Scissors).unwrap();
+    assert_eq!(outcome.winner_id, Some(PlayerId(5)));
+
+    let outcome = game.play_round(MoveType::Rock).unwrap();
+    assert_eq!((outcome.match_winner, outcome.match_winner_id), (Some(Winner::Enemy), Some(PlayerId(6))));
+
+    let winner_ids: Vec<_> = game.history().iter().map(|round| round.winner_id).collect();
+    assert_eq!(winner_ids, vec![Some(PlayerId(6)), None, Some(PlayerId(5)), Some(PlayerId(6))]);
+
+    let events: Vec<GameEvent> = events.try_iter().collect();
+    assert!(matches!(events[2], GameEvent::RoundWon { winner_id: Some(PlayerId(6)), .. }));
+    assert!(matches!(events.last(), Some(GameEvent::MatchWon { winner_id: Some(PlayerId(6)), .. })));
+}
+
+#[test]
+fn test_game_rejects_players_with_the_same_id() {
+    let game = Game::new(GameSettings::from_first_to(2))
+        .with_players(Player::bot(PlayerId(5), "Alpha"), Player::human(PlayerId(5), "Beta"));
+    assert!(matches!(game, Err(Error::InvalidSetting(_))));
+
+    let mut state = Game::new(GameSettings::from_first_to(2)).state();
+    state.players[1].id = PlayerId(0);
+    assert!(matches!(Game::from_state(state), Err(Error::InvalidSave(_))));
+}
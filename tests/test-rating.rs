@@ -0,0 +1,160 @@
+use rock_paper_scissors::*;
+
+fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!((actual - expected).abs() < tolerance, "{actual} is not close to {expected}");
+}
+
+#[test]
+fn test_elo_updates() {
+    let mut elo = Elo::with_k_factor(16.0);
+
+    assert_eq!(elo.rating(PlayerId(0)), 1500.0);
+    assert_eq!(elo.expected_score(PlayerId(0), PlayerId(1)), 0.5);
+
+    elo.record(PlayerId(0), PlayerId(1), 1.0);
+    assert_eq!(elo.rating(PlayerId(0)), 1508.0);
+    assert_eq!(elo.rating(PlayerId(1)), 1492.0);
+
+    elo.record(PlayerId(0), PlayerId(1), 0.5);
+    assert!(elo.rating(PlayerId(0)) < 1508.0);
+    assert_close(elo.rating(PlayerId(0)) + elo.rating(PlayerId(1)), 3000.0, 1e-9);
+}
+
+#[test]
+fn test_elo_expected_score() {
+    let mut elo = Elo::new();
+    elo.set_rating(PlayerId(0), 1900.0);
+    elo.set_rating(PlayerId(1), 1500.0);
+
+    assert_close(elo.expected_score(PlayerId(0), PlayerId(1)), 10.0 / 11.0, 1e-9);
+    assert_close(elo.expected_score(PlayerId(1), PlayerId(0)), 1.0 / 11.0, 1e-9);
+}
+
+#[test]
+fn test_glicko2_matches_glickman_example() {
+    let mut glicko = Glicko2::new();
+    glicko.set_rating(PlayerId(0), Glicko2Rating {
+        rating: 1500.0,
+        deviation: 200.0,
+        volatility: 0.06,
+    });
+    glicko.set_rating(PlayerId(1), Glicko2Rating {
+        rating: 1400.0,
+        deviation: 30.0,
+        volatility: 0.06,
+    });
+    glicko.set_rating(PlayerId(2), Glicko2Rating {
+        rating: 1550.0,
+        deviation: 100.0,
+        volatility: 0.06,
+    });
+    glicko.set_rating(PlayerId(3), Glicko2Rating {
+        rating: 1700.0,
+        deviation: 300.0,
+        volatility: 0.06,
+    });
+
+    glicko.record(PlayerId(0), PlayerId(1), 1.0);
+    glicko.record(PlayerId(0), PlayerId(2), 0.0);
+    glicko.record(PlayerId(0), PlayerId(3), 0.0);
+    assert_eq!(glicko.pending_matches(), 3);
+
+    glicko.end_period();
+
+    let rating = glicko.rating(PlayerId(0));
+    assert_close(rating.rating, 1464.06, 0.01);
+    assert_close(rating.deviation, 151.52, 0.01);
+    assert_close(rating.volatility, 0.05999, 0.00001);
+    assert_eq!(glicko.pending_matches(), 0);
+}
+
+#[test]
+fn test_glicko2_idle_players_become_uncertain() {
+    let mut glicko = Glicko2::new();
+    glicko.record(PlayerId(0), PlayerId(1), 1.0);
+    glicko.end_period();
+
+    glicko.record(PlayerId(0), PlayerId(2), 1.0);
+    let idle = glicko.rating(PlayerId(1));
+    glicko.end_period();
+
+    assert!(glicko.rating(PlayerId(1)).deviation > idle.deviation);
+    assert_eq!(glicko.rating(PlayerId(1)).rating, idle.rating);
+}
+
+#[test]
+fn test_glicko2_period_length() {
+    let mut glicko = Glicko2::with_period_length(2);
+
+    glicko.record(PlayerId(0), PlayerId(1), 1.0);
+    assert_eq!(glicko.rating(PlayerId(0)).rating, 1500.0);
+
+    glicko.record(PlayerId(0), PlayerId(1), 1.0);
+    assert_eq!(glicko.pending_matches(), 0);
+    assert!(glicko.rating(PlayerId(0)).rating > 1500.0);
+}
+
+#[test]
+fn test_leaderboard() {
+    let mut elo = Elo::new();
+    elo.record(PlayerId(2), PlayerId(0), 1.0);
+    elo.record(PlayerId(2), PlayerId(1), 0.5);
+
+    let board = elo.leaderboard();
+
+    assert_eq!(board.iter().map(|entry| entry.player).collect::<Vec<_>>(), vec![
+        PlayerId(2),
+        PlayerId(1),
+        PlayerId(0)
+    ]);
+    assert_eq!(board[0].rank, 1);
+    assert_eq!(board[0].matches, 2);
+    assert_eq!(board[2].matches, 1);
+}
+
+#[test]
+fn test_record_game_and_scores() {
+    let mut game = Game::new(GameSettings::from_first_to(1))
+        .with_players(Player::human(PlayerId(7), "Ada"), Player::bot(PlayerId(8), "Marvin"))
+        .unwrap()
+        .with_opponent(ConstantStrategy::new(MoveType::Scissors));
+    let mut elo = Elo::new();
+
+    assert!(matches!(elo.record_game(&game), Err(Error::InvalidScores(_))));
+
+    game.play_round(MoveType::Rock).unwrap();
+    elo.record_game(&game).unwrap();
+    assert!(elo.rating(PlayerId(7)) > elo.rating(PlayerId(8)));
+
+    let before = elo.rating(PlayerId(7));
+
+    let settings = GameSettings::from_first_to(2);
+    let scores = Scores {
+        user_wins: 1,
+        enemy_wins: 2,
+        ties: 0,
+    };
+    elo.record_scores(PlayerId(7), PlayerId(8), &scores, &settings).unwrap();
+    assert!(elo.rating(PlayerId(7)) < before);
+    assert_eq!(elo.leaderboard()[0].matches, 2);
+}
+
+#[test]
+fn test_record_tournament_matches() {
+    let mut tournament = Tournament::with_seed(TournamentFormat::RoundRobin, GameSettings::from_first_to(3), 3);
+    tournament.add_bot("Rock", ConstantStrategy::new(MoveType::Rock));
+    tournament.add_bot("Paper", ConstantStrategy::new(MoveType::Paper));
+    tournament.add_bot("Stone", ConstantStrategy::new(MoveType::Rock));
+
+    let report = tournament.run().unwrap();
+    let mut glicko = Glicko2::new();
+
+    for result in &report.matches {
+        glicko.record_match(result);
+    }
+
+    glicko.end_period();
+
+    assert_eq!(glicko.leaderboard()[0].player, PlayerId(1));
+    assert!(glicko.leaderboard().iter().all(|entry| entry.matches == 2));
+}
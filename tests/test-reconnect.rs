@@ -0,0 +1,283 @@
+mod common;
+
+use common::{connect, local_server, play_round, skip_to_round, start_match, start_server, Serving};
+use rock_paper_scissors::*;
+use std::net::{SocketAddr, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+fn start_reconnect_server(grace: Duration, rule: ForfeitRule) -> (SocketAddr, Serving) {
+    start_server(local_server(GameSettings::from_first_to(3)).with_reconnect(grace, rule), 1)
+}
+
+#[test]
+fn test_resume_keeps_round_scores_and_pending_moves() {
+    let (address, server) = start_reconnect_server(Duration::from_secs(10), ForfeitRule::OpponentWins);
+    let (mut user, mut enemy) = start_match(address, "default");
+    let (enemy_id, token) = (enemy.player, enemy.session.clone().unwrap());
+
+    play_round(&mut user, &mut enemy, MoveType::Paper, MoveType::Rock);
+    skip_to_round(&mut user, 2);
+    skip_to_round(&mut enemy, 2);
+
+    // The user's move for round 2 is in when the enemy drops out.
+    user.send(&Message::Move(MoveType::Scissors)).unwrap();
+    drop(enemy);
+    assert_eq!(user.receive().unwrap(), Message::Waiting);
+
+    let mut enemy = Client::connect(address, "").unwrap();
+    enemy.send(&Message::Resume(token)).unwrap();
+
+    assert_eq!(enemy.receive().unwrap(), Message::Resumed {
+        player: enemy_id,
+        round: 2,
+        scores: Scores {
+            user_wins: 0,
+            enemy_wins: 1,
+            ties: 0,
+        },
+        commitment: None,
+    });
+    assert_eq!(enemy.receive().unwrap(), Message::Match {
+        opponent: user.player,
+        name: String::from("User"),
+    });
+    assert_eq!(enemy.receive().unwrap(), Message::Settings(GameSettings::from_first_to(3)));
+    assert_eq!(enemy.receive().unwrap(), Message::Rules(RuleSet::classic().gestures));
+    assert_eq!(enemy.receive().unwrap(), Message::Round(2));
+
+    enemy.send(&Message::Move(MoveType::Paper)).unwrap();
+    assert_eq!(user.receive().unwrap(), Message::Result {
+        round: 2,
+        moves: PlayerMoves {
+            user_move: MoveType::Scissors,
+            enemy_move: MoveType::Paper,
+        },
+        winner: Winner::User,
+        scores: Scores {
+            user_wins: 2,
+            enemy_wins: 0,
+            ties: 0,
+        },
+    });
+
+    let enemy = thread::spawn(move || enemy.play(&mut ConstantStrategy::new(MoveType::Scissors)));
+    assert_eq!(user.play(&mut ConstantStrategy::new(MoveType::Rock)).unwrap(), Winner::User);
+    assert_eq!(enemy.join().unwrap().unwrap(), Winner::Enemy);
+
+    drop(user);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].winner, Winner::User);
+    assert_eq!(records[0].forfeited_by, None);
+    assert_eq!(records[0].replay.players[1].id, enemy_id);
+    assert!(records[0].replay.verify().is_ok());
+}
+
+#[test]
+fn test_client_resume_between_rounds() {
+    let (address, server) = start_reconnect_server(Duration::from_secs(10), ForfeitRule::OpponentWins);
+    let (mut user, mut enemy) = start_match(address, "default");
+    let (enemy_id, token) = (enemy.player, enemy.session.clone().unwrap());
+
+    user.send(&Message::Move(MoveType::Rock)).unwrap();
+    enemy.send(&Message::Move(MoveType::Paper)).unwrap();
+    assert!(matches!(enemy.receive().unwrap(), Message::Result { .. }));
+    drop(enemy);
+
+    let user = thread::spawn(move || user.play(&mut ConstantStrategy::new(MoveType::Rock)));
+
+    let mut enemy = Client::resume(address, &token).unwrap();
+    assert_eq!(enemy.player, enemy_id);
+    assert_eq!(enemy.session, Some(token));
+
+    assert_eq!(enemy.play(&mut ConstantStrategy::new(MoveType::Paper)).unwrap(), Winner::User);
+    assert_eq!(user.join().unwrap().unwrap(), Winner::Enemy);
+
+    drop(enemy);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].replay.scores.enemy_wins, 3);
+    assert_eq!(records[0].forfeited_by, None);
+}
+
+#[test]
+fn test_forfeit_rules() {
+    let cases = [
+        (ForfeitRule::OpponentWins, Winner::User, Winner::User),
+        (ForfeitRule::LeaderWins, Winner::Enemy, Winner::Enemy),
+        (ForfeitRule::Draw, Winner::Tie, Winner::Tie),
+    ];
+
+    for (rule, over, winner) in cases {
+        let (address, server) = start_reconnect_server(Duration::from_millis(100), rule);
+        let (mut user, mut enemy) = start_match(address, "default");
+
+        // The enemy leads 1-0 when it drops out.
+        play_round(&mut user, &mut enemy, MoveType::Scissors, MoveType::Rock);
+        skip_to_round(&mut user, 2);
+        user.send(&Message::Move(MoveType::Rock)).unwrap();
+        drop(enemy);
+
+        assert_eq!(user.receive().unwrap(), Message::Waiting);
+        assert_eq!(user.receive().unwrap(), Message::Over {
+            winner: over,
+            forfeit: true,
+        });
+
+        drop(user);
+        let records = server.join().unwrap().unwrap();
+        assert_eq!(records[0].winner, winner);
+        assert_eq!(records[0].forfeited_by, Some(PlayerId(1)));
+    }
+}
+
+#[test]
+fn test_resume_errors() {
+    let mut server = Server::bind("127.0.0.1:0", GameSettings::from_first_to(3))
+        .unwrap()
+        .with_reconnect(Duration::from_millis(100), ForfeitRule::OpponentWins);
+    let address = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+
+    assert_eq!(
+        Client::resume(address, "00112233445566778899aabbccddeeff").err(),
+        Some(Error::Protocol(String::from("There is no match to resume for this session")))
+    );
+
+    let (mut user, enemy) = start_match(address, "default");
+    let token = enemy.session.clone().unwrap();
+    drop(enemy);
+
+    user.send(&Message::Move(MoveType::Rock)).unwrap();
+    assert_eq!(user.play(&mut ConstantStrategy::new(MoveType::Rock)).unwrap(), Winner::User);
+
+    // The session ends with the match.
+    assert_eq!(
+        Client::resume(address, &token).err(),
+        Some(Error::Protocol(String::from("There is no match to resume for this session")))
+    );
+}
+
+#[test]
+fn test_older_clients_forfeit_at_once() {
+    let (address, server) = start_reconnect_server(Duration::from_secs(60), ForfeitRule::OpponentWins);
+
+    let mut user = connect(address, "User", "default");
+
+    let mut enemy = Client::connect_with_versions(address, "Old", &[1, 2]).unwrap();
+    enemy.join_queue("default").unwrap();
+    skip_to_round(&mut enemy, 1);
+    assert_eq!(enemy.session, None);
+    drop(enemy);
+
+    assert_eq!(user.play(&mut ConstantStrategy::new(MoveType::Rock)).unwrap(), Winner::User);
+
+    drop(user);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].forfeited_by, Some(PlayerId(1)));
+}
+
+#[test]
+fn test_idle_connections_do_not_hold_up_a_resume() {
+    let (address, server) = start_reconnect_server(Duration::from_secs(2), ForfeitRule::OpponentWins);
+    let (mut user, enemy) = start_match(address, "default");
+    let token = enemy.session.clone().unwrap();
+
+    // These never say HELLO, and each would keep the server busy for its whole handshake timeout if it waited on
+    // them in turn.
+    let idle: Vec<TcpStream> = (0..3).map(|_| TcpStream::connect(address).unwrap()).collect();
+    user.send(&Message::Move(MoveType::Rock)).unwrap();
+    drop(enemy);
+    assert_eq!(user.receive().unwrap(), Message::Waiting);
+
+    let mut enemy = Client::resume(address, &token).unwrap();
+    let enemy = thread::spawn(move || enemy.play(&mut ConstantStrategy::new(MoveType::Scissors)));
+    assert_eq!(user.play(&mut ConstantStrategy::new(MoveType::Rock)).unwrap(), Winner::User);
+    assert_eq!(enemy.join().unwrap().unwrap(), Winner::Enemy);
+
+    drop((user, idle));
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].forfeited_by, None);
+}
+
+#[test]
+fn test_resume_keeps_pending_commitments() {
+    let (address, server) = start_reconnect_server(Duration::from_secs(10), ForfeitRule::OpponentWins);
+    let (mut user, mut enemy) = start_match(address, "default");
+    let (enemy_id, token) = (enemy.player, enemy.session.clone().unwrap());
+    let (paper, rock) = (Reveal::random(MoveType::Paper), Reveal::random(MoveType::Rock));
+
+    // The enemy drops out after committing, before it is asked to reveal.
+    enemy.send(&Message::Commit(rock.commitment())).unwrap();
+    drop(enemy);
+
+    user.send(&Message::Commit(paper.commitment())).unwrap();
+    assert_eq!(user.receive().unwrap(), Message::Commit(rock.commitment()));
+    user.send(&Message::Reveal(paper)).unwrap();
+    assert_eq!(user.receive().unwrap(), Message::Waiting);
+
+    let mut enemy = Client::connect(address, "").unwrap();
+    enemy.send(&Message::Resume(token)).unwrap();
+    assert_eq!(enemy.receive().unwrap(), Message::Resumed {
+        player: enemy_id,
+        round: 1,
+        scores: Scores::new(),
+        commitment: Some(rock.commitment()),
+    });
+
+    for _ in 0..3 {
+        enemy.receive().unwrap();
+    }
+
+    // The user's commitment asks for the reveal again, which the client makes from the move it committed to.
+    enemy.pending = Some(rock);
+    let enemy = thread::spawn(move || enemy.play(&mut ConstantStrategy::new(MoveType::Scissors)));
+
+    assert_eq!(user.receive().unwrap(), Message::Reveal(rock));
+    assert!(matches!(user.receive().unwrap(), Message::Result { winner: Winner::User, .. }));
+    assert_eq!(user.play(&mut ConstantStrategy::new(MoveType::Rock)).unwrap(), Winner::User);
+    assert_eq!(enemy.join().unwrap().unwrap(), Winner::Enemy);
+
+    drop(user);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].forfeited_by, None);
+    assert_eq!(records[0].replay.rounds[0].moves.enemy_move, MoveType::Rock);
+}
+
+#[test]
+fn test_brand_new_client_resumes_after_commit() {
+    let (address, server) = start_reconnect_server(Duration::from_secs(10), ForfeitRule::OpponentWins);
+    let (mut user, mut enemy) = start_match(address, "default");
+    let (enemy_id, token) = (enemy.player, enemy.session.clone().unwrap());
+
+    // The enemy commits to its move and loses its connection along with the client, keeping only what it wrote down.
+    let rock = Reveal::random(MoveType::Rock);
+    enemy.send(&Message::Commit(rock.commitment())).unwrap();
+    let written_down = rock.to_string();
+    drop(enemy);
+
+    let paper = Reveal::random(MoveType::Paper);
+    user.send(&Message::Commit(paper.commitment())).unwrap();
+    assert_eq!(user.receive().unwrap(), Message::Commit(rock.commitment()));
+    user.send(&Message::Reveal(paper)).unwrap();
+    assert_eq!(user.receive().unwrap(), Message::Waiting);
+
+    // A client without the reveal cannot go on with the round.
+    let forgotten = Client::resume(address, &token).err();
+    assert!(matches!(forgotten, Some(Error::Protocol(_))));
+    let wrong = Client::resume_with(address, &token, Some(Reveal::random(MoveType::Rock))).err();
+    assert!(matches!(wrong, Some(Error::Protocol(_))));
+
+    let mut enemy = Client::resume_with(address, &token, Some(written_down.parse().unwrap())).unwrap();
+    assert_eq!((enemy.player, enemy.pending), (enemy_id, Some(rock)));
+    let enemy = thread::spawn(move || enemy.play(&mut ConstantStrategy::new(MoveType::Scissors)));
+
+    while user.receive().unwrap() != Message::Reveal(rock) {}
+    assert!(matches!(user.receive().unwrap(), Message::Result { winner: Winner::User, .. }));
+    assert_eq!(user.play(&mut ConstantStrategy::new(MoveType::Rock)).unwrap(), Winner::User);
+    assert_eq!(enemy.join().unwrap().unwrap(), Winner::Enemy);
+
+    drop(user);
+    let records = server.join().unwrap().unwrap();
+    assert_eq!(records[0].forfeited_by, None);
+    assert_eq!(records[0].replay.rounds[0].moves.enemy_move, MoveType::Rock);
+}
@@ -0,0 +1,115 @@
+use rock_paper_scissors::*;
+
+fn finished_game(settings: GameSettings, seed: u64) -> Game {
+    let mut game = Game::with_seed(settings, seed)
+        .with_rules(RuleSet::lizard_spock())
+        .with_players(Player::human(PlayerId(4), "Ada Lovelace"), Player::bot(PlayerId(9), "Marvin"))
+        .unwrap()
+        .with_opponent(MarkovStrategy::with_rules(RuleSet::lizard_spock(), 1));
+    let user_moves = [MoveType::Rock, MoveType::Spock, MoveType::Lizard, MoveType::Rock, MoveType::Paper];
+
+    for user_move in user_moves.iter().cycle() {
+        if game.is_over() {
+            break;
+        }
+
+        game.play_round(*user_move).unwrap();
+    }
+
+    game
+}
+
+#[test]
+fn test_replay_round_trip() {
+    let game = finished_game(GameSettings::from_format(MatchFormat::WinBy { target: 3, margin: 2 }), 12);
+    let replay = Replay::from_game(&game);
+    let text = replay.to_string();
+
+    assert!(text.starts_with("rock-paper-scissors replay 1\nseed 12\n"));
+    assert!(text.contains("player 4 human Ada Lovelace\n"));
+    assert!(text.contains("format win-by 3 2\n"));
+    assert!(text.ends_with(&format!("sha256 {}\n", replay.hash())));
+
+    let parsed = Replay::parse(&text).unwrap();
+    assert_eq!(parsed, replay);
+
+    let replayed = parsed.verify().unwrap();
+    assert_eq!(replayed.history(), game.history());
+    assert_eq!(replayed.winning_player(), game.winning_player());
+}
+
+#[test]
+fn test_replay_reproduces_coin_flip_tiebreaks() {
+    let settings = GameSettings::from_first_to(3).with_tie_policy(TiePolicy::CapThenTiebreak {
+        max_ties: 0,
+        tiebreaker: Tiebreaker::CoinFlip,
+    });
+    let mut game = Game::with_seed(settings, 99);
+
+    while !game.is_over() {
+        game.play_round(MoveType::Rock).unwrap();
+    }
+
+    let replay = Replay::parse(&Replay::from_game(&game).to_string()).unwrap();
+
+    assert_eq!(replay.verify().unwrap().scores(), game.scores());
+}
+
+#[test]
+fn test_replay_detects_tampering() {
+    let game = finished_game(GameSettings::from_first_to(3), 3);
+    let text = Replay::from_game(&game).to_string();
+
+    let tampered = text.replacen("seed 3", "seed 4", 1);
+    assert_eq!(
+        Replay::parse(&tampered),
+        Err(Error::InvalidReplay(String::from("The replay does not match its hash")))
+    );
+
+    let unhashed = text.lines().filter(|line| !line.starts_with("sha256")).collect::<Vec<_>>().join("\n");
+    assert!(Replay::parse(&unhashed).is_err());
+
+    let newer = text.replacen("replay 1", "replay 2", 1);
+    assert!(matches!(Replay::parse(&newer), Err(Error::InvalidReplay(_))));
+}
+
+#[test]
+fn test_verify_detects_wrong_records() {
+    let game = finished_game(GameSettings::from_first_to(3), 8);
+    let replay = Replay::from_game(&game);
+
+    let mut wrong_winner = replay.clone();
+    wrong_winner.rounds[0].winner = match replay.rounds[0].winner {
+        Winner::User => Winner::Enemy,
+        _ => Winner::User,
+    };
+    let error = Replay::parse(&wrong_winner.to_string()).unwrap().verify().err().unwrap();
+    assert!(error.to_string().contains("Round 1 was recorded as won by"));
+
+    let mut wrong_scores = replay.clone();
+    wrong_scores.scores.ties += 1;
+    assert!(matches!(wrong_scores.verify(), Err(Error::InvalidReplay(_))));
+
+    let mut extra_round = replay.clone();
+    extra_round.rounds.push(replay.rounds[0]);
+    assert_eq!(
+        extra_round.verify().err().unwrap(),
+        Error::InvalidReplay(format!("Round {} was played after the match was decided", replay.rounds.len() + 1))
+    );
+}
+
+#[test]
+fn test_replay_files() {
+    let game = finished_game(GameSettings::from_format(MatchFormat::BestOf(5)), 30);
+    let replay = Replay::from_game(&game);
+    let path = std::env::temp_dir().join("rock-paper-scissors-replay-test.rps");
+
+    replay.save(&path).unwrap();
+    let loaded = Replay::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, replay);
+
+    let mut output = Vec::new();
+    replay.write_to(&mut output).unwrap();
+    assert_eq!(Replay::read_from(&mut output.as_slice()).unwrap(), replay);
+}
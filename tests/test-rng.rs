@@ -0,0 +1,65 @@
+use rock_paper_scissors::*;
+
+fn play_match(seed: u64, strategy: &mut dyn Strategy, user_moves: &[MoveType]) -> Vec<PlayerMoves> {
+    let mut rng = seeded_rng(seed);
+    let mut history = Vec::new();
+
+    for user_move in user_moves {
+        let enemy_move = strategy.next_move_with(&history, &mut rng);
+        history.push(PlayerMoves {
+            user_move: *user_move,
+            enemy_move,
+        });
+    }
+
+    history
+}
+
+#[test]
+fn test_seeded_random_moves_repeat() {
+    let mut first = seeded_rng(1);
+    let mut second = seeded_rng(1);
+
+    let first_moves: Vec<MoveType> = (0..50).map(|_| MoveType::random_move_with(&mut first)).collect();
+    let second_moves: Vec<MoveType> = (0..50).map(|_| MoveType::random_move_with(&mut second)).collect();
+
+    assert_eq!(first_moves, second_moves);
+}
+
+#[test]
+fn test_different_seeds_differ() {
+    let rules = RuleSet::balanced(101).unwrap();
+    let mut first = seeded_rng(1);
+    let mut second = seeded_rng(2);
+
+    let first_moves: Vec<MoveType> = (0..20).map(|_| rules.random_move_with(&mut first)).collect();
+    let second_moves: Vec<MoveType> = (0..20).map(|_| rules.random_move_with(&mut second)).collect();
+
+    assert_ne!(first_moves, second_moves);
+}
+
+#[test]
+fn test_seeded_strategies_are_reproducible() {
+    let user_moves: Vec<MoveType> = [MoveType::Rock, MoveType::Paper, MoveType::Paper, MoveType::Scissors]
+        .iter()
+        .cycle()
+        .take(40)
+        .copied()
+        .collect();
+
+    let strategies: Vec<fn() -> Box<dyn Strategy>> = vec![
+        || Box::new(RandomStrategy::with_rules(RuleSet::lizard_spock())),
+        || Box::new(BeatLastStrategy::with_rules(RuleSet::lizard_spock())),
+        || Box::new(CopyLastStrategy::new()),
+        || Box::new(MarkovStrategy::new(2)),
+        || Box::new(EnsembleStrategy::new()),
+        || Box::new(MixedStrategy::uniform(&RuleSet::classic())),
+    ];
+
+    for strategy in strategies {
+        let first = play_match(99, &mut *strategy(), &user_moves);
+        let second = play_match(99, &mut *strategy(), &user_moves);
+
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,103 @@
+use rock_paper_scissors::*;
+
+#[test]
+fn test_rule_set_default_is_classic() {
+    assert_eq!(RuleSet::default(), RuleSet::classic());
+}
+
+#[test]
+fn test_classic_matches_check_who_wins_round() {
+    let rules = RuleSet::classic();
+
+    for user_move in &rules.gestures {
+        for enemy_move in &rules.gestures {
+            let player_moves = PlayerMoves {
+                user_move: *user_move,
+                enemy_move: *enemy_move,
+            };
+
+            assert_eq!(player_moves.check_who_wins_round_with(&rules), player_moves.check_who_wins_round());
+        }
+    }
+}
+
+#[test]
+fn test_lizard_spock_is_balanced() {
+    let rules = RuleSet::lizard_spock();
+
+    for gesture in &rules.gestures {
+        let wins = rules.gestures.iter().filter(|other| rules.beats(gesture, other)).count();
+        let losses = rules.gestures.iter().filter(|other| rules.beats(other, gesture)).count();
+
+        assert_eq!(wins, 2);
+        assert_eq!(losses, 2);
+    }
+}
+
+#[test]
+fn test_invalid_moves_lose() {
+    let rules = RuleSet::classic();
+
+    assert_eq!(rules.check_who_wins(&MoveType::None, &MoveType::Rock), Winner::Enemy);
+    assert_eq!(rules.check_who_wins(&MoveType::Spock, &MoveType::Rock), Winner::Enemy);
+}
+
+#[test]
+fn test_balanced_rule_sets() {
+    for gesture_count in [7, 15, 25, 101] {
+        let rules = RuleSet::balanced(gesture_count).unwrap();
+
+        assert_eq!(rules.gestures.len(), gesture_count as usize);
+
+        for gesture in &rules.gestures {
+            let wins = rules.gestures.iter().filter(|other| rules.beats(gesture, other)).count();
+
+            assert_eq!(wins, (gesture_count as usize - 1) / 2);
+            assert!(!rules.beats(gesture, gesture));
+        }
+    }
+
+    assert_eq!(RuleSet::balanced(3), Ok(RuleSet::classic()));
+    assert_eq!(RuleSet::balanced(5), Ok(RuleSet::lizard_spock()));
+    assert!(RuleSet::balanced(1).is_err());
+    assert!(RuleSet::balanced(8).is_err());
+}
+
+#[test]
+fn test_balanced_agrees_with_lizard_spock() {
+    let generated = RuleSet::balanced(7).unwrap();
+    let lizard_spock = RuleSet::lizard_spock();
+
+    for winner in &lizard_spock.gestures {
+        for loser in &lizard_spock.gestures {
+            assert_eq!(generated.beats(winner, loser), lizard_spock.beats(winner, loser));
+        }
+    }
+}
+
+#[test]
+fn test_move_index_round_trip() {
+    for index in 0..=u8::MAX {
+        assert_eq!(MoveType::from_index(index).index(), Some(index));
+    }
+}
+
+#[test]
+fn test_parse_move() {
+    let rules = RuleSet::balanced(25).unwrap();
+
+    assert_eq!(rules.parse_move("1"), Some(MoveType::Rock));
+    assert_eq!(rules.parse_move("25"), Some(MoveType::Gesture(24)));
+    assert_eq!(rules.parse_move("26"), None);
+    assert_eq!(rules.parse_move("0"), None);
+    assert_eq!(rules.parse_move("gesture 12"), Some(MoveType::Gesture(11)));
+    assert_eq!(rules.parse_move("SPOCK"), Some(MoveType::Spock));
+}
+
+#[test]
+fn test_describe() {
+    let rules = RuleSet::classic();
+
+    assert_eq!(rules.describe(&MoveType::Paper, &MoveType::Rock), Some("Paper covers Rock".to_string()));
+    assert_eq!(rules.describe(&MoveType::Rock, &MoveType::Paper), None);
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "serde")]
+
+use rock_paper_scissors::*;
+
+fn played_game(seed: u64) -> Game {
+    let mut game = Game::with_seed(GameSettings::from_first_to(10), seed)
+        .with_rules(RuleSet::lizard_spock())
+        .with_players(Player::human(PlayerId(3), "Ada"), Player::bot(PlayerId(4), "Marvin"))
+        .unwrap()
+        .with_opponent(MarkovStrategy::with_rules(RuleSet::lizard_spock(), 1));
+
+    for user_move in [MoveType::Rock, MoveType::Spock, MoveType::Rock, MoveType::Lizard] {
+        game.play_round(user_move).unwrap();
+    }
+
+    game
+}
+
+fn assert_resumes(game: &mut Game, resumed: Game) {
+    let mut resumed = resumed.with_opponent(MarkovStrategy::with_rules(RuleSet::lizard_spock(), 1));
+
+    assert_eq!(resumed.state(), game.state());
+
+    for user_move in [MoveType::Paper, MoveType::Rock, MoveType::Scissors] {
+        assert_eq!(resumed.play_round(user_move), game.play_round(user_move));
+    }
+}
+
+#[test]
+fn test_json_round_trip() {
+    let mut game = played_game(21);
+    let json = game.to_json().unwrap();
+
+    assert!(json.contains("\"seed\": \"21\""));
+    assert!(json.contains("Marvin"));
+
+    assert_resumes(&mut game, Game::from_json(&json).unwrap());
+}
+
+#[test]
+fn test_toml_round_trip_with_large_seed() {
+    let mut game = played_game(u64::MAX);
+    let text = game.to_toml().unwrap();
+
+    assert!(text.contains("18446744073709551615"));
+
+    assert_resumes(&mut game, Game::from_toml(&text).unwrap());
+}
+
+#[test]
+fn test_save_and_load_files() {
+    let directory = std::env::temp_dir();
+
+    for name in ["rock-paper-scissors-save-test.json", "rock-paper-scissors-save-test.toml"] {
+        let path = directory.join(name);
+        let mut game = played_game(5);
+
+        game.save(&path).unwrap();
+        let loaded = Game::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_resumes(&mut game, loaded);
+    }
+
+    assert!(matches!(Game::load(directory.join("rock-paper-scissors-missing.json")), Err(Error::Io(_))));
+}
+
+#[test]
+fn test_rejects_invalid_saves() {
+    assert!(matches!(Game::from_json("{ \"seed\": 1 }"), Err(Error::InvalidSave(_))));
+    assert!(matches!(Game::from_toml("seed = 1"), Err(Error::InvalidSave(_))));
+
+    let json = played_game(1).to_json().unwrap();
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value["scores"]["user_wins"] = serde_json::Value::from(11);
+
+    assert!(matches!(Game::from_json(&value.to_string()), Err(Error::InvalidScores(_))));
+}
+
+#[test]
+fn test_public_types_round_trip() {
+    let rules = RuleSet::lizard_spock();
+    let json = serde_json::to_string(&rules).unwrap();
+    assert_eq!(serde_json::from_str::<RuleSet>(&json).unwrap(), rules);
+
+    let settings = GameSettings::from_format(MatchFormat::WinBy { target: 5, margin: 2 })
+        .with_tie_policy(TiePolicy::CapThenTiebreak {
+            max_ties: 3,
+            tiebreaker: Tiebreaker::CoinFlip,
+        });
+    let text = toml::to_string(&settings).unwrap();
+    assert_eq!(toml::from_str::<GameSettings>(&text).unwrap(), settings);
+
+    let mut elo = Elo::new();
+    elo.record(PlayerId(0), PlayerId(1), 1.0);
+    let json = serde_json::to_string(&elo).unwrap();
+    assert_eq!(serde_json::from_str::<Elo>(&json).unwrap(), elo);
+
+    let mut strategy = MarkovStrategy::new(1);
+    strategy.next_move(&[PlayerMoves::new()]);
+    let restored: MarkovStrategy = serde_json::from_str(&serde_json::to_string(&strategy).unwrap()).unwrap();
+    assert_eq!(restored, MarkovStrategy::new(1));
+}
@@ -7,16 +7,19 @@ fn test_scores_new() {
     assert_eq!(scores, Scores {
         user_wins: 0,
         enemy_wins: 0,
+        ties: 0,
     });
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_check_for_winner() {
     let game_settings = GameSettings::from_first_to(3);
 
     let scores = Scores {
         user_wins: 3,
         enemy_wins: 1,
+        ties: 0,
     };
 
     assert_eq!(scores.check_for_winner(&game_settings), Ok(Winner::User));
@@ -24,6 +27,7 @@ fn test_check_for_winner() {
     let scores = Scores {
         user_wins: 2,
         enemy_wins: 3,
+        ties: 0,
     };
 
     assert_eq!(scores.check_for_winner(&game_settings), Ok(Winner::Enemy));
@@ -31,6 +35,7 @@ fn test_check_for_winner() {
     let scores = Scores {
         user_wins: 0,
         enemy_wins: 2,
+        ties: 0,
     };
 
     assert_eq!(scores.check_for_winner(&game_settings), Err("rock-paper-scissors: err: No winner yet"));
@@ -41,6 +46,7 @@ fn test_scores_reset() {
     let mut scores = Scores {
         user_wins: 3,
         enemy_wins: 2,
+        ties: 0,
     };
 
     scores.reset();
@@ -48,5 +54,17 @@ fn test_scores_reset() {
     assert_eq!(scores, Scores {
         user_wins: 0,
         enemy_wins: 0,
+        ties: 0,
     });
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_winner() {
+    let game_settings = GameSettings::from_first_to(2);
+    let mut scores = Scores::new();
+
+    assert_eq!(scores.winner(&game_settings), None);
+
+    scores.user_wins = 2;
+    assert_eq!(scores.winner(&game_settings), Some(Winner::User));
+}
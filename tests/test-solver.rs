@@ -0,0 +1,76 @@
+use rock_paper_scissors::*;
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "expected {}, got {}", expected, actual);
+}
+
+#[test]
+fn test_classic_equilibrium_is_uniform() {
+    let equilibrium = PayoffMatrix::from_rules(&RuleSet::classic()).solve();
+
+    assert_close(equilibrium.value, 0.0);
+
+    for gesture in RuleSet::classic().gestures {
+        assert_close(equilibrium.user_strategy.probability(&gesture), 1.0 / 3.0);
+        assert_close(equilibrium.enemy_strategy.probability(&gesture), 1.0 / 3.0);
+    }
+}
+
+#[test]
+fn test_balanced_equilibrium_is_uniform() {
+    let rules = RuleSet::balanced(7).unwrap();
+    let payoffs = PayoffMatrix::from_rules(&rules);
+    let equilibrium = payoffs.solve();
+
+    assert_close(equilibrium.value, 0.0);
+    assert!(payoffs.enemy_exploitability(&equilibrium.enemy_strategy) < 1e-9);
+    assert!(payoffs.enemy_exploitability(&MixedStrategy::uniform(&rules)) < 1e-9);
+}
+
+#[test]
+fn test_weighted_rock_equilibrium() {
+    let mut payoffs = PayoffMatrix::from_rules(&RuleSet::classic());
+    payoffs.set_win_value(&MoveType::Rock, 2.0);
+
+    let equilibrium = payoffs.solve();
+
+    // Rock 1/4, Paper 1/2, Scissors 1/4 makes every reply earn zero on average.
+    assert_close(equilibrium.value, 0.0);
+    assert_close(equilibrium.enemy_strategy.probability(&MoveType::Rock), 0.25);
+    assert_close(equilibrium.enemy_strategy.probability(&MoveType::Paper), 0.5);
+    assert_close(equilibrium.enemy_strategy.probability(&MoveType::Scissors), 0.25);
+    assert!(payoffs.user_exploitability(&equilibrium.user_strategy) < 1e-9);
+    assert!(payoffs.enemy_exploitability(&MixedStrategy::uniform(&RuleSet::classic())) > 0.1);
+}
+
+#[test]
+fn test_asymmetric_game_value() {
+    let payoffs = PayoffMatrix::new(
+        vec![MoveType::Rock, MoveType::Paper],
+        vec![vec![3.0, -1.0], vec![-2.0, 1.0]],
+    )
+    .unwrap();
+
+    let equilibrium = payoffs.solve();
+
+    // The user mixes 3/7 Rock, the enemy mixes 2/7 Rock, and the value is 1/7.
+    assert_close(equilibrium.value, 1.0 / 7.0);
+    assert_close(equilibrium.user_strategy.probability(&MoveType::Rock), 3.0 / 7.0);
+    assert_close(equilibrium.enemy_strategy.probability(&MoveType::Rock), 2.0 / 7.0);
+    assert_close(
+        payoffs.expected_payoff(&equilibrium.user_strategy, &equilibrium.enemy_strategy),
+        1.0 / 7.0,
+    );
+}
+
+#[test]
+fn test_equilibrium_strategy_as_opponent() {
+    let mut payoffs = PayoffMatrix::from_rules(&RuleSet::classic());
+    payoffs.set_win_value(&MoveType::Scissors, 5.0);
+
+    let mut enemy = payoffs.solve().enemy_strategy;
+
+    for _ in 0..20 {
+        assert!(RuleSet::classic().contains(&enemy.next_move(&[])));
+    }
+}
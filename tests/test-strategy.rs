@@ -0,0 +1,77 @@
+use rock_paper_scissors::*;
+
+fn round(user_move: MoveType, enemy_move: MoveType) -> PlayerMoves {
+    PlayerMoves {
+        user_move,
+        enemy_move,
+    }
+}
+
+#[test]
+fn test_random_strategy() {
+    let mut strategy = RandomStrategy::new();
+
+    for _ in 0..20 {
+        assert!(RuleSet::classic().contains(&strategy.next_move(&[])));
+    }
+}
+
+#[test]
+fn test_constant_strategy() {
+    let mut strategy = ConstantStrategy::new(MoveType::Spock);
+    let history = [round(MoveType::Rock, MoveType::Spock)];
+
+    assert_eq!(strategy.next_move(&[]), MoveType::Spock);
+    assert_eq!(strategy.next_move(&history), MoveType::Spock);
+}
+
+#[test]
+fn test_cycle_strategy() {
+    let mut strategy = CycleStrategy::new(vec![MoveType::Rock, MoveType::Paper, MoveType::Scissors]);
+    let mut history = Vec::new();
+    let mut played = Vec::new();
+
+    for _ in 0..6 {
+        let enemy_move = strategy.next_move(&history);
+        played.push(enemy_move);
+        history.push(round(MoveType::Rock, enemy_move));
+    }
+
+    assert_eq!(played, vec![
+        MoveType::Rock,
+        MoveType::Paper,
+        MoveType::Scissors,
+        MoveType::Rock,
+        MoveType::Paper,
+        MoveType::Scissors,
+    ]);
+
+    assert_eq!(CycleStrategy::new(Vec::new()).next_move(&history), MoveType::None);
+}
+
+#[test]
+fn test_copy_last_strategy() {
+    let mut strategy = CopyLastStrategy::new();
+    let history = [round(MoveType::Rock, MoveType::Paper), round(MoveType::Scissors, MoveType::Rock)];
+
+    assert!(RuleSet::classic().contains(&strategy.next_move(&[])));
+    assert_eq!(strategy.next_move(&history), MoveType::Scissors);
+}
+
+#[test]
+fn test_beat_last_strategy() {
+    let mut strategy = BeatLastStrategy::with_rules(RuleSet::lizard_spock());
+    let history = [round(MoveType::Lizard, MoveType::Rock)];
+
+    for _ in 0..20 {
+        let enemy_move = strategy.next_move(&history);
+        assert!(matches!(enemy_move, MoveType::Rock | MoveType::Scissors));
+    }
+}
+
+#[test]
+fn test_boxed_strategy() {
+    let mut strategy: Box<dyn Strategy> = Box::new(ConstantStrategy::new(MoveType::Paper));
+
+    assert_eq!(strategy.next_move(&[round(MoveType::Rock, MoveType::Rock)]), MoveType::Paper);
+}
@@ -0,0 +1,87 @@
+use rock_paper_scissors::*;
+
+fn scores(user_wins: u8, enemy_wins: u8, ties: u8) -> Scores {
+    Scores {
+        user_wins,
+        enemy_wins,
+        ties,
+    }
+}
+
+#[test]
+fn test_replay_ignores_ties_for_round_limits() {
+    let settings = GameSettings::from_format(MatchFormat::FixedRounds(3)).with_tie_policy(TiePolicy::Replay);
+
+    assert_eq!(scores(1, 1, 5).winner(&settings), None);
+    assert_eq!(scores(2, 1, 5).winner(&settings), Some(Winner::User));
+    assert_eq!(scores(2, 1, 5).evaluate(&settings), Ok(Some(Winner::User)));
+}
+
+#[test]
+fn test_award_nobody_counts_ties_as_rounds() {
+    let settings = GameSettings::from_format(MatchFormat::FixedRounds(3));
+
+    assert_eq!(settings.tie_policy, TiePolicy::AwardNobody);
+    assert_eq!(scores(1, 1, 1).winner(&settings), Some(Winner::Tie));
+    assert!(scores(1, 1, 5).evaluate(&settings).is_err());
+}
+
+#[test]
+fn test_half_point() {
+    let settings = GameSettings::from_first_to(3).with_tie_policy(TiePolicy::HalfPoint);
+
+    assert_eq!(scores(2, 1, 1).winner(&settings), None);
+    assert_eq!(scores(2, 1, 2).winner(&settings), Some(Winner::User));
+    assert_eq!(scores(1, 1, 4).winner(&settings), Some(Winner::Tie));
+
+    // Two wins and two ties already make three points, so 3-0 with two ties overshoots the match.
+    assert_eq!(scores(2, 1, 3).evaluate(&settings), Ok(Some(Winner::User)));
+    assert!(scores(3, 0, 2).evaluate(&settings).is_err());
+}
+
+#[test]
+fn test_tiebreaker_decide() {
+    let mut rng = seeded_rng(3);
+
+    assert_eq!(Tiebreaker::FavorUser.decide(&mut rng), Winner::User);
+    assert_eq!(Tiebreaker::FavorEnemy.decide(&mut rng), Winner::Enemy);
+
+    let flips: Vec<Winner> = (0..50).map(|_| Tiebreaker::CoinFlip.decide(&mut rng)).collect();
+    assert!(flips.contains(&Winner::User));
+    assert!(flips.contains(&Winner::Enemy));
+    assert!(!flips.contains(&Winner::Tie));
+}
+
+#[test]
+fn test_game_breaks_ties_after_the_cap() {
+    let settings = GameSettings::from_first_to(2).with_tie_policy(TiePolicy::CapThenTiebreak {
+        max_ties: 2,
+        tiebreaker: Tiebreaker::FavorEnemy,
+    });
+    let mut game = Game::new(settings).with_opponent(ConstantStrategy::new(MoveType::Rock));
+    let events = game.subscribe();
+
+    assert_eq!(game.play_round(MoveType::Rock).unwrap().winner, Winner::Tie);
+    assert_eq!(game.play_round(MoveType::Rock).unwrap().winner, Winner::Tie);
+
+    let outcome = game.play_round(MoveType::Rock).unwrap();
+    assert_eq!(outcome.winner, Winner::Enemy);
+    assert_eq!(outcome.scores, scores(0, 1, 2));
+    assert!(events.try_iter().any(|event| event == GameEvent::TiebreakDecided { round: 3, winner: Winner::Enemy }));
+
+    // The tiebreak ended the run of ties, so the next tie counts normally again.
+    assert_eq!(game.play_round(MoveType::Rock).unwrap().winner, Winner::Tie);
+    assert_eq!(game.play_round(MoveType::Paper).unwrap().match_winner, None);
+    assert_eq!(game.scores(), &scores(1, 1, 3));
+}
+
+#[test]
+fn test_game_with_zero_cap_breaks_every_tie() {
+    let settings = GameSettings::from_first_to(1).with_tie_policy(TiePolicy::CapThenTiebreak {
+        max_ties: 0,
+        tiebreaker: Tiebreaker::FavorUser,
+    });
+    let mut game = Game::new(settings).with_opponent(ConstantStrategy::new(MoveType::Paper));
+
+    assert_eq!(game.play_round(MoveType::Paper).unwrap().match_winner, Some(Winner::User));
+}
@@ -0,0 +1,205 @@
+use rock_paper_scissors::*;
+
+fn constant_bots(format: TournamentFormat, moves: &[MoveType]) -> Tournament {
+    let mut tournament = Tournament::with_seed(format, GameSettings::from_first_to(2), 7);
+    tournament.max_rounds = 10;
+
+    for (index, move_type) in moves.iter().enumerate() {
+        tournament.add_bot(&format!("Bot {index}"), ConstantStrategy::new(*move_type));
+    }
+
+    tournament
+}
+
+fn ranking(report: &TournamentReport) -> Vec<PlayerId> {
+    report.standings.iter().map(|standing| standing.player.id).collect()
+}
+
+#[test]
+fn test_round_robin_standings() {
+    let mut tournament = constant_bots(TournamentFormat::RoundRobin, &[
+        MoveType::Rock,
+        MoveType::Paper,
+        MoveType::Rock,
+        MoveType::Scissors,
+    ]);
+    let report = tournament.run().unwrap();
+
+    assert_eq!(report.matches.len(), 6);
+    assert!(report.byes.is_empty());
+    assert_eq!(ranking(&report), vec![PlayerId(1), PlayerId(0), PlayerId(2), PlayerId(3)]);
+
+    let paper = &report.standings[0];
+    assert_eq!((paper.points, paper.wins, paper.draws, paper.losses), (2.0, 2, 0, 1));
+
+    let rock = &report.standings[1];
+    assert_eq!((rock.points, rock.wins, rock.draws, rock.losses), (1.5, 1, 1, 1));
+    assert_eq!(rock.head_to_head, 0.5);
+    assert_eq!(rock.buchholz, 2.0 + 1.5 + 1.0);
+    assert_eq!(rock.rank, 2);
+    assert_eq!(rock.eliminated_in, None);
+
+    let draw = report.matches.iter().find(|m| m.winner.is_none()).unwrap();
+    assert!(draw.players.contains(&PlayerId(0)) && draw.players.contains(&PlayerId(2)));
+    assert_eq!(draw.scores.ties, 10);
+    assert!(!draw.tiebreak);
+}
+
+#[test]
+fn test_round_robin_byes() {
+    let mut tournament = constant_bots(TournamentFormat::RoundRobin, &[MoveType::Rock, MoveType::Paper, MoveType::Scissors]);
+    let report = tournament.run().unwrap();
+
+    assert_eq!(report.matches.len(), 3);
+    assert_eq!(report.byes.len(), 3);
+    assert!(report.standings.iter().all(|standing| standing.byes == 1 && standing.points == 1.0));
+}
+
+#[test]
+fn test_single_elimination() {
+    let mut tournament = constant_bots(TournamentFormat::SingleElimination, &[
+        MoveType::Paper,
+        MoveType::Rock,
+        MoveType::Scissors,
+        MoveType::Rock,
+    ]);
+    let report = tournament.run().unwrap();
+
+    assert_eq!(report.matches.len(), 3);
+    assert_eq!(report.matches[0].players, [PlayerId(0), PlayerId(3)]);
+    assert_eq!(report.matches[1].players, [PlayerId(1), PlayerId(2)]);
+
+    let champion = &report.standings[0];
+    assert_eq!(champion.player.id, PlayerId(0));
+    assert_eq!(champion.eliminated_in, None);
+    assert_eq!(report.standings[1].eliminated_in, Some(2));
+    assert_eq!(report.standings[2].eliminated_in, Some(1));
+    assert_eq!(report.standings[3].eliminated_in, Some(1));
+}
+
+#[test]
+fn test_single_elimination_byes_and_tiebreaks() {
+    let mut tournament = constant_bots(TournamentFormat::SingleElimination, &[
+        MoveType::Rock,
+        MoveType::Rock,
+        MoveType::Rock,
+    ]);
+    let report = tournament.run().unwrap();
+
+    assert_eq!(report.byes, vec![(1, PlayerId(0))]);
+    assert_eq!(report.matches.len(), 2);
+    assert!(report.matches.iter().all(|m| m.tiebreak && m.winner.is_some()));
+    assert_eq!(report.standings.iter().filter(|s| s.eliminated_in.is_none()).count(), 1);
+}
+
+#[test]
+fn test_double_elimination() {
+    let mut tournament = constant_bots(TournamentFormat::DoubleElimination, &[
+        MoveType::Paper,
+        MoveType::Rock,
+        MoveType::Scissors,
+        MoveType::Rock,
+        MoveType::Paper,
+    ]);
+    let report = tournament.run().unwrap();
+
+    let champion = &report.standings[0];
+    assert_eq!(champion.eliminated_in, None);
+    assert!(champion.losses <= 1);
+    assert!(report.standings[1..].iter().all(|s| s.losses == 2 && s.eliminated_in.is_some()));
+}
+
+#[test]
+fn test_swiss_avoids_rematches() {
+    let mut tournament = Tournament::with_seed(TournamentFormat::Swiss { rounds: 3 }, GameSettings::from_first_to(3), 11);
+
+    for index in 0..6 {
+        tournament.add_bot(&format!("Bot {index}"), RandomStrategy::new());
+    }
+
+    let report = tournament.run().unwrap();
+
+    assert_eq!(report.matches.len(), 9);
+    assert!(report.byes.is_empty());
+
+    for (index, a) in report.matches.iter().enumerate() {
+        for b in &report.matches[index + 1..] {
+            let mut pair_a = a.players;
+            let mut pair_b = b.players;
+            pair_a.sort();
+            pair_b.sort();
+            assert_ne!(pair_a, pair_b);
+        }
+    }
+
+    let points: f64 = report.standings.iter().map(|s| s.points).sum();
+    assert_eq!(points, 9.0);
+}
+
+#[test]
+fn test_swiss_byes_go_to_different_players() {
+    let mut tournament = constant_bots(TournamentFormat::Swiss { rounds: 3 }, &[
+        MoveType::Rock,
+        MoveType::Paper,
+        MoveType::Scissors,
+        MoveType::Rock,
+        MoveType::Paper,
+    ]);
+    let report = tournament.run().unwrap();
+
+    assert_eq!(report.byes.len(), 3);
+    assert_eq!(report.matches.len(), 6);
+
+    let mut bye_players: Vec<PlayerId> = report.byes.iter().map(|(_, player)| *player).collect();
+    bye_players.dedup();
+    assert_eq!(bye_players.len(), 3);
+
+    for standing in &report.standings {
+        assert_eq!(standing.points, standing.wins as f64 + standing.draws as f64 / 2.0 + standing.byes as f64);
+    }
+}
+
+#[test]
+fn test_same_seed_same_tournament() {
+    let run = || {
+        let mut tournament = Tournament::with_seed(TournamentFormat::DoubleElimination, GameSettings::from_first_to(3), 5);
+        tournament.add_bot("Random", RandomStrategy::new());
+        tournament.add_bot("Counter", BeatLastStrategy::new());
+        tournament.add_bot("Copy", CopyLastStrategy::new());
+        tournament.add_bot("Markov", MarkovStrategy::new(1));
+        tournament.run().unwrap()
+    };
+
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn test_tournament_errors() {
+    let mut tournament = Tournament::new(TournamentFormat::RoundRobin, GameSettings::new());
+    tournament.add_bot("Alone", RandomStrategy::new());
+
+    assert!(matches!(tournament.run(), Err(Error::InvalidSetting(_))));
+
+    tournament.add_entrant(Player::bot(PlayerId(0), "Twin"), RandomStrategy::new());
+
+    assert!(matches!(tournament.run(), Err(Error::InvalidSetting(_))));
+    assert_eq!(tournament.players().len(), 2);
+}
+
+#[test]
+fn test_win_by_match_that_never_ends_is_settled() {
+    let settings = GameSettings::from_format(MatchFormat::WinBy { target: 3, margin: 2 });
+    let mut tournament = Tournament::with_seed(TournamentFormat::RoundRobin, settings, 3);
+    let rocky = tournament.add_bot("Rocky", ConstantStrategy::new(MoveType::Rock));
+    tournament.add_bot("Trader", CycleStrategy::new(vec![MoveType::Scissors, MoveType::Paper]));
+
+    // The bots trade wins, so only the limit of the scores stops the match.
+    let report = tournament.run().unwrap();
+
+    assert_eq!(report.matches[0].scores, Scores {
+        user_wins: 255,
+        enemy_wins: 254,
+        ties: 0,
+    });
+    assert_eq!(report.matches[0].winner, Some(rocky));
+}
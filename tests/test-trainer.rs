@@ -0,0 +1,79 @@
+use rock_paper_scissors::*;
+
+#[test]
+fn test_trainer_converges_on_weighted_rock() {
+    let mut payoffs = PayoffMatrix::from_rules(&RuleSet::classic());
+    payoffs.set_win_value(&MoveType::Rock, 2.0);
+
+    let mut trainer = RegretTrainer::new(payoffs.clone());
+    let reports = trainer.train(5000);
+
+    assert_eq!(reports.len(), 5000);
+    assert_eq!(reports[4999].iteration, 5000);
+    assert!(reports[4999].exploitability < reports[9].exploitability);
+    assert!(reports[4999].exploitability < 0.02);
+
+    let enemy = trainer.average_enemy_strategy();
+    assert!((enemy.probability(&MoveType::Paper) - 0.5).abs() < 0.02);
+    assert!(payoffs.enemy_exploitability(&enemy) < 0.02);
+}
+
+#[test]
+fn test_trainer_handles_larger_rule_sets() {
+    let rules = RuleSet::balanced(7).unwrap();
+    let mut trainer = RegretTrainer::from_rules(&rules);
+    trainer.train(100);
+
+    assert!(trainer.exploitability() < 1e-9);
+
+    for gesture in &rules.gestures {
+        assert!((trainer.average_user_strategy().probability(gesture) - 1.0 / 7.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_trained_strategy_round_trip() {
+    let mut payoffs = PayoffMatrix::from_rules(&RuleSet::lizard_spock());
+    payoffs.set_win_value(&MoveType::Spock, 3.0);
+
+    let mut trainer = RegretTrainer::new(payoffs);
+    trainer.train(200);
+
+    let exported = trainer.average_enemy_strategy().export(&RuleSet::lizard_spock());
+    let mut loaded = MixedStrategy::import(&exported, &RuleSet::lizard_spock()).unwrap();
+
+    for gesture in RuleSet::lizard_spock().gestures {
+        let expected = trainer.average_enemy_strategy().probability(&gesture);
+        assert!((loaded.probability(&gesture) - expected).abs() < 1e-12);
+    }
+
+    assert!(RuleSet::lizard_spock().contains(&loaded.next_move(&[])));
+}
+
+#[test]
+fn test_import_rejects_bad_input() {
+    assert!(MixedStrategy::import("Fire 1.0", &RuleSet::classic()).is_err());
+    assert!(MixedStrategy::import("Rock", &RuleSet::classic()).is_err());
+    assert!(MixedStrategy::import("Rock -1", &RuleSet::classic()).is_err());
+}
+
+#[test]
+fn test_export_round_trip_with_named_gestures() {
+    let mut rules = RuleSet::balanced(7).unwrap();
+    rules.names.insert(MoveType::Gesture(5), String::from("Fire"));
+    rules.names.insert(MoveType::Gesture(6), String::from("Water Balloon"));
+
+    let strategy = MixedStrategy::new(vec![
+        (MoveType::Gesture(5), 1.0),
+        (MoveType::Gesture(6), 2.0),
+        (MoveType::Rock, 1.0),
+    ]);
+    let exported = strategy.export(&rules);
+    assert!(exported.starts_with("Fire 0.25\nWater Balloon 0.5\n"));
+
+    let loaded = MixedStrategy::import(&exported, &rules).unwrap();
+
+    for (gesture, _) in &strategy.weights {
+        assert_eq!(loaded.probability(gesture), strategy.probability(gesture));
+    }
+}